@@ -0,0 +1,137 @@
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+import pytest
+
+from wake.config import WakeConfig
+from wake.development.chain_interfaces import AnvilChainInterface
+from wake.development.globals import chain_interfaces_manager
+from wake.testing.core import Chain
+
+
+class FakeNode:
+    """
+    JSON-RPC communicator emulating a local Anvil node without accounts. New blocks are only mined by `mine`.
+    Requests are dispatched to `handlers` by method name, requests with unknown methods return `None`.
+    """
+
+    chain_id: int
+    # lowercase address => transaction count
+    nonces: Dict[str, int]
+    requests: List[str]
+    handlers: Dict[str, Callable[..., Any]]
+    # hashes of blocks of the canonical chain indexed by block number
+    _block_hashes: List[str]
+    _snapshots: List[int]
+    _mined_count: int
+
+    def __init__(self, chain_id: int = 31337):
+        self.chain_id = chain_id
+        self.nonces = {}
+        self.requests = []
+        self.handlers = {
+            "web3_clientVersion": lambda: "anvil/0.2.0",
+            "eth_chainId": lambda: hex(self.chain_id),
+            "anvil_nodeInfo": lambda: {"hardFork": "CANCUN", "forkConfig": {}},
+            "anvil_setChainId": self._set_chain_id,
+            "eth_accounts": lambda: [],
+            "eth_blockNumber": lambda: hex(self.block_number),
+            "eth_getTransactionCount": lambda address, block_identifier: hex(
+                self.nonces.get(address.lower(), 0)
+            ),
+            "eth_getBlockByNumber": self._get_block,
+            "evm_snapshot": self._snapshot,
+            "evm_revert": self._revert,
+        }
+        self._block_hashes = [self._block_hash(0)]
+        self._snapshots = []
+        self._mined_count = 0
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        pass
+
+    @property
+    def block_number(self) -> int:
+        return len(self._block_hashes) - 1
+
+    def mine(self) -> None:
+        # blocks mined after a revert get new hashes like in a real node
+        self._mined_count += 1
+        self._block_hashes.append(self._block_hash(self._mined_count))
+
+    def send_request(self, method: str, params: Optional[List] = None) -> Any:
+        self.requests.append(method)
+        handler = self.handlers.get(method)
+        if handler is None:
+            return None
+        return handler(*(params or []))
+
+    def send_batch_request(self, requests: List[Tuple[str, Optional[List]]]) -> List:
+        return [self.send_request(method, params) for method, params in requests]
+
+    @staticmethod
+    def _block_hash(i: int) -> str:
+        return "0x" + format(i, "064x")
+
+    def _get_block(self, block_identifier: str, include_transactions: bool = False):
+        if block_identifier == "pending":
+            number = self.block_number + 1
+        elif block_identifier == "earliest":
+            number = 0
+        elif block_identifier.startswith("0x"):
+            number = int(block_identifier, 16)
+        else:
+            number = self.block_number
+        if number > self.block_number + 1:
+            return None
+
+        block_hash = self._block_hashes[number] if number <= self.block_number else None
+        parent_hash = self._block_hashes[number - 1] if number > 0 else "0x" + "00" * 32
+        return {
+            "number": hex(number),
+            "hash": block_hash,
+            "parentHash": parent_hash,
+            "timestamp": hex(1_000 + number),
+            "gasLimit": hex(30_000_000),
+            "gasUsed": "0x0",
+            "baseFeePerGas": "0x0",
+            "transactions": [],
+        }
+
+    def _snapshot(self) -> str:
+        self._snapshots.append(self.block_number)
+        return hex(len(self._snapshots) - 1)
+
+    def _revert(self, snapshot_id: str) -> bool:
+        index = int(snapshot_id, 16)
+        if index >= len(self._snapshots):
+            return False
+        del self._block_hashes[self._snapshots[index] + 1 :]
+        # the snapshot and all later snapshots are removed
+        del self._snapshots[index:]
+        return True
+
+    def _set_chain_id(self, chain_id: int) -> None:
+        self.chain_id = chain_id
+
+
+@pytest.fixture
+def node(monkeypatch) -> FakeNode:
+    node = FakeNode()
+    chain_interface = AnvilChainInterface(WakeConfig(), node)  # pyright: ignore
+    monkeypatch.setattr(
+        chain_interfaces_manager,
+        "get_or_create",
+        lambda *args, **kwargs: chain_interface,
+    )
+    monkeypatch.setattr(chain_interfaces_manager, "free", lambda chain_interface: None)
+    return node
+
+
+@pytest.fixture
+def chain(node: FakeNode):
+    """
+    Testing chain connected to `node`.
+    """
+    chain = Chain()
+    with chain.connect():
+        yield chain
@@ -0,0 +1,62 @@
+from enum import IntEnum
+
+import pytest
+
+import wake.development.core
+from wake.development.core import _validate_abi_value
+
+
+class Status(IntEnum):
+    ACTIVE = 0
+    PAUSED = 1
+
+
+class Other(IntEnum):
+    A = 0
+
+
+ENUM_ABI = {"internalType": "enum Vault.Status", "name": "s", "type": "uint8"}
+
+
+@pytest.fixture
+def enums_index(monkeypatch):
+    monkeypatch.setattr(
+        wake.development.core,
+        "enums_index",
+        {
+            "Vault.Status": [(__name__, ("Status",), 2)],
+            "Other": [(__name__, ("Other",), 1)],
+        },
+    )
+
+
+def test_enum_member_accepted(enums_index):
+    _validate_abi_value(ENUM_ABI, Status.PAUSED, "s")
+    # plain integers are only bounds-checked
+    _validate_abi_value(ENUM_ABI, 1, "s")
+
+
+def test_enum_value_out_of_members_rejected(enums_index):
+    with pytest.raises(ValueError, match=r"out of bounds: 2 not in \[0, 1\]"):
+        _validate_abi_value(ENUM_ABI, 2, "s")
+    with pytest.raises(ValueError, match="out of bounds"):
+        _validate_abi_value(
+            {"internalType": "enum Vault.Status[]", "type": "uint8[]"}, [0, 5], "s"
+        )
+
+
+def test_enum_of_other_type_rejected(enums_index):
+    with pytest.raises(ValueError, match="must be a member of enum Vault.Status"):
+        _validate_abi_value(ENUM_ABI, Other.A, "s")
+
+
+def test_enum_not_in_index_not_checked(monkeypatch):
+    monkeypatch.setattr(wake.development.core, "enums_index", {})
+    _validate_abi_value(ENUM_ABI, Other.A, "s")
+
+
+def test_int_out_of_bounds():
+    with pytest.raises(ValueError, match="out of bounds"):
+        _validate_abi_value(ENUM_ABI, 256, "s")
+    with pytest.raises(ValueError, match="out of bounds"):
+        _validate_abi_value({"internalType": "int8", "type": "int8"}, -129, "x")
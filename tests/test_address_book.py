@@ -0,0 +1,54 @@
+import pytest
+
+from wake.config import WakeConfig
+from wake.development.core import Account, Address, _signer_account
+from wake.development.globals import get_config, set_config
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+USDC = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+
+
+@pytest.fixture
+def address_config():
+    config = get_config()
+    set_config(WakeConfig.fromdict({"addresses": {31337: {"USDC": USDC}}}))
+    yield
+    set_config(config)
+
+
+def test_address_book(address_config, chain: Chain):
+    addresses = chain.addresses
+
+    assert addresses.USDC == Account(USDC, chain)
+    assert addresses["USDC"].address == Address(USDC)
+    assert "USDC" in addresses and len(addresses) == 1
+    with pytest.raises(AttributeError):
+        addresses.DAI
+
+    assert chain.addresses is addresses
+
+
+def test_address_book_of_other_chain_empty(address_config, node: FakeNode):
+    node.chain_id = 10
+    chain = Chain()
+    with chain.connect():
+        assert len(chain.addresses) == 0
+
+
+def test_address_book_account_impersonated_for_tx(
+    address_config, chain: Chain, node: FakeNode
+):
+    node.handlers["eth_sendTransaction"] = lambda tx: "0x" + "00" * 32
+    usdc = chain.addresses.USDC
+    node.requests.clear()
+
+    with _signer_account(usdc):
+        chain.chain_interface.send_transaction({"from": str(usdc.address)})
+
+    assert node.requests == [
+        "anvil_impersonateAccount",
+        "eth_sendTransaction",
+        "anvil_stopImpersonatingAccount",
+    ]
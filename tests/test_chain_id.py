@@ -0,0 +1,70 @@
+import pytest
+
+from wake.development.core import Account, ReplayProtectionError
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def test_change_chain_id_restored(chain: Chain, node: FakeNode):
+    with pytest.raises(RuntimeError):
+        with chain.change_chain_id(10):
+            assert chain.chain_id == 10
+            assert node.chain_id == 10
+            raise RuntimeError()
+
+    assert chain.chain_id == 31337
+    assert node.chain_id == 31337
+
+
+def test_chain_id_restored_on_revert(chain: Chain, node: FakeNode):
+    snapshot = chain.snapshot()
+
+    with chain.change_chain_id(10):
+        # revert to a snapshot taken before the chain ID was changed
+        chain.revert(snapshot)
+        assert chain.chain_id == 31337
+        assert node.chain_id == 31337
+        node.requests.clear()
+
+    # nothing to restore when the context ends
+    assert "anvil_setChainId" not in node.requests
+    assert chain.chain_id == 31337
+
+
+def test_chain_id_override_per_call(chain: Chain, node: FakeNode):
+    chain_ids = []
+
+    def call(params, block):
+        chain_ids.append(node.chain_id)
+        return "0x"
+
+    node.handlers["eth_call"] = call
+    account = Account("0x" + "22" * 20, chain)
+
+    account.call(from_=account, chain_id_override=10)
+    assert chain_ids == [10]
+    assert chain.chain_id == 31337
+    assert node.chain_id == 31337
+
+    account.call(from_=account)
+    assert chain_ids == [10, 31337]
+
+
+def test_replay_protection_uses_cached_chain_id(chain: Chain, node: FakeNode):
+    sender = "0x" + "22" * 20
+    node.requests.clear()
+
+    chain._check_replay_protection({"from": sender, "chainId": 31337})
+    assert "eth_chainId" not in node.requests
+
+    with pytest.raises(ReplayProtectionError):
+        chain._check_replay_protection({"from": sender, "chainId": 1})
+
+
+def test_chain_id_override_requires_mined_tx(chain: Chain, node: FakeNode):
+    account = Account("0x" + "22" * 20, chain)
+
+    with pytest.raises(ValueError, match="mined synchronously"):
+        account.transact(from_=account, confirmations=0, chain_id_override=10)
+    assert "anvil_setChainId" not in node.requests
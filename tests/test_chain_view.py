@@ -0,0 +1,57 @@
+import pytest
+
+from wake.development.core import Account
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def _view_call(chain: Chain):
+    # stands in for a generated pytypes function accepting `request_type` and `block`
+    def call(request_type, block, chain_id_override=None):
+        assert request_type == "call"
+        return Account(1, chain).call(
+            from_=Account(2, chain),
+            gas_limit="auto",
+            block=block,
+            chain_id_override=chain_id_override,
+        )
+
+    return call
+
+
+def test_view_call_does_not_touch_shared_state(chain: Chain, node: FakeNode):
+    call_params = []
+
+    def call(params, block):
+        call_params.append((params, block))
+        return "0x"
+
+    node.handlers["eth_estimateGas"] = lambda params, block: hex(50_000)
+    node.handlers["eth_call"] = call
+    chain._last_gas_estimate = 12_345
+    journal_size = chain._journal.stats.entries
+
+    view = chain.view()
+    node.mine()
+    view.call(_view_call(chain))
+
+    params, block = call_params[0]
+    assert block == hex(view.block_number)
+    assert "nonce" not in params
+    assert Account(2, chain).address not in chain._nonces
+    assert chain._last_gas_estimate == 12_345
+    assert chain._journal.stats.entries == journal_size
+
+    # requests outside of the view still build full transactions
+    Account(1, chain).call(from_=Account(2, chain), gas_limit="auto")
+    assert "nonce" in call_params[1][0]
+    assert chain._last_gas_estimate == 50_000
+
+
+def test_view_call_rejects_chain_id_override(chain: Chain, node: FakeNode):
+    node.handlers["eth_call"] = lambda params, block: "0x"
+
+    with pytest.raises(ValueError):
+        chain.view().call(_view_call(chain), chain_id_override=1)
+    assert "anvil_setChainId" not in node.requests
@@ -0,0 +1,98 @@
+import hashlib
+
+from wake.development.core import CurveKey, KeyCurve
+from wake.development.globals import random
+from wake.testing.core import Chain
+
+# RFC 6979 A.2.5, ECDSA with P-256 and SHA-256, message "sample"
+PRIVATE_KEY = 0xC9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721
+PUBLIC_KEY_XY = (
+    0x60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6,
+    0x7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299,
+)
+R = 0xEFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716
+S = 0xF7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8
+N = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
+
+
+# RFC 8032 7.1, test 1
+ED25519_PRIVATE_KEY = bytes.fromhex(
+    "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+)
+ED25519_PUBLIC_KEY = bytes.fromhex(
+    "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+)
+ED25519_SIGNATURE = bytes.fromhex(
+    "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+)
+
+
+def _signature(r: int, s: int) -> bytes:
+    return r.to_bytes(32, "big") + s.to_bytes(32, "big")
+
+
+def test_secp256r1_rfc6979_vector():
+    key = CurveKey.from_key(KeyCurve.SECP256R1, PRIVATE_KEY)
+    assert key.public_key_xy == PUBLIC_KEY_XY
+
+    # s of the test vector is in the upper half of the curve order and gets normalized
+    signature = key.sign(b"sample")
+    assert signature == _signature(R, N - S)
+    assert signature == key.sign_hash(hashlib.sha256(b"sample").digest())
+
+    assert key.verify(b"sample", signature)
+    assert not key.verify(b"test", signature)
+
+
+def test_secp256r1_verify_like_precompile():
+    key = CurveKey.from_key(KeyCurve.SECP256R1, PRIVATE_KEY)
+    data_hash = hashlib.sha256(b"sample").digest()
+
+    # RIP-7212 does not require low s values
+    assert key.verify_hash(data_hash, _signature(R, S))
+    assert key.verify_hash(data_hash, _signature(R, N - S))
+
+    assert not key.verify_hash(data_hash, _signature(0, S))
+    assert not key.verify_hash(data_hash, _signature(R, N))
+    assert not key.verify_hash(data_hash, _signature(R, S)[:63])
+
+    assert key.precompile_input(data_hash, _signature(R, S)) == (
+        data_hash
+        + _signature(R, S)
+        + PUBLIC_KEY_XY[0].to_bytes(32, "big")
+        + PUBLIC_KEY_XY[1].to_bytes(32, "big")
+    )
+
+
+def test_secp256r1_random_keys(chain: Chain):
+    for _ in range(10):
+        key = CurveKey.new(KeyCurve.SECP256R1, chain=chain)
+        signature = key.sign(b"data")
+        assert int.from_bytes(signature[32:], "big") <= N // 2
+        assert key.verify(b"data", signature)
+        assert not CurveKey.new(KeyCurve.SECP256R1, chain=chain).verify(
+            b"data", signature
+        )
+
+
+def test_ed25519_rfc8032_vector(chain: Chain):
+    key = CurveKey.from_key(KeyCurve.ED25519, ED25519_PRIVATE_KEY)
+    assert key.public_key == ED25519_PUBLIC_KEY
+
+    assert key.sign(b"") == ED25519_SIGNATURE
+    assert key.verify(b"", ED25519_SIGNATURE)
+    assert not key.verify(b"data", ED25519_SIGNATURE)
+    assert not CurveKey.new(KeyCurve.ED25519, chain=chain).verify(
+        b"", ED25519_SIGNATURE
+    )
+
+
+def test_new_keys_reproducible_with_seed(chain: Chain):
+    state = random.getstate()
+    try:
+        random.seed(1)
+        first = [CurveKey.new(curve, chain=chain) for curve in KeyCurve]
+        random.seed(1)
+        assert [CurveKey.new(curve, chain=chain) for curve in KeyCurve] == first
+    finally:
+        random.setstate(state)
@@ -0,0 +1,83 @@
+import pytest
+
+from wake.config import WakeConfig
+from wake.development.core import DataTruncatedError
+from wake.development.globals import get_config, set_config
+from wake.development.primitive_types import bytes32
+from wake.development.transactions import Eip1559Transaction
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+TX_HASH = "0x" + "11" * 32
+OUTPUT = "0x" + "ab" * 64
+
+
+@pytest.fixture
+def return_data_limit():
+    config = get_config()
+    set_config(WakeConfig.fromdict({"general": {"max_return_data_size": 32}}))
+    yield
+    set_config(config)
+
+
+def _transaction(chain: Chain, node: FakeNode) -> Eip1559Transaction:
+    node.handlers["eth_getTransactionReceipt"] = lambda tx_hash: {
+        "status": "0x1",
+        "contractAddress": None,
+    }
+    node.handlers["trace_transaction"] = lambda tx_hash: [
+        {"result": {"output": OUTPUT}}
+    ]
+    return Eip1559Transaction(
+        TX_HASH, {"to": "0x" + "22" * 20}, None, bytearray, chain  # pyright: ignore
+    )
+
+
+def test_return_data_truncated_at_fetch(
+    return_data_limit, chain: Chain, node: FakeNode
+):
+    tx = _transaction(chain, node)
+
+    assert tx.raw_return_value == bytearray.fromhex("ab" * 32)
+    assert tx.return_data_truncated
+    # the full data are not kept in the cached trace
+    assert tx._trace_transaction is not None
+    assert tx._trace_transaction[0]["result"]["output"] == "0x" + "ab" * 32
+
+    with pytest.raises(DataTruncatedError):
+        tx.return_value
+    with pytest.raises(DataTruncatedError):
+        tx.decode_return(bytes)
+
+
+def test_return_data_not_truncated(chain: Chain, node: FakeNode):
+    tx = _transaction(chain, node)
+
+    assert tx.raw_return_value == bytearray.fromhex(OUTPUT[2:])
+    assert not tx.return_data_truncated
+    assert tx.return_value == bytearray.fromhex(OUTPUT[2:])
+    assert tx.decode_return(bytes32) == bytes.fromhex("ab" * 32)
+
+
+def test_log_data_truncated_in_receipt(chain: Chain, node: FakeNode):
+    config = get_config()
+    set_config(WakeConfig.fromdict({"general": {"max_log_data_size": 32}}))
+    try:
+        node.handlers["eth_getTransactionReceipt"] = lambda tx_hash: {
+            "status": "0x1",
+            "contractAddress": None,
+            "logs": [{"topics": [], "data": OUTPUT}],
+        }
+        tx = Eip1559Transaction(
+            TX_HASH, {"to": "0x" + "22" * 20}, None, bytearray, chain  # pyright: ignore
+        )
+        tx.status
+
+        # the full data are not kept in the cached receipt
+        assert tx._tx_receipt is not None
+        assert tx._tx_receipt["logs"][0]["data"] == "0x" + "ab" * 32
+        assert tx.raw_events[0].data == bytes.fromhex("ab" * 32)
+        assert tx.log_data_truncated
+    finally:
+        set_config(config)
@@ -0,0 +1,90 @@
+import pytest
+
+from wake.development.core import Abi, Address, DeploymentFailed
+from wake.development.json_rpc import JsonRpcError
+from wake.development.transactions import Error
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+DEPLOYER = Address(0xDE)
+TX_PARAMS = {
+    "type": 2,
+    "from": str(DEPLOYER),
+    "nonce": 3,
+    "data": bytes.fromhex("6080"),
+    "gas": 100_000,
+    "value": 0,
+    "maxFeePerGas": 0,
+    "maxPriorityFeePerGas": 0,
+}
+
+
+@pytest.fixture
+def sent(chain: Chain, node: FakeNode, monkeypatch):
+    sent = []
+    automine = [False]
+    node.handlers["anvil_getAutomine"] = lambda: automine[0]
+    node.handlers["evm_setAutomine"] = lambda value: automine.__setitem__(0, value)
+    monkeypatch.setattr(
+        chain, "_build_transaction", lambda *args, **kwargs: dict(TX_PARAMS)
+    )
+
+    def send(tx_params, from_):
+        sent.append(tx_params)
+        return "0x" + "11" * 32
+
+    monkeypatch.setattr(chain, "_send_transaction", send)
+    return sent
+
+
+def _deploy(chain: Chain):
+    return chain._transact(None, [], {}, True, bytearray, 0, None)
+
+
+def test_deploy_simulation_succeeds(chain: Chain, node: FakeNode, sent):
+    node.handlers["eth_call"] = lambda tx, block: "0x"
+
+    tx = _deploy(chain)
+    assert tx.tx_hash == "0x" + "11" * 32
+    assert len(sent) == 1
+
+    _deploy(chain)
+    # the automine flag is fetched only once
+    assert node.requests.count("anvil_getAutomine") == 1
+
+
+def test_deploy_simulation_reverts(chain: Chain, node: FakeNode, sent):
+    revert_data = Abi.encode_with_signature("Error(string)", ["string"], ["no"])
+
+    def revert(tx, block):
+        raise JsonRpcError(
+            {"code": 3, "message": "execution reverted", "data": "0x" + revert_data.hex()}
+        )
+
+    node.handlers["eth_call"] = revert
+
+    with pytest.raises(DeploymentFailed) as e:
+        _deploy(chain)
+    assert e.value.error == Error("no")
+    assert e.value.deployer == DEPLOYER
+    assert e.value.nonce == 3
+    assert sent == []
+
+
+def test_deploy_simulation_non_revert_error_sent(chain: Chain, node: FakeNode, sent):
+    def insufficient_funds(tx, block):
+        raise JsonRpcError({"code": -32003, "message": "insufficient funds for gas"})
+
+    node.handlers["eth_call"] = insufficient_funds
+
+    _deploy(chain)
+    assert len(sent) == 1
+
+
+def test_deploy_not_simulated_with_automine(chain: Chain, node: FakeNode, sent):
+    chain.automine = True
+    _deploy(chain)
+
+    assert "eth_call" not in node.requests
+    assert len(sent) == 1
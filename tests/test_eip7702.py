@@ -0,0 +1,152 @@
+import eth_account
+import pytest
+import rlp
+from Crypto.Hash import keccak
+
+from wake.development.core import (
+    Account,
+    Address,
+    Authorization,
+    InvalidAuthorizationError,
+    _sign_eip7702_transaction,
+)
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+CHAIN_ID = 31337
+KEY = bytes.fromhex("4c0883a69102937d6231471b5decb335d5c22fa3bd7e4a8d29c3e8fbac7bf8f1")
+AUTHORITY = Address(eth_account.Account.from_key(KEY).address)
+SENDER = Address("0x000000000000000000000000000000000000dEaD")
+DELEGATE = Address("0x00000000000000000000000000000000000000c0")
+
+
+def _sign(chain_id: int, nonce: int, key: bytes = KEY) -> Authorization:
+    signed = eth_account.Account.signHash(
+        Authorization.signing_hash(chain_id, DELEGATE, nonce), key
+    )
+    return Authorization(
+        chain_id=chain_id,
+        address=DELEGATE,
+        nonce=nonce,
+        y_parity=signed.v - 27,
+        r=signed.r,
+        s=signed.s,
+    )
+
+
+@pytest.fixture
+def account_nonces(node: FakeNode):
+    node.nonces = {str(SENDER).lower(): 3, str(AUTHORITY).lower(): 7}
+
+
+def test_authorization_authority_recovery():
+    authorization = _sign(CHAIN_ID, 5)
+    assert authorization.authority == AUTHORITY
+    assert Authorization.from_json(authorization.to_json()) == authorization
+
+
+def test_authorization_invalid_signature_values():
+    authorization = _sign(CHAIN_ID, 0)
+
+    with pytest.raises(InvalidAuthorizationError):
+        Authorization(**{**authorization.__dict__, "y_parity": 2}).authority
+
+    # high s values are rejected
+    n = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+    with pytest.raises(InvalidAuthorizationError):
+        Authorization(**{**authorization.__dict__, "s": n - authorization.s}).authority
+
+    with pytest.raises(InvalidAuthorizationError):
+        Authorization(**{**authorization.__dict__, "r": 0}).authority
+
+
+def test_validate_authorization_list_nonces(chain: Chain, account_nonces):
+    _, nonces = chain._validate_authorization_list(SENDER, [_sign(CHAIN_ID, 7)])
+    assert nonces[SENDER] == 4
+    assert nonces[AUTHORITY] == 8
+
+    # cross-chain authorization
+    _, nonces = chain._validate_authorization_list(SENDER, [_sign(0, 7)])
+    assert nonces[AUTHORITY] == 8
+
+    # consecutive authorizations of the same authority
+    _, nonces = chain._validate_authorization_list(
+        SENDER, [_sign(CHAIN_ID, 7), _sign(CHAIN_ID, 8)]
+    )
+    assert nonces[AUTHORITY] == 9
+
+
+def test_validate_authorization_list_rejects_invalid(chain: Chain, account_nonces):
+    with pytest.raises(InvalidAuthorizationError, match="chain ID"):
+        chain._validate_authorization_list(SENDER, [_sign(1, 7)])
+
+    with pytest.raises(InvalidAuthorizationError, match="does not match nonce 7"):
+        chain._validate_authorization_list(SENDER, [_sign(CHAIN_ID, 6)])
+
+
+def test_validate_self_sponsored_authorization(chain: Chain, account_nonces):
+    # the sender nonce is incremented before the authorization list is processed
+    authorizations, nonces = chain._validate_authorization_list(
+        AUTHORITY, [_sign(CHAIN_ID, 8)]
+    )
+    assert authorizations == [_sign(CHAIN_ID, 8)]
+    assert nonces[AUTHORITY] == 9
+
+    # cannot be signed again without the private key
+    with pytest.raises(InvalidAuthorizationError, match="current nonce \\+ 1"):
+        chain._validate_authorization_list(AUTHORITY, [_sign(CHAIN_ID, 7)])
+
+
+def test_self_sponsored_authorization_signed_again(chain: Chain, account_nonces):
+    Account.from_key(KEY, chain=chain, per_chain=True)
+
+    authorizations, nonces = chain._validate_authorization_list(
+        AUTHORITY, [_sign(CHAIN_ID, 7)]
+    )
+    assert authorizations == [_sign(CHAIN_ID, 8)]
+    assert authorizations[0].authority == AUTHORITY
+    assert nonces[AUTHORITY] == 9
+
+
+def test_sign_eip7702_transaction():
+    authorization = _sign(CHAIN_ID, 1)
+    raw_tx = _sign_eip7702_transaction(
+        {
+            "type": 4,
+            "chainId": CHAIN_ID,
+            "nonce": 0,
+            "from": str(AUTHORITY),
+            "to": str(AUTHORITY),
+            "value": 0,
+            "data": b"",
+            "gas": 100_000,
+            "maxPriorityFeePerGas": 1,
+            "maxFeePerGas": 2,
+            "accessList": [],
+            "authorizationList": [authorization.to_json()],
+        },
+        KEY,
+    )
+    assert raw_tx[0] == 4
+
+    fields = rlp.decode(raw_tx[1:])
+    assert len(fields) == 13
+    chain_id, address, nonce, y_parity, r, s = fields[9][0]
+    assert address == bytes(DELEGATE)
+    assert [int.from_bytes(v, "big") for v in (chain_id, nonce, y_parity, r, s)] == [
+        CHAIN_ID,
+        1,
+        authorization.y_parity,
+        authorization.r,
+        authorization.s,
+    ]
+
+    signing_hash = keccak.new(
+        data=b"\x04" + rlp.encode(fields[:10]), digest_bits=256
+    ).digest()
+    y_parity = int.from_bytes(fields[10], "big")
+    r = int.from_bytes(fields[11], "big")
+    s = int.from_bytes(fields[12], "big")
+    signer = eth_account.Account._recover_hash(signing_hash, vrs=(y_parity, r, s))
+    assert Address(signer) == AUTHORITY
@@ -0,0 +1,69 @@
+import pytest
+
+from wake.development.core import Address
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+REGISTRY = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e"
+RESOLVER = "0x4976fb03C32e5B8cfe2b6cCB31c09Ba78EBaBa41"
+OWNER = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+
+
+def _word(address: str) -> str:
+    return "0x" + address[2:].lower().rjust(64, "0")
+
+
+@pytest.fixture
+def ens(node: FakeNode):
+    resolvers = {REGISTRY.lower(): RESOLVER, RESOLVER.lower(): OWNER}
+
+    def call(params, block):
+        return _word(resolvers[params["to"].lower()])
+
+    node.handlers["eth_getCode"] = lambda address, block: (
+        "0x60" if address.lower() in resolvers else "0x"
+    )
+    node.handlers["eth_call"] = call
+    return resolvers
+
+
+def test_resolve_ens(ens, chain: Chain, node: FakeNode):
+    address = chain.resolve_ens("Vitalik.eth")
+    assert isinstance(address, Address)
+    assert address == Address(OWNER)
+
+    # resolved names are cached
+    node.requests.clear()
+    assert chain.resolve_ens("vitalik.eth") == Address(OWNER)
+    assert node.requests == []
+
+
+def test_resolve_ens_without_resolver(ens, chain: Chain):
+    ens[REGISTRY.lower()] = str(Address.ZERO)
+
+    with pytest.raises(ValueError, match="has no resolver"):
+        chain.resolve_ens("vitalik.eth")
+
+
+def test_ens_names_resolved_in_arguments(ens, chain: Chain):
+    abi = {
+        "inputs": [
+            {"name": "to", "type": "address"},
+            {"name": "recipients", "type": "address[]"},
+            {"name": "label", "type": "string"},
+        ]
+    }
+    data = chain._encode_arguments(abi, ["vitalik.eth", ["vitalik.eth"], "vitalik.eth"])
+    assert data == chain._encode_arguments(abi, [OWNER, [OWNER], "vitalik.eth"])
+    assert chain._resolve_address("vitalik.eth", "`to` account") == Address(OWNER)
+
+
+def test_ens_cache_cleared_on_revert(ens, chain: Chain, node: FakeNode):
+    snapshot = chain.snapshot()
+    chain.resolve_ens("vitalik.eth")
+    chain.revert(snapshot)
+
+    node.requests.clear()
+    assert chain.resolve_ens("vitalik.eth") == Address(OWNER)
+    assert "eth_call" in node.requests
@@ -0,0 +1,54 @@
+import pytest
+
+from wake.development.core import Address, _get_constructor_storage
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def _step(op: str, depth: int, *stack: int) -> dict:
+    return {"op": op, "depth": depth, "stack": [hex(x) for x in stack]}
+
+
+def test_constructor_storage_skips_other_contexts_and_reverted_frames():
+    trace = {
+        "structLogs": [
+            # the slot is on top of the stack, followed by the value
+            _step("SSTORE", 1, 5, 0),
+            # storage of the called contract
+            _step("CALL", 1),
+            _step("SSTORE", 2, 9, 1),
+            _step("STOP", 2),
+            _step("POP", 1, 1),
+            # library writing the storage of the constructor
+            _step("DELEGATECALL", 1),
+            _step("SSTORE", 2, 7, 1),
+            _step("RETURN", 2),
+            _step("POP", 1, 1),
+            # reverted library call
+            _step("DELEGATECALL", 1),
+            _step("SSTORE", 2, 8, 1),
+            _step("REVERT", 2),
+            _step("POP", 1, 0),
+            _step("RETURN", 1),
+        ]
+    }
+    assert _get_constructor_storage(trace) == {0: 5, 1: 7}
+
+
+def test_etch_validates_arguments_first(chain: Chain, node: FakeNode):
+    address = Address(0x1234)
+
+    with pytest.raises(ValueError):
+        chain.etch(address, b"\x00", storage={0: b"\x01" * 33})
+    with pytest.raises(ValueError):
+        chain.etch(address, b"\x00", nonce=-1)
+    assert "anvil_setCode" not in node.requests
+
+
+def test_etch_fqn_override_undone_on_revert(chain: Chain):
+    address = Address(0x1234)
+
+    with chain.snapshot_and_revert():
+        chain._fqn_overrides[address] = (b"\x00" * 32, "contracts/A.sol:A")
+    assert address not in chain._fqn_overrides
@@ -0,0 +1,51 @@
+from types import SimpleNamespace
+
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def _tx_hash(i: int) -> str:
+    return "0x" + format(i, "064x")
+
+
+def test_subscription_advances_after_each_block(chain: Chain, node: FakeNode):
+    # one transaction with two events in block 1, one transaction with one event in block 2
+    logs = [
+        {"blockNumber": "0x1", "logIndex": "0x0", "transactionHash": _tx_hash(1)},
+        {"blockNumber": "0x1", "logIndex": "0x1", "transactionHash": _tx_hash(1)},
+        {"blockNumber": "0x2", "logIndex": "0x0", "transactionHash": _tx_hash(2)},
+    ]
+    from_blocks = []
+
+    def get_logs(params):
+        from_blocks.append(int(params["fromBlock"], 16))
+        return [l for l in logs if int(l["blockNumber"], 16) >= from_blocks[-1]]
+
+    node.handlers["eth_getLogs"] = get_logs
+    chain.txs._transactions[_tx_hash(1)] = SimpleNamespace(  # pyright: ignore
+        events=["a", "b"]
+    )
+    chain.txs._transactions[_tx_hash(2)] = SimpleNamespace(  # pyright: ignore
+        events=["c"]
+    )
+
+    subscription = chain.events()
+    node.mine()
+    node.mine()
+    node.mine()
+
+    it = iter(subscription)
+    assert next(it) == "a"
+    # stopped in the middle of block 1
+    assert subscription.next_block == 1
+    assert next(it) == "b"
+    assert next(it) == "c"
+    assert subscription.next_block == 2
+    it.close()
+
+    # block 2 was not finished, its events are yielded again
+    assert subscription.poll() == ["c"]
+    assert from_blocks == [1, 2]
+    assert subscription.next_block == 4
+    assert subscription.poll() == []
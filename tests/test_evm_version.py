@@ -0,0 +1,35 @@
+import pytest
+
+from wake.development.core import IncompatibleEvmVersion
+from wake.testing.core import Chain
+
+# PUSH0 PUSH0 RETURN
+PUSH0_CODE = bytes.fromhex("5f5ff3")
+# PUSH1 0x5f PUSH1 0x00 RETURN
+PUSH1_CODE = bytes.fromhex("605f6000f3")
+
+
+def _contract(evm_version: str) -> type:
+    return type("Token", (), {"_evm_version": evm_version})
+
+
+def test_newer_evm_version_without_new_opcodes_warns(chain: Chain):
+    # the chain runs Cancun, no Prague opcodes in the code
+    with pytest.warns(RuntimeWarning, match="prague"):
+        chain._check_evm_version(_contract("prague"), PUSH1_CODE)
+
+    chain._check_evm_version(_contract("shanghai"), PUSH0_CODE)
+
+
+def test_unsupported_opcodes_listed(chain: Chain):
+    chain._hardfork = "MERGE"
+    with pytest.raises(IncompatibleEvmVersion) as e:
+        chain._check_evm_version(_contract("shanghai"), PUSH0_CODE)
+    assert e.value.opcodes == ["PUSH0"]
+
+    # PUSH1 data is skipped
+    with pytest.warns(RuntimeWarning):
+        chain._check_evm_version(_contract("shanghai"), PUSH1_CODE)
+
+    chain.evm_version_check = False
+    chain._check_evm_version(_contract("shanghai"), PUSH0_CODE)
@@ -0,0 +1,41 @@
+import eth_utils
+
+from wake.development.core import (
+    Address,
+    external_error_abis,
+    register_error_abi,
+)
+from wake.development.transactions import ExternalError
+from wake.testing.core import Chain
+
+UNAUTHORIZED_ABI = {
+    "type": "error",
+    "name": "Unauthorized",
+    "inputs": [{"name": "caller", "type": "uint256"}],
+}
+UNAUTHORIZED_SELECTOR = eth_utils.abi.function_abi_to_4byte_selector(UNAUTHORIZED_ABI)
+
+
+def test_register_abi_shares_error_registry(chain: Chain):
+    external_error_abis.clear()
+    chain.register_abi(Address(1), [UNAUTHORIZED_ABI])
+    assert list(external_error_abis) == [UNAUTHORIZED_SELECTOR]
+
+    e = chain._process_revert_data(
+        None, UNAUTHORIZED_SELECTOR + (5).to_bytes(32, "big")
+    )
+    assert isinstance(e, ExternalError)
+    assert e.name == "Unauthorized"
+    assert e.arguments == {"caller": 5}
+
+
+def test_register_error_abi_decodes_external_error(chain: Chain):
+    external_error_abis.clear()
+    abi = dict(UNAUTHORIZED_ABI, inputs=[{"name": "", "type": "uint256"}])
+    register_error_abi({"abi": [abi]})
+
+    e = chain._process_revert_data(
+        None, UNAUTHORIZED_SELECTOR + (7).to_bytes(32, "big")
+    )
+    assert isinstance(e, ExternalError)
+    assert e.arguments == {"arg0": 7}
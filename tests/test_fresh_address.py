@@ -0,0 +1,43 @@
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def test_fresh_addresses_distinct(chain: Chain):
+    a = chain.fresh_address()
+    b = chain.fresh_address("recipient")
+
+    assert a != b
+    assert a.label == "fresh0"
+    assert b.label == "recipient"
+    assert chain.fresh_addresses == [a, b]
+    assert not a.has_signer
+
+
+def test_fresh_addresses_restored_on_revert(chain: Chain):
+    a = chain.fresh_address()
+
+    snapshot = chain.snapshot()
+    b = chain.fresh_address()
+    chain.revert(snapshot)
+
+    assert chain.fresh_addresses == [a]
+    assert chain.fresh_address() == b
+
+
+def test_fresh_address_skips_used_addresses_on_fork(
+    chain: Chain, node: FakeNode, monkeypatch
+):
+    with chain.snapshot_and_revert():
+        first = chain.fresh_address()
+
+    used = {str(first.address).lower()}
+    node.handlers["eth_getCode"] = lambda address, block: (
+        "0x6080" if address.lower() in used else "0x"
+    )
+    node.handlers["eth_getBalance"] = lambda address, block: "0x0"
+    monkeypatch.setattr(chain, "_fork", "http://localhost:8545")
+
+    fresh = chain.fresh_address()
+    assert fresh != first
+    assert "eth_getCode" in node.requests
@@ -0,0 +1,42 @@
+import pytest
+
+from wake.development.core import Account
+from wake.development.json_rpc.communicator import JsonRpcError
+from wake.development.transactions import Halt, HaltReasonEnum
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def _estimate(
+    chain: Chain, node: FakeNode, required_gas: int, node_estimate: int, exact
+) -> int:
+    def call(params, block):
+        if int(params["gas"], 16) < required_gas:
+            raise JsonRpcError({"code": -32000, "message": "out of gas"})
+        return "0x"
+
+    node.handlers["eth_estimateGas"] = lambda params, block: hex(node_estimate)
+    node.handlers["eth_call"] = call
+    return Account(1, chain).estimate(from_=Account(2, chain), exact=exact)
+
+
+@pytest.mark.parametrize("node_estimate", [50_000, 54_321, 60_000])
+def test_exact_estimate_finds_minimal_gas_limit(
+    chain: Chain, node: FakeNode, node_estimate: int
+):
+    assert _estimate(chain, node, 54_321, node_estimate, True) == 54_321
+
+
+def test_estimate_not_refined_by_default(chain: Chain, node: FakeNode):
+    assert _estimate(chain, node, 54_321, 60_000, None) == 60_000
+    assert "eth_call" not in node.requests
+
+    chain.exact_gas_estimates = True
+    assert _estimate(chain, node, 54_321, 60_000, None) == 54_321
+
+
+def test_exact_estimate_raises_above_block_gas_limit(chain: Chain, node: FakeNode):
+    with pytest.raises(Halt) as e:
+        _estimate(chain, node, 40_000_000, 30_000_000, True)
+    assert e.value.reason == HaltReasonEnum.OUT_OF_GAS
@@ -0,0 +1,64 @@
+import pytest
+
+from wake.development.transactions import (
+    Halt,
+    HaltReasonEnum,
+    UnknownTransactionRevertedError,
+    must_revert,
+    parse_halt_reason,
+)
+
+
+@pytest.mark.parametrize(
+    "message, reason",
+    [
+        ("EvmError: OutOfGas", HaltReasonEnum.OUT_OF_GAS),
+        ("out of gas", HaltReasonEnum.OUT_OF_GAS),
+        ("EvmError: InvalidFEOpcode", HaltReasonEnum.INVALID_OPCODE),
+        ("invalid opcode: opcode 0xfe not defined", HaltReasonEnum.INVALID_OPCODE),
+        ("invalid jump destination", HaltReasonEnum.INVALID_JUMP),
+        ("stack underflow (0 <=> 2)", HaltReasonEnum.STACK_UNDERFLOW),
+        ("write protection", HaltReasonEnum.STATE_CHANGE_DURING_STATIC_CALL),
+        ("max call depth exceeded", HaltReasonEnum.CALL_TOO_DEEP),
+        ("EvmError: CreateCollision", HaltReasonEnum.CREATE_COLLISION),
+        ("max code size exceeded", HaltReasonEnum.CREATE_CONTRACT_SIZE_LIMIT),
+        ("EvmError: OutOfGas(Basic)", HaltReasonEnum.OUT_OF_GAS),
+        ("stack limit reached 1024 (1023)", HaltReasonEnum.STACK_OVERFLOW),
+        ("Bad jump destination", HaltReasonEnum.INVALID_JUMP),
+        ("something unexpected", HaltReasonEnum.UNKNOWN),
+        # only the error identifier is matched
+        ("insufficient funds for gas * price + value", HaltReasonEnum.UNKNOWN),
+        ("transaction ran out of gas", HaltReasonEnum.UNKNOWN),
+    ],
+)
+def test_parse_halt_reason(message: str, reason: HaltReasonEnum):
+    assert parse_halt_reason(message) == reason
+
+
+def test_parse_halt_reason_revert():
+    assert parse_halt_reason("execution reverted") is None
+    assert parse_halt_reason("EvmError: Revert") is None
+
+
+def test_halt_equals_unknown_revert():
+    halt = Halt(reason=HaltReasonEnum.OUT_OF_GAS, message="EvmError: OutOfGas")
+
+    assert halt == UnknownTransactionRevertedError(b"")
+    assert UnknownTransactionRevertedError(b"") == halt
+    assert halt != UnknownTransactionRevertedError(b"\x00")
+    assert halt == Halt(reason=HaltReasonEnum.OUT_OF_GAS)
+    assert halt != Halt(reason=HaltReasonEnum.INVALID_OPCODE)
+    assert str(halt) == "Halt(reason=OUT_OF_GAS)"
+    assert HaltReasonEnum.OUT_OF_GAS == "OUT_OF_GAS"
+
+
+def test_must_revert_matches_halt():
+    with must_revert(UnknownTransactionRevertedError(b"")):
+        raise Halt(reason=HaltReasonEnum.OUT_OF_GAS)
+
+    with must_revert(HaltReasonEnum.OUT_OF_GAS):
+        raise Halt(reason=HaltReasonEnum.OUT_OF_GAS)
+
+    with pytest.raises(Halt):
+        with must_revert(HaltReasonEnum.INVALID_OPCODE):
+            raise Halt(reason=HaltReasonEnum.OUT_OF_GAS)
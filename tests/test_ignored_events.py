@@ -0,0 +1,86 @@
+import pytest
+from rich.tree import Tree
+
+from wake.development.call_trace import CallTrace, CallTraceKind
+from wake.development.core import Account, Address
+from wake.development.transactions import LegacyTransaction
+from wake.testing.core import Chain
+
+APPROVAL = bytes.fromhex("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925")
+TRANSFER = bytes.fromhex("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+TOKEN = Address(0x70)
+PROXY = Address(0x71)
+
+
+def _log(selector: bytes, address: Address):
+    return {"address": str(address), "topics": ["0x" + selector.hex()], "data": "0x"}
+
+
+def test_ignore_and_unignore(chain: Chain):
+    chain.ignore_event(APPROVAL)
+    chain.ignore_event(APPROVAL)
+    chain.ignore_event(address=PROXY)
+    assert chain.ignored_events == [(APPROVAL, None), (None, PROXY)]
+
+    chain.unignore_event(APPROVAL)
+    assert chain.ignored_events == [(None, PROXY)]
+
+    with pytest.raises(ValueError):
+        chain.unignore_event(APPROVAL)
+    with pytest.raises(ValueError):
+        chain.ignore_event()
+
+
+def test_ignored_by_topic0_or_address(chain: Chain):
+    chain.ignore_event(APPROVAL)
+    assert chain._is_log_ignored(_log(APPROVAL, TOKEN))
+    assert chain._is_log_ignored(_log(APPROVAL, PROXY))
+    assert not chain._is_log_ignored(_log(TRANSFER, TOKEN))
+
+    chain.unignore_event(APPROVAL)
+    chain.ignore_event(address=PROXY)
+    assert chain._is_log_ignored(_log(TRANSFER, PROXY))
+    assert not chain._is_log_ignored(_log(TRANSFER, TOKEN))
+
+    chain.unignore_event(address=PROXY)
+    chain.ignore_event(TRANSFER, address=PROXY)
+    assert chain._is_log_ignored(_log(TRANSFER, PROXY))
+    assert not chain._is_log_ignored(_log(TRANSFER, TOKEN))
+    assert not chain._is_log_ignored(_log(APPROVAL, PROXY))
+
+
+def test_ignored_events_count(chain: Chain):
+    tx = LegacyTransaction("0x" + "11" * 32, {}, None, type(None), chain)
+    tx._tx_receipt = {
+        "logs": [_log(APPROVAL, TOKEN), _log(TRANSFER, TOKEN), _log(APPROVAL, PROXY)]
+    }
+
+    chain.ignore_event(APPROVAL)
+    assert tx.ignored_events_count == 2
+
+
+def test_ignored_events_not_rendered_in_trace(chain: Chain):
+    trace = CallTrace(
+        None,
+        "Token",
+        "approve",
+        None,
+        TOKEN,
+        [],
+        [],
+        0,
+        0,
+        CallTraceKind.CALL,
+        1,
+        chain,
+        Account(Address(1), chain),
+        [],
+        {},
+    )
+    chain.ignore_event(APPROVAL, address=TOKEN)
+    trace._add_event(APPROVAL)
+    trace._add_event(TRANSFER)
+
+    tree = Tree("")
+    trace._into_tree(tree)
+    assert [str(child.label) for child in tree.children] == ["emit 0x" + TRANSFER.hex()]
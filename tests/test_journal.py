@@ -0,0 +1,155 @@
+from wake.utils.journal import (
+    Journal,
+    JournaledDict,
+    JournaledKeyedDefaultDict,
+    JournaledList,
+    JournaledSet,
+)
+
+
+def test_changes_not_recorded_without_checkpoint():
+    journal = Journal()
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 0)
+    slots = JournaledSet(journal)
+
+    nonces["alice"] += 1
+    slots.add(1)
+    assert journal.stats.entries == 0
+    assert journal.stats.recorded == 0
+
+
+def test_rollback_restores_changed_entries():
+    journal = Journal()
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 10)
+    slots = JournaledSet(journal)
+    nonces["alice"] = 1
+    slots.add(1)
+
+    checkpoint = journal.checkpoint()
+    nonces["alice"] = 2
+    nonces["alice"] = 3
+    # value created by the default factory
+    assert nonces["bob"] == 10
+    del nonces["alice"]
+    slots.add(2)
+    slots.add(2)
+    slots.discard(1)
+    # only the first change of an entry since the checkpoint is recorded
+    assert journal.stats.entries == 4
+
+    journal.rollback(checkpoint)
+    assert dict(nonces) == {"alice": 1}
+    assert slots == {1}
+    assert journal.stats == (0, 0, 4, 4, 0)
+
+
+def test_nested_checkpoints():
+    journal = Journal()
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 0)
+
+    first = journal.checkpoint()
+    nonces["alice"] = 1
+    second = journal.checkpoint()
+    nonces["alice"] = 2
+    third = journal.checkpoint()
+    nonces["alice"] = 3
+
+    journal.rollback(second)
+    assert nonces["alice"] == 1
+    # checkpoints taken after the rolled back one are discarded
+    assert journal.stats.checkpoints == 1
+    assert third not in journal._checkpoints
+
+    nonces["alice"] = 4
+    journal.rollback(first)
+    assert "alice" not in nonces
+    assert journal.stats.entries == 0
+
+
+
+def test_release_compacts_entries():
+    journal = Journal()
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 0)
+
+    first = journal.checkpoint()
+    nonces["alice"] = 1
+    nonces["bob"] = 1
+    second = journal.checkpoint()
+    nonces["alice"] = 2
+
+    # entries of the oldest checkpoint make up more than half of the journal
+    journal.release(first)
+    assert journal.stats.entries == 1
+    assert journal.stats.checkpoints == 1
+
+    journal.rollback(second)
+    assert dict(nonces) == {"alice": 1, "bob": 1}
+
+
+def test_release_keeps_entries_of_older_checkpoints():
+    journal = Journal()
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 0)
+
+    first = journal.checkpoint()
+    nonces["alice"] = 1
+    second = journal.checkpoint()
+    nonces["alice"] = 2
+    nonces["bob"] = 2
+
+    journal.release(second)
+    assert journal.stats.entries == 3
+    journal.compact()
+    # still needed by the first checkpoint
+    assert journal.stats.entries == 3
+
+    journal.rollback(first)
+    assert dict(nonces) == {}
+
+
+def test_limit_evicts_oldest_checkpoints():
+    journal = Journal()
+    journal.limit = 2
+    nonces = JournaledKeyedDefaultDict(journal, lambda key: 0)
+
+    first = journal.checkpoint()
+    nonces["alice"] = 1
+    nonces["bob"] = 1
+    second = journal.checkpoint()
+    nonces["alice"] = 2
+    assert not journal.has_checkpoint(first)
+    assert journal.stats.entries == 1
+    assert journal.stats.evicted == 1
+
+    journal.rollback(second)
+    assert dict(nonces) == {"alice": 1, "bob": 1}
+
+    # lowering the limit evicts immediately
+    journal.checkpoint()
+    nonces["alice"] = 3
+    journal.limit = 0
+    assert journal.stats == (0, 0, 4, 1, 2)
+
+
+def test_journaled_list_rollback():
+    journal = Journal()
+    items = JournaledList(journal)
+    items.append("a")
+
+    checkpoint = journal.checkpoint()
+    items.append("b")
+    items.append("c")
+    journal.rollback(checkpoint)
+    assert items == ["a"]
+
+
+def test_journaled_dict_rollback():
+    journal = Journal()
+    items = JournaledDict(journal)
+    items["a"] = 1
+
+    checkpoint = journal.checkpoint()
+    items["a"] = 2
+    items["b"] = 3
+    del items["a"]
+    journal.rollback(checkpoint)
+    assert items == {"a": 1}
@@ -0,0 +1,138 @@
+import json
+from collections import OrderedDict, deque
+
+import pytest
+
+from wake.config import WakeConfig
+from wake.development.json_rpc import ConnectionLost, ExecutionTimeout, ForkTimeout
+from wake.development.json_rpc.abc import ProtocolAbc
+from wake.development.json_rpc.communicator import JsonRpcCommunicator
+
+
+class _Protocol(ProtocolAbc):
+    """
+    Persistent connection replying to requests in order, `None` in `replies` simulates a request timing out.
+    """
+
+    def __init__(self, replies, reconnect_fails: bool = False):
+        self._timeout = 15
+        self.replies = deque(replies)
+        self.reconnect_fails = reconnect_fails
+        self.reconnects = 0
+
+    def __enter__(self):
+        pass
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        pass
+
+    def send_recv(self, data: str):
+        json.loads(data)
+        return self.recv()
+
+    def recv(self):
+        reply = self.replies.popleft()
+        if reply is None:
+            raise TimeoutError()
+        return reply
+
+    def reconnect(self) -> None:
+        self.reconnects += 1
+        if self.reconnect_fails:
+            raise ConnectionRefusedError()
+
+
+def _communicator(protocol: _Protocol) -> JsonRpcCommunicator:
+    communicator = JsonRpcCommunicator(WakeConfig(), "http://localhost:8545")
+    communicator._protocol = protocol
+    return communicator
+
+
+def test_stale_responses_discarded():
+    protocol = _Protocol(
+        [
+            None,
+            {"jsonrpc": "2.0", "id": 0, "result": "0xdead"},
+            {"jsonrpc": "2.0", "id": 1, "result": "0x1"},
+        ]
+    )
+    communicator = _communicator(protocol)
+    communicator.execution_timeout = 1
+
+    with pytest.raises(ExecutionTimeout):
+        communicator.send_request("eth_call", [])
+    assert protocol.reconnects == 1
+
+    assert communicator.send_request("eth_blockNumber") == "0x1"
+
+
+def test_connection_lost_after_failed_reconnect():
+    protocol = _Protocol([None], reconnect_fails=True)
+    communicator = _communicator(protocol)
+
+    with pytest.raises(TimeoutError):
+        communicator.send_request("eth_blockNumber")
+
+    with pytest.raises(ConnectionLost):
+        communicator.send_request("eth_blockNumber")
+    assert protocol.reconnects == 2
+
+    # reconnecting is retried by further requests
+    protocol.reconnect_fails = False
+    protocol.replies.append({"jsonrpc": "2.0", "id": 2, "result": "0x1"})
+    assert communicator.send_request("eth_blockNumber") == "0x1"
+    assert protocol.reconnects == 3
+
+
+_FORK_TIMEOUT = {
+    "code": -32603,
+    "message": "failed to get account for 0x0000000000000000000000000000000000000001: operation timed out",
+}
+
+
+def test_fork_timeout_fallback_explicit_block():
+    protocol = _Protocol(
+        [
+            {"jsonrpc": "2.0", "id": 0, "result": "0x1"},
+            {"jsonrpc": "2.0", "id": 1, "error": _FORK_TIMEOUT},
+        ]
+    )
+    communicator = _communicator(protocol)
+    communicator._fallback_cache = OrderedDict()
+    params = ["0x0000000000000000000000000000000000000001", "0x10"]
+
+    assert communicator.send_request("eth_getBalance", params) == "0x1"
+    assert communicator.send_request("eth_getBalance", params) == "0x1"
+
+
+def test_fork_timeout_fallback_block_tag_not_cached():
+    protocol = _Protocol(
+        [
+            {"jsonrpc": "2.0", "id": 0, "result": "0x1"},
+            {"jsonrpc": "2.0", "id": 1, "error": _FORK_TIMEOUT},
+        ]
+    )
+    communicator = _communicator(protocol)
+    communicator._fallback_cache = OrderedDict()
+    params = ["0x0000000000000000000000000000000000000001", "latest"]
+
+    assert communicator.send_request("eth_getBalance", params) == "0x1"
+    with pytest.raises(ForkTimeout):
+        communicator.send_request("eth_getBalance", params)
+
+
+def test_revert_mentioning_timeout_not_fork_timeout():
+    protocol = _Protocol(
+        [
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "error": {"code": 3, "message": "execution reverted: timeout"},
+            },
+        ]
+    )
+    communicator = _communicator(protocol)
+
+    with pytest.raises(Exception) as e:
+        communicator.send_request("eth_call", [{}, "latest"])
+    assert not isinstance(e.value, ForkTimeout)
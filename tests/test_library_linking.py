@@ -0,0 +1,79 @@
+from typing import List
+
+import pytest
+
+from wake.development.core import (
+    Address,
+    Contract,
+    LibraryNotDeployed,
+    RequestType,
+)
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+LIB_ID = bytes.fromhex("ab" * 17)
+LIB_ADDRESS = Address(0x5)
+
+
+class _Linked(Contract):
+    _abi = {}
+    _creation_code = "6080" + "__$" + LIB_ID.hex() + "$__" + "00"
+
+
+@pytest.fixture
+def code_requests(chain: Chain, node: FakeNode, monkeypatch) -> List[str]:
+    requests = []
+
+    def get_code(address: str, block: str) -> str:
+        requests.append(block)
+        return node.codes.get(address.lower(), "0x")
+
+    node.codes = {str(LIB_ADDRESS).lower(): "0x6080"}  # pyright: ignore
+    node.handlers["eth_getCode"] = get_code
+    monkeypatch.setattr(_Linked, "_execute", lambda *args: None)
+    chain.evm_version_check = False
+    return requests
+
+
+def _deploy(chain: Chain, block=None) -> None:
+    _Linked._deploy(
+        RequestType.TX,
+        [],
+        False,
+        _Linked,
+        None,
+        0,
+        None,
+        {LIB_ID: (LIB_ADDRESS, "Lib")},
+        chain,
+        None,
+        None,
+        None,
+        None,
+        None,
+        block,
+        None,
+    )
+
+
+def test_library_verified_once(chain: Chain, code_requests: List[str]):
+    _deploy(chain)
+    _deploy(chain)
+    assert code_requests == ["pending"]
+
+    _deploy(chain, block=1)
+    assert code_requests == ["pending", "0x1"]
+
+
+def test_library_verified_again_after_revert(
+    chain: Chain, node: FakeNode, code_requests: List[str]
+):
+    snapshot = chain.snapshot()
+    _deploy(chain)
+    chain.revert(snapshot)
+
+    node.codes = {}  # pyright: ignore
+    with pytest.raises(LibraryNotDeployed):
+        _deploy(chain)
+    assert len(code_requests) == 2
@@ -0,0 +1,41 @@
+import pytest
+
+from wake.development.core import Address, RevertToSnapshotFailedError
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def test_load_state_drops_cached_state(chain: Chain, node: FakeNode):
+    node.handlers["anvil_loadState"] = lambda data: True
+    lib = "ab" * 17
+
+    chain._nonces[Address(1)] = 5
+    chain._written_slots.add((Address(1), 0))
+    chain.txs._transactions["0x" + "11" * 32] = 0  # pyright: ignore
+    chain.txs.register_tx("0x" + "11" * 32)
+    chain.blocks[0]
+
+    chain.load_state({"state": {}, "libraries": {lib: str(Address(2))}})
+
+    assert len(chain._nonces) == 0
+    assert len(chain._written_slots) == 0
+    assert len(chain.txs) == 0
+    assert chain.txs._transactions == {}
+    assert chain.blocks._blocks == {}
+    assert chain._deployed_libraries[bytes.fromhex(lib)][-1].address == Address(2)
+
+
+def test_load_state_invalidates_snapshots(chain: Chain, node: FakeNode):
+    node.handlers["anvil_loadState"] = lambda data: True
+
+    snapshot = chain.snapshot()
+    chain._nonces[Address(1)] = 5
+    chain.load_state({"state": {}})
+
+    assert chain.journal_stats.entries == 0
+    assert chain.journal_stats.checkpoints == 0
+    with pytest.raises(RevertToSnapshotFailedError):
+        chain.revert(snapshot)
+    assert "evm_revert" not in node.requests
+    assert len(chain._nonces) == 0
@@ -0,0 +1,93 @@
+from types import SimpleNamespace
+
+import wake.development.core
+from wake.development.core import Address, process_debug_trace_for_panic_location
+from wake.development.transactions import (
+    Panic,
+    PanicCodeEnum,
+    _panic_code_descriptions,
+)
+
+
+def test_panic_str():
+    panic = Panic(PanicCodeEnum.UNDERFLOW_OVERFLOW)
+    assert (
+        str(panic)
+        == "Panic(code=UNDERFLOW_OVERFLOW (0x11): Integer underflow or overflow)"
+    )
+    assert str(Panic(0x99)) == "Panic(code=0x99)"
+
+    panic.location = "Counter.decrement"
+    assert str(panic).endswith(") in Counter.decrement")
+
+
+def test_panic_location_not_compared():
+    panic = Panic(PanicCodeEnum.ASSERT_FAIL)
+    panic.location = "Counter.increment"
+
+    assert panic == Panic(PanicCodeEnum.ASSERT_FAIL)
+
+
+def test_panic_code_descriptions():
+    assert set(_panic_code_descriptions) == set(PanicCodeEnum)
+    assert PanicCodeEnum.POP_EMPTY_ARRAY.description == ".pop() on empty array"
+
+
+class _Callee:
+    _abi = {bytes.fromhex("aabbccdd"): {"name": "decrement"}}
+
+
+def test_panic_location_from_debug_trace(monkeypatch):
+    fqns = {
+        Address(1): "contracts/Caller.sol:Caller",
+        Address(2): "contracts/Counter.sol:Counter",
+    }
+    monkeypatch.setattr(
+        wake.development.core,
+        "get_fqn_from_address",
+        lambda addr, block, chain: fqns[addr],
+    )
+    monkeypatch.setitem(
+        wake.development.core.contracts_by_fqn,
+        "contracts/Counter.sol:Counter",
+        (__name__, ("_Callee",)),
+    )
+    tx = SimpleNamespace(
+        to=SimpleNamespace(address=Address(1)),
+        data=bytes.fromhex("11223344"),
+        block=SimpleNamespace(number=1),
+        chain=None,
+    )
+    panic_memory = ["4e487b71" + "00" * 28]
+    debug_trace = {
+        "structLogs": [
+            {
+                "op": "CALL",
+                "pc": 0,
+                "depth": 1,
+                # argsSize, argsOffset, value, address, gas
+                "stack": ["0x4", "0x0", "0x0", "0x2", "0xffff"],
+                "memory": ["aabbccdd" + "00" * 28],
+            },
+            # panic in the callee bubbled up unchanged by the caller
+            {
+                "op": "REVERT",
+                "pc": 5,
+                "depth": 2,
+                "stack": ["0x4", "0x0"],
+                "memory": panic_memory,
+            },
+            {
+                "op": "REVERT",
+                "pc": 9,
+                "depth": 1,
+                "stack": ["0x4", "0x0"],
+                "memory": panic_memory,
+            },
+        ]
+    }
+
+    assert (
+        process_debug_trace_for_panic_location(tx, debug_trace)  # pyright: ignore
+        == "Counter.decrement"
+    )
@@ -0,0 +1,46 @@
+from wake.development.call_trace import get_precompiled_gas_cost
+from wake.development.core import Address
+
+
+def _modexp_input(base: bytes, exp: bytes, mod: bytes) -> bytes:
+    return (
+        len(base).to_bytes(32, "big")
+        + len(exp).to_bytes(32, "big")
+        + len(mod).to_bytes(32, "big")
+        + base
+        + exp
+        + mod
+    )
+
+
+def test_costs_keyed_by_hardfork():
+    pairing_input = bytes(2 * 192)
+    assert get_precompiled_gas_cost(Address(8), pairing_input, "BYZANTIUM") == 260000
+    assert get_precompiled_gas_cost(Address(8), pairing_input, "CANCUN") == 113000
+    assert get_precompiled_gas_cost(Address(7), b"", "PETERSBURG") == 40000
+    assert get_precompiled_gas_cost(Address(7), b"", "ISTANBUL") == 6000
+
+    # 3^0xffff mod 2^256 - 1
+    data = _modexp_input(b"\x03", b"\xff\xff", b"\xff" * 32)
+    assert get_precompiled_gas_cost(Address(5), data, "LONDON") == 200
+    assert get_precompiled_gas_cost(Address(5), data, "OSAKA") == 500
+
+    # latest hardfork by default
+    assert get_precompiled_gas_cost(Address(5), data) == 500
+
+
+def test_unknown_precompiles():
+    assert get_precompiled_gas_cost(Address(10), bytes(192), "SHANGHAI") is None
+    assert get_precompiled_gas_cost(Address(10), bytes(192), "CANCUN") == 50000
+
+    # BLS12-381
+    assert get_precompiled_gas_cost(Address(0x0B), bytes(256), "CANCUN") is None
+    assert get_precompiled_gas_cost(Address(0x0B), bytes(256), "PRAGUE") == 375
+    # G1MSM is not priced
+    assert get_precompiled_gas_cost(Address(0x0C), bytes(160), "PRAGUE") is None
+
+    # P256VERIFY
+    assert get_precompiled_gas_cost(Address(0x100), bytes(160), "PRAGUE") is None
+    assert get_precompiled_gas_cost(Address(0x100), bytes(160), "OSAKA") == 6900
+
+    assert get_precompiled_gas_cost(Address(0x1234), b"") is None
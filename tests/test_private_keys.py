@@ -0,0 +1,83 @@
+import pytest
+
+from wake.config import WakeConfig
+from wake.development.core import Account, Address, _PrivateKeysIndex
+from wake.development.core import _private_keys_index as global_private_keys_index
+from wake.development.globals import get_config, set_config
+from wake.testing.core import Chain
+
+KEY = bytes.fromhex("4c0883a69102937d6231471b5decb335d5c22fa3bd7e4a8d29c3e8fbac7bf8f1")
+OTHER_KEY = bytes.fromhex(
+    "8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f"
+)
+
+
+@pytest.mark.parametrize("encrypt", [True, False])
+def test_private_keys_index_roundtrip(encrypt: bool):
+    config = get_config()
+    set_config(WakeConfig.fromdict({"general": {"encrypt_private_keys": encrypt}}))
+    try:
+        index = _PrivateKeysIndex()
+        address = Address(1)
+        index[address] = KEY
+
+        assert address in index
+        assert index.get(address) == KEY
+        assert index.get(Address(2)) is None
+        with index.use(address) as key:
+            assert key == KEY
+
+        if encrypt:
+            assert KEY not in index._keys[address]
+        else:
+            assert index._keys[address] == KEY
+
+        index.clear()
+        assert address not in index
+    finally:
+        set_config(config)
+
+
+def test_private_keys_index_zeroizes_buffer(monkeypatch):
+    index = _PrivateKeysIndex()
+    index[Address(1)] = KEY
+
+    buffers = []
+    decrypt = index._decrypt
+
+    def _decrypt(address):
+        buffer = decrypt(address)
+        buffers.append(buffer)
+        return buffer
+
+    monkeypatch.setattr(index, "_decrypt", _decrypt)
+
+    with index.use(Address(1)) as key:
+        # the buffer itself is passed to the signing library, not a `bytes` copy
+        assert isinstance(key, bytearray)
+        assert key == KEY
+    assert key == bytearray(len(KEY))
+    assert index.get(Address(1)) == KEY
+
+    assert len(buffers) == 2
+    assert all(buffer == bytearray(len(KEY)) for buffer in buffers)
+
+
+def test_private_key_deprecated():
+    address = Address.from_key(KEY)
+    assert address.has_signer
+
+    with pytest.warns(DeprecationWarning, match="export_private_key"):
+        assert address.private_key == KEY
+    assert address.export_private_key() == KEY
+
+
+def test_account_key_registered_per_chain_by_default(chain: Chain):
+    account = Account.from_key(KEY, chain=chain)
+    assert account.has_signer
+    assert account.address in chain._private_keys_index
+    assert account.address not in global_private_keys_index
+
+    account = Account.from_key(OTHER_KEY, chain=chain, per_chain=False)
+    assert account.address in global_private_keys_index
+    assert account.address not in chain._private_keys_index
@@ -0,0 +1,63 @@
+import eth_utils
+
+from wake.development.core import Account, Address
+from wake.development.internal import ExternalEvent, UnknownEvent
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+TRANSFER_ABI = {
+    "type": "event",
+    "name": "Transfer",
+    "anonymous": False,
+    "inputs": [
+        {"name": "from", "type": "address", "indexed": True},
+        {"name": "id", "type": "uint256", "indexed": True},
+        {"name": "memo", "type": "string", "indexed": True},
+        {"name": "value", "type": "uint256", "indexed": False},
+    ],
+}
+TRANSFER_TOPIC = eth_utils.abi.event_abi_to_log_topic(TRANSFER_ABI)
+METADATA = bytes(range(53))
+
+
+def _transfer_event(chain: Chain) -> UnknownEvent:
+    event = UnknownEvent(
+        [
+            TRANSFER_TOPIC,
+            bytes(12) + bytes(Address(0xAB)),
+            (7).to_bytes(32, "big"),
+            eth_utils.keccak(text="memo"),
+        ],
+        (100).to_bytes(32, "big"),
+    )
+    event.origin = Account(Address(1), chain)
+    return event
+
+
+def test_decode_indexed_arguments(chain: Chain):
+    event = chain._decode_registered_event(TRANSFER_ABI, _transfer_event(chain))
+
+    assert isinstance(event, ExternalEvent)
+    assert event.name == "Transfer"
+    assert event.arguments["from"] == Address(0xAB)
+    assert event.arguments["id"] == 7
+    assert event.arguments["memo"] == eth_utils.keccak(text="memo")
+    assert event.arguments["value"] == 100
+
+
+def test_metadata_lookup_cached(chain: Chain, node: FakeNode):
+    node.handlers["eth_getCode"] = lambda address, block: "0x" + (
+        b"\x60\x80" + METADATA
+    ).hex()
+    chain.register_abi(METADATA, [TRANSFER_ABI])
+
+    metadata_cache = {}
+    for _ in range(3):
+        assert (
+            chain._find_registered_event_abi(
+                Address(1), TRANSFER_TOPIC, 0, metadata_cache
+            )
+            == TRANSFER_ABI
+        )
+    assert node.requests.count("eth_getCode") == 1
@@ -0,0 +1,260 @@
+import gc
+
+import pytest
+
+from wake.development.core import RevertToSnapshotFailedError, Snapshot
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def _tx_hash(i: int) -> str:
+    return "0x" + format(i, "064x")
+
+
+def _mine_tx(chain: Chain, node: FakeNode, i: int) -> None:
+    # cached transactions are represented by their index
+    node.mine()
+    chain.txs._transactions[_tx_hash(i)] = i  # pyright: ignore
+    chain.txs.register_tx(_tx_hash(i))
+    chain.blocks[node.block_number]
+
+
+def test_history_limit_prunes_txs_and_blocks(chain: Chain, node: FakeNode):
+    chain.txs.history_limit = 3
+    chain.blocks.history_limit = 3
+
+    _mine_tx(chain, node, 0)
+    chain.txs.pin(_tx_hash(0))
+    chain.blocks.pin(1)
+    for i in range(1, 6):
+        _mine_tx(chain, node, i)
+
+    assert len(chain.txs) == 6
+    assert chain.txs[5] == 5
+    assert chain.txs[-1] == 5
+    assert chain.txs[3] == 3
+    with pytest.raises(IndexError):
+        chain.txs[2]
+
+    # pinned entries are kept in addition to the most recent ones
+    assert set(chain.txs._transactions) == {_tx_hash(i) for i in (0, 3, 4, 5)}
+    assert set(chain.blocks._blocks) == {1, 5, 6}
+    assert chain.txs[_tx_hash(0)] == 0
+
+    chain.txs.unpin(_tx_hash(0))
+    chain.blocks.unpin(1)
+    _mine_tx(chain, node, 6)
+    assert set(chain.txs._transactions) == {_tx_hash(i) for i in (4, 5, 6)}
+    assert set(chain.blocks._blocks) == {5, 6, 7}
+
+
+def test_revert_restores_pruned_history(chain: Chain, node: FakeNode):
+    chain.txs.history_limit = 2
+
+    _mine_tx(chain, node, 0)
+    _mine_tx(chain, node, 1)
+    snapshot = chain.snapshot()
+    for i in range(2, 5):
+        _mine_tx(chain, node, i)
+    assert len(chain.txs) == 5
+    assert chain.txs._tx_hashes_offset == 3
+
+    chain.revert(snapshot)
+    assert node.block_number == 2
+    assert len(chain.txs) == 2
+    assert chain.txs._tx_hashes_offset == 0
+    assert chain.txs[0] == 0 and chain.txs[1] == 1
+    assert set(chain.blocks._blocks) == {1, 2}
+
+
+def test_gas_snapshot_rejects_pruned_txs(chain: Chain, node: FakeNode):
+    chain.txs.history_limit = 2
+
+    _mine_tx(chain, node, 0)
+    with pytest.raises(RuntimeError, match="pruned"):
+        with chain.gas_snapshot("pruned"):
+            for i in range(1, 4):
+                _mine_tx(chain, node, i)
+
+
+def test_snapshot_object(chain: Chain, node: FakeNode):
+    _mine_tx(chain, node, 0)
+
+    node.requests.clear()
+    snapshot = chain.snapshot()
+    assert isinstance(snapshot, Snapshot)
+    assert snapshot == snapshot.id == "0x0"
+    assert snapshot.chain is chain
+    # the block is only fetched on revert
+    assert node.requests == ["evm_snapshot"]
+    assert snapshot.block_number is None
+
+    _mine_tx(chain, node, 1)
+    snapshot.revert()
+    assert node.block_number == 1
+    assert len(chain.txs) == 1
+    assert snapshot.block_number == 1
+    assert snapshot.timestamp == 1_001
+
+
+def test_double_revert_detected(chain: Chain, node: FakeNode):
+    first = chain.snapshot()
+    _mine_tx(chain, node, 0)
+    second = chain.snapshot()
+    _mine_tx(chain, node, 1)
+
+    chain.revert(first)
+    assert chain._revert_count == 1
+
+    # reverting to an earlier snapshot invalidates the later one
+    with pytest.raises(RevertToSnapshotFailedError):
+        chain.revert(second)
+    with pytest.raises(RevertToSnapshotFailedError):
+        first.revert()
+    # snapshot IDs are accepted as plain strings
+    with pytest.raises(RevertToSnapshotFailedError):
+        chain.revert(first.id)
+    assert chain._revert_count == 1
+
+
+def test_with_rollback(chain: Chain, node: FakeNode):
+    _mine_tx(chain, node, 0)
+    chain._nonces["alice"] = 1
+    outer = chain.snapshot()
+
+    with pytest.raises(RuntimeError):
+        with chain.with_rollback():
+            _mine_tx(chain, node, 1)
+            _mine_tx(chain, node, 2)
+            chain._nonces["alice"] = 3
+            chain._written_slots.add(("alice", 0))
+            inner = chain.snapshot()
+            raise RuntimeError()
+
+    assert node.block_number == 1
+    assert len(chain.txs) == 1
+    assert set(chain.txs._transactions) == {_tx_hash(0)}
+    assert set(chain.blocks._blocks) == {1}
+    assert chain._nonces["alice"] == 1
+    assert len(chain._written_slots) == 0
+    assert chain._revert_count == 1
+
+    # snapshots taken inside the context are invalidated, earlier ones are kept
+    with pytest.raises(RevertToSnapshotFailedError):
+        chain.revert(inner)
+    chain.revert(outer)
+
+
+def test_snapshot_kept_by_string_id(chain: Chain, node: FakeNode):
+    snapshot_id = chain.snapshot().id
+    chain._nonces["alice"] = 1
+    gc.collect()
+    chain.compact()
+
+    # snapshots are not released when the Snapshot object is garbage collected
+    assert chain.journal_stats.checkpoints == 1
+    chain.revert(snapshot_id)
+    assert "alice" not in chain._nonces
+
+
+def test_released_snapshot(chain: Chain, node: FakeNode):
+    kept = chain.snapshot()
+    chain._nonces["alice"] = 1
+    released = chain.snapshot()
+    chain._nonces["alice"] = 2
+    assert chain.journal_stats.checkpoints == 2
+
+    released.release()
+    assert chain.journal_stats.checkpoints == 1
+    assert len(chain._snapshots) == 1
+    with pytest.raises(RevertToSnapshotFailedError):
+        released.revert()
+
+    kept.revert()
+    assert "alice" not in chain._nonces
+
+
+def test_evicted_snapshot_not_reverted(chain: Chain, node: FakeNode):
+    evicted = chain.snapshot()
+    chain._nonces["alice"] = 1
+    kept = chain.snapshot()
+    chain.journal_limit = 1
+    with pytest.warns(RuntimeWarning, match=evicted.id):
+        chain._nonces["alice"] = 2
+
+    with pytest.raises(RevertToSnapshotFailedError):
+        evicted.revert()
+
+    kept.revert()
+    assert chain._nonces["alice"] == 1
+
+
+def test_with_rollback_evicted_checkpoint(chain: Chain, node: FakeNode):
+    _mine_tx(chain, node, 0)
+    chain.journal_limit = 1
+
+    with pytest.raises(RevertToSnapshotFailedError):
+        with pytest.warns(RuntimeWarning):
+            with chain.with_rollback():
+                _mine_tx(chain, node, 1)
+                chain._nonces["alice"] = 1
+                chain._nonces["bob"] = 1
+
+    # the chain is rolled back even though the checkpoint was evicted
+    assert node.block_number == 1
+    assert len(chain.txs) == 1
+    assert "alice" not in chain._nonces
+
+    # the exception raised inside the context is not masked
+    with pytest.raises(ValueError):
+        with pytest.warns(RuntimeWarning):
+            with chain.with_rollback():
+                _mine_tx(chain, node, 1)
+                chain._nonces["alice"] = 1
+                chain._nonces["bob"] = 1
+                raise ValueError()
+    assert node.block_number == 1
+
+
+def test_snapshots_released_on_disconnect(node: FakeNode):
+    chain = Chain()
+    with chain.connect():
+        snapshot = chain.snapshot()
+        chain._nonces["alice"] = 1
+
+    assert chain._snapshots == {}
+    assert chain._journal.stats.entries == 0
+    assert chain._journal.stats.checkpoints == 0
+
+
+def test_tx_batch_flushed_before_revert(chain: Chain, node: FakeNode):
+    batches = []
+    chain.set_batched_tx_callback(
+        lambda txs: batches.append((txs, node.requests.count("evm_revert")))
+    )
+
+    snapshot = chain.snapshot()
+    chain._add_to_tx_batch(0)  # pyright: ignore
+    chain.revert(snapshot)
+    assert batches == [([0], 0)]
+
+    with chain.with_rollback():
+        chain._add_to_tx_batch(1)  # pyright: ignore
+    assert batches[1] == ([1], 1)
+
+
+def test_revert_validates_head_lazily(chain: Chain, node: FakeNode):
+    _mine_tx(chain, node, 0)
+    raw_snapshot_id = chain.snapshot().id
+    _mine_tx(chain, node, 1)
+
+    node.requests.clear()
+    # only the snapshot ID is kept, the snapshot block is not needed
+    chain.revert(raw_snapshot_id)
+    assert "eth_getBlockByNumber" not in node.requests
+
+    # the cached block 2 of the abandoned branch is dropped on the next access
+    node.mine()
+    assert chain.blocks[2].parent_hash == chain.blocks[1].hash
+    chain.blocks.verify()
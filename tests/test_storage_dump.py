@@ -0,0 +1,79 @@
+import gzip
+import json
+from typing import Dict
+
+from wake.development.core import Account, Address
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+
+def test_storage_dump_reads_requested_slots(chain: Chain, node: FakeNode):
+    storage = {0: 1, 5: 0, 7: 2}
+
+    def get_storage_at(address: str, slot: str, block: str) -> str:
+        return "0x" + format(storage.get(int(slot, 16), 0), "064x")
+
+    node.handlers["eth_getStorageAt"] = get_storage_at
+    account = Account(Address(0x1234), chain)
+
+    assert account.storage_dump(slots=[7, 5, 0, 7], start=1) == {
+        7: (2).to_bytes(32, "big")
+    }
+    assert account.storage_dump(slots=range(10), limit=1) == {
+        0: (1).to_bytes(32, "big")
+    }
+    assert "anvil_dumpState" not in node.requests
+
+
+def _dump_state(storage: Dict[int, int]) -> str:
+    state = {
+        "accounts": {
+            str(Address(0x1234)): {
+                "storage": {hex(k): hex(v) for k, v in storage.items()}
+            }
+        }
+    }
+    return "0x" + gzip.compress(json.dumps(state).encode("utf-8")).hex()
+
+
+def test_storage_dump_pages_reuse_state_dump(chain: Chain, node: FakeNode):
+    node.handlers["anvil_dumpState"] = lambda: _dump_state({1: 1, 2: 2, 3: 3})
+    account = Account(Address(0x1234), chain)
+
+    assert account.storage_dump(limit=2) == {
+        1: (1).to_bytes(32, "big"),
+        2: (2).to_bytes(32, "big"),
+    }
+    assert account.storage_dump(limit=2, start=3) == {3: (3).to_bytes(32, "big")}
+    assert node.requests.count("anvil_dumpState") == 1
+
+    # the last page was not truncated, the state is dumped again
+    account.storage_dump(limit=2, start=3)
+    assert node.requests.count("anvil_dumpState") == 2
+
+    # a new block invalidates the dump
+    account.storage_dump(limit=1)
+    node.mine()
+    account.storage_dump(limit=1, start=2)
+    assert node.requests.count("anvil_dumpState") == 4
+
+
+def test_storage_dump_reads_fork_storage_range(chain: Chain, node: FakeNode):
+    def storage_range_at(block_hash, tx_index, address, start_key, max_results):
+        assert address == str(Address(0x1234))
+        return {
+            "storage": {
+                "0x" + "aa" * 32: {"key": hex(5), "value": hex(7)},
+                "0x" + "bb" * 32: {"key": None, "value": hex(1)},
+            },
+            "nextKey": None,
+        }
+
+    node.handlers["debug_storageRangeAt"] = storage_range_at
+    chain._forked_chain_id = 1
+
+    assert Account(Address(0x1234), chain).storage_dump() == {
+        5: (7).to_bytes(32, "big")
+    }
+    assert "anvil_dumpState" not in node.requests
@@ -0,0 +1,184 @@
+from typing import Dict, List, Tuple
+
+import pytest
+
+from wake.development.utils import (
+    StorageLayoutIncompatible,
+    assert_storage_compatible,
+    check_storage_compatibility,
+)
+
+TYPES = {
+    "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": 20},
+    "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": 32},
+    "t_array(t_uint256)49_storage": {
+        "encoding": "inplace",
+        "label": "uint256[49]",
+        "numberOfBytes": 49 * 32,
+        "base": "t_uint256",
+    },
+    "t_array(t_uint256)50_storage": {
+        "encoding": "inplace",
+        "label": "uint256[50]",
+        "numberOfBytes": 50 * 32,
+        "base": "t_uint256",
+    },
+}
+
+
+def _struct(type_id: str, ast_id: int) -> Dict:
+    return {
+        "encoding": "inplace",
+        "label": "struct Vault.Position",
+        "numberOfBytes": 64,
+        "members": [
+            {
+                "astId": ast_id + 1,
+                "contract": "Vault",
+                "label": "owner",
+                "offset": 0,
+                "slot": 0,
+                "type": "t_address",
+            },
+            {
+                "astId": ast_id + 2,
+                "contract": "Vault",
+                "label": "amount",
+                "offset": 0,
+                "slot": 1,
+                "type": "t_uint256",
+            },
+        ],
+    }
+
+
+def _contract(variables: List[Tuple[str, int, str]], struct_ast_id: int = 10):
+    struct_id = f"t_struct(Position){struct_ast_id}_storage"
+    types = {**TYPES, struct_id: _struct(struct_id, struct_ast_id)}
+    storage = [
+        {
+            "astId": i,
+            "contract": "Vault",
+            "label": label,
+            "offset": 0,
+            "slot": slot,
+            "type": struct_id if type_id == "position" else type_id,
+        }
+        for i, (label, slot, type_id) in enumerate(variables)
+    ]
+    return type("Vault", (), {"_storage_layout": {"storage": storage, "types": types}})
+
+
+V1 = _contract(
+    [
+        ("owner", 0, "t_address"),
+        ("position", 1, "position"),
+        ("__gap", 3, "t_array(t_uint256)50_storage"),
+    ]
+)
+
+
+def test_storage_compatible_upgrade():
+    # new variable taking a slot of the gap, struct type with different AST IDs
+    v2 = _contract(
+        [
+            ("owner", 0, "t_address"),
+            ("position", 1, "position"),
+            ("fee", 3, "t_uint256"),
+            ("__gap", 4, "t_array(t_uint256)49_storage"),
+        ],
+        struct_ast_id=20,
+    )
+    assert check_storage_compatibility(V1, v2) == []
+    assert_storage_compatible(V1, v2)
+
+
+def test_storage_incompatible_upgrade():
+    v2 = _contract(
+        [
+            ("owner", 0, "t_uint256"),
+            ("fee", 1, "t_uint256"),
+            ("__gap", 2, "t_array(t_uint256)49_storage"),
+        ]
+    )
+    changes = {(c.label, c.kind) for c in check_storage_compatibility(V1, v2)}
+    assert changes == {
+        ("owner", "retyped"),
+        ("position", "removed"),
+        ("__gap", "moved"),
+        ("__gap", "retyped"),
+    }
+
+    with pytest.raises(StorageLayoutIncompatible) as e:
+        assert_storage_compatible(V1, v2)
+    assert len(e.value.changes) == 4
+
+
+def test_storage_variable_renamed_in_place():
+    v2 = _contract(
+        [
+            ("admin", 0, "t_address"),
+            ("position", 1, "position"),
+            ("__gap", 3, "t_array(t_uint256)50_storage"),
+        ]
+    )
+    changes = check_storage_compatibility(V1, v2)
+    assert [(c.label, c.kind) for c in changes] == [("owner", "renamed")]
+
+    with pytest.warns(UserWarning, match="renamed to admin"):
+        assert_storage_compatible(V1, v2)
+
+
+def _positions_contract(members: int, in_mapping: bool):
+    struct = _struct("t_struct(Position)10_storage", 10)
+    if members > 2:
+        struct["numberOfBytes"] = 96
+        struct["members"].append(
+            {
+                "astId": 13,
+                "contract": "Vault",
+                "label": "fee",
+                "offset": 0,
+                "slot": 2,
+                "type": "t_uint256",
+            }
+        )
+    mapping_id = "t_mapping(t_address,t_struct(Position)10_storage)"
+    types = {
+        **TYPES,
+        "t_struct(Position)10_storage": struct,
+        mapping_id: {
+            "encoding": "mapping",
+            "label": "mapping(address => struct Vault.Position)",
+            "numberOfBytes": 32,
+            "key": "t_address",
+            "value": "t_struct(Position)10_storage",
+        },
+    }
+    storage = [
+        {
+            "astId": 1,
+            "contract": "Vault",
+            "label": "positions",
+            "offset": 0,
+            "slot": 0,
+            "type": mapping_id if in_mapping else "t_struct(Position)10_storage",
+        }
+    ]
+    return type("Vault", (), {"_storage_layout": {"storage": storage, "types": types}})
+
+
+def test_struct_member_appended():
+    # structs stored only as mapping values may grow
+    assert (
+        check_storage_compatibility(
+            _positions_contract(2, True), _positions_contract(3, True)
+        )
+        == []
+    )
+
+    # inline structs would overlap the following variables
+    changes = check_storage_compatibility(
+        _positions_contract(2, False), _positions_contract(3, False)
+    )
+    assert [(c.label, c.kind) for c in changes] == [("positions", "retyped")]
@@ -0,0 +1,38 @@
+import pytest
+
+from wake.development.core import Address
+from wake.testing.core import Chain
+
+from .conftest import FakeNode
+
+TOKEN = Address("0x" + "22" * 20)
+
+
+def test_token_flows_since_first_local_block(node: FakeNode):
+    node.mine()
+    node.mine()
+    chain = Chain()
+    with chain.connect():
+        filters = []
+
+        def get_logs(params):
+            filters.append(params)
+            return []
+
+        node.handlers["eth_getLogs"] = get_logs
+        node.mine()
+
+        flows = chain.token_flows(TOKEN)
+        assert flows.token == TOKEN
+        # blocks mined before the chain was connected are not aggregated
+        assert filters[0]["fromBlock"] == hex(3)
+        assert filters[0]["toBlock"] == hex(3)
+
+
+def test_ether_flows_require_range(chain: Chain, node: FakeNode):
+    node.mine()
+
+    with pytest.raises(ValueError, match="since_block and to_block"):
+        chain.token_flows(None)
+    with pytest.raises(ValueError, match="since_block and to_block"):
+        chain.token_flows(None, since_block=1)
@@ -0,0 +1,104 @@
+from typing import List
+
+import pytest
+
+from wake.testing.core import Chain, _order_transactions
+
+from .conftest import FakeNode
+
+ALICE = "0x" + "aa" * 20
+BOB = "0x" + "bb" * 20
+
+
+class _Tx:
+    def __init__(self, tx_hash: str, sender: str, nonce: int, priority_fee: int):
+        self.tx_hash = tx_hash
+        self._tx_params = {
+            "from": sender,
+            "nonce": nonce,
+            "maxFeePerGas": 100,
+            "maxPriorityFeePerGas": priority_fee,
+        }
+
+
+def _hash(i: int) -> str:
+    return "0x" + format(i, "064x")
+
+
+def _txs() -> List[_Tx]:
+    return [
+        _Tx(_hash(1), ALICE, 0, 1),
+        _Tx(_hash(2), BOB, 0, 5),
+        _Tx(_hash(3), ALICE, 1, 10),
+    ]
+
+
+def test_order_fifo():
+    txs = _txs()
+    assert _order_transactions(txs, "fifo", 0) == txs  # pyright: ignore
+
+
+def test_order_fees_keeps_nonce_order():
+    txs = _txs()
+    # alice's second tx pays the most but cannot precede her first tx
+    assert _order_transactions(txs, "fees", 0) == [  # pyright: ignore
+        txs[1],
+        txs[0],
+        txs[2],
+    ]
+
+
+def test_order_comparator():
+    txs = _txs()
+
+    def bob_first(a, b) -> int:
+        return (a._tx_params["from"] != BOB) - (b._tx_params["from"] != BOB)
+
+    assert _order_transactions(txs, bob_first, 0)[0] is txs[1]  # pyright: ignore
+
+
+def _setup_pool(chain: Chain, node: FakeNode, fee_ordering: bool) -> List[_Tx]:
+    txs = _txs()
+    txs[2]._tx_params["maxPriorityFeePerGas"] = 1
+    fees = {tx.tx_hash: tx._tx_params["maxPriorityFeePerGas"] for tx in txs}
+    pool: List[str] = [tx.tx_hash for tx in txs]
+
+    def pending_block(block_identifier: str, include_transactions: bool = False):
+        block = node._get_block(block_identifier, include_transactions)
+        if block_identifier == "pending":
+            # stable sort keeps the submission order of equal fees like Anvil
+            block["transactions"] = (
+                sorted(pool, key=lambda h: -fees[h]) if fee_ordering else list(pool)
+            )
+        return block
+
+    node.handlers["eth_getBlockByNumber"] = pending_block
+    node.handlers["anvil_dropTransaction"] = lambda tx_hash: pool.remove(tx_hash)
+
+    def submit(tx_params, from_):
+        tx_hash = next(tx.tx_hash for tx in txs if tx._tx_params == tx_params)
+        pool.append(tx_hash)
+        return tx_hash
+
+    chain._submit_transaction = submit  # pyright: ignore
+    for tx in txs:
+        chain._txs._transactions[tx.tx_hash] = tx  # pyright: ignore
+        chain._txs._tx_hashes.append(tx.tx_hash)
+    return txs
+
+
+def test_reorder_verified(chain: Chain, node: FakeNode):
+    _setup_pool(chain, node, fee_ordering=False)
+    chain.tx_ordering = lambda a, b: -1 if a._tx_params["from"] == BOB else 1
+
+    chain._reorder_pending_txs()
+    assert node.requests.count("anvil_dropTransaction") == 3
+
+
+def test_reorder_fee_ordered_client_rejected(chain: Chain, node: FakeNode):
+    _setup_pool(chain, node, fee_ordering=True)
+    # bob pays the highest priority fee, a fee-ordering client includes bob's tx first
+    chain.tx_ordering = "fifo"
+
+    with pytest.raises(RuntimeError, match="--order fifo"):
+        chain._reorder_pending_txs()
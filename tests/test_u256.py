@@ -0,0 +1,54 @@
+import eth_abi.abi
+import pytest
+
+from wake.development.core import Abi, _validate_abi_value
+from wake.development.primitive_types import U256, uint256, wrapping_cast
+
+
+def test_u256_arithmetic():
+    a = U256(5)
+
+    assert isinstance(a, uint256)
+    assert isinstance(a + 1, U256) and a + 1 == 6
+    assert isinstance(10 - a, U256) and 10 - a == 5
+    assert isinstance(a * a, U256) and a * a == 25
+    assert isinstance(2**a, U256) and 2**a == 32
+    assert isinstance(a // 2, U256) and a // 2 == 2
+    assert a > 4 and a <= 5 and a == 5
+
+
+def test_u256_overflow():
+    with pytest.raises(OverflowError):
+        U256(5) - 6
+    with pytest.raises(OverflowError):
+        4 - U256(5)
+    with pytest.raises(OverflowError):
+        U256.max + 1
+    with pytest.raises(OverflowError):
+        U256(2**128) * 2**128
+    with pytest.raises(ValueError):
+        U256(-1)
+    with pytest.raises(TypeError):
+        U256(2) ** -1
+
+
+def test_u256_wrapping():
+    assert U256(0).wrapping_sub(1) == U256.max
+    assert U256.max.wrapping_add(2) == 1
+    assert U256(2**255).wrapping_mul(2) == 0
+    assert ~U256(0) == U256.max
+    assert U256.max << 1 == U256.max - 1
+    assert wrapping_cast(U256, -1) == U256.max
+
+
+def test_u256_abi_encoding_fast_path(monkeypatch):
+    expected = (5).to_bytes(32, "big") + (2**256 - 1).to_bytes(32, "big")
+    assert Abi.encode(["uint256", "uint256"], [5, 2**256 - 1]) == expected
+
+    def encode(types, arguments):
+        raise AssertionError("eth_abi must not be used for U256 arguments")
+
+    monkeypatch.setattr(eth_abi.abi, "encode", encode)
+    assert Abi.encode(["uint256", "uint256"], [U256(5), U256.max]) == expected
+    # range already checked when the value was created
+    _validate_abi_value({"type": "uint256"}, U256.max, "amount")
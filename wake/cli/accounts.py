@@ -154,24 +154,27 @@ def accounts_remove(ctx: Context, keystore: Optional[str], alias: str):
 @click.pass_context
 def accounts_list(ctx: Context, keystore: Optional[str]):
     """
-    List all accounts.
+    List all accounts. Without --keystore, all keystores in the search path are listed in order of precedence.
     """
-    import json
-
     from eth_utils.address import to_checksum_address
 
+    from wake.development.core import list_keystore_aliases
+    from wake.development.globals import set_config
+
+    set_config(ctx.obj["config"])
+
     if keystore is None:
-        path = Path(ctx.obj["config"].global_data_path) / "keystore"
+        aliases = list_keystore_aliases()
     else:
-        path = Path(keystore)
-    if not path.is_dir():
-        return
-
-    for file in sorted(path.iterdir()):
-        if file.suffix == ".json" and file.is_file():
-            with file.open() as f:
-                k = json.load(f)
-            console.print(f"{file.stem}: {to_checksum_address(k['address'])}")
+        aliases = list_keystore_aliases([Path(keystore)])
+
+    for alias in aliases:
+        address = to_checksum_address(str(alias.address))
+        line = f"{alias.alias}: {address} ({alias.path.parent})"
+        if alias.shadowed:
+            console.print(f"[dim]{line} (shadowed)[/dim]")
+        else:
+            console.print(line)
 
 
 @run_accounts.command(name="import")
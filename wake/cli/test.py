@@ -33,10 +33,10 @@ def run_no_pytest(
         attach_debugger,
         chain_interfaces_manager,
         get_coverage_handler,
-        random,
         reset_exception_handled,
         set_coverage_handler,
         set_exception_handler,
+        set_random_seed,
     )
     from wake.testing.coverage import (
         CoverageHandler,
@@ -84,7 +84,7 @@ def run_no_pytest(
             test_functions.append((func_name, func))
 
     if proc_count == 1:
-        random.seed(random_seeds[0])
+        set_random_seed(random_seeds[0])
         console.print(f"Using random seed {random_seeds[0].hex()}")
 
         if debug:
@@ -350,11 +350,48 @@ class HardhatConfig(WakeConfigModel):
     """
 
 
+class GasSnapshotConfig(WakeConfigModel):
+    path: Annotated[Path, BeforeValidator(lambda p: Path(p).resolve())] = Field(
+        default_factory=lambda: Path.cwd() / ".gas-snapshot.json"
+    )
+    """
+    Path to the JSON file with recorded gas snapshots.
+    """
+    tolerance: float = Field(default=0.0, ge=0.0)
+    """
+    Maximum allowed relative increase of gas used compared to the recorded value (e.g. `0.05` for 5 %).
+    """
+    update: bool = False
+    """
+    Overwrite recorded gas snapshots instead of failing on gas regressions.
+    """
+
+
 class TestingConfig(WakeConfigModel):
     cmd: str = "anvil"
     """
     Which development chain to use for testing. Should be one of `anvil`, `ganache` or `hardhat`.
     """
+    fork_timeout: Optional[float] = Field(default=None, gt=0)
+    """
+    Timeout in seconds of requests sent by the development chain to the forked chain RPC endpoint (Anvil only).
+    """
+    fork_timeout_fallback: bool = False
+    """
+    Return the last successfully fetched result of a state read (balance, code, storage, nonce, block) when the request times out
+    because the forked chain RPC endpoint stalls, instead of raising `ForkTimeout`. Only reads at an explicit block number
+    or hash are served from the cache, reads at block tags such as `latest` always raise `ForkTimeout`.
+    """
+    deterministic_timestamps: bool = False
+    """
+    Launch non-forked Anvil chains from a genesis timestamp derived from the random seed of the test run and mine each block
+    1 second after its parent, so that block timestamps and hashes only depend on the seed and the chain history.
+    If `False`, Anvil derives block timestamps from the wall clock.
+    """
+    gas_snapshot: GasSnapshotConfig = Field(default_factory=GasSnapshotConfig)
+    """
+    Gas snapshot regression testing config options.
+    """
     anvil: AnvilConfig = Field(default_factory=AnvilConfig)
     """
     Anvil-specific config options.
@@ -381,6 +418,10 @@ class DeploymentConfig(WakeConfigModel):
 
 
 class GeneralConfig(WakeConfigModel):
+    block_history_limit: Optional[int] = Field(default=None, gt=0)
+    """
+    Maximum number of blocks cached in `chain.blocks`, `None` for unlimited.
+    """
     call_trace_options: FrozenSet[str] = frozenset(
         [
             "contract_name",
@@ -396,14 +437,60 @@ class GeneralConfig(WakeConfigModel):
     """
     Options to include in call traces.
     """
+    encrypt_private_keys: bool = True
+    """
+    Keep imported private keys encrypted in memory and decrypt them only for the time of signing.
+    """
+    error_abis: FrozenSet[
+        Annotated[Path, BeforeValidator(lambda p: Path(p).resolve())]
+    ] = frozenset()
+    """
+    JSON ABI files (or compilation artifacts with an `abi` key) used to decode custom errors not found in `pytypes`.
+    """
+    explorer_cache: bool = True
+    """
+    Cache successful chain explorer responses on disk.
+    """
+    explorer_requests_per_second: float = Field(default=5, ge=0)
+    """
+    Maximum number of requests per second sent to chain explorers, `0` for unlimited.
+    """
+    gas_limit_multiplier: float = Field(default=1.2, ge=1.0)
+    """
+    Multiplier applied to gas estimates when `gas_limit="auto"` is used.
+    """
     json_rpc_timeout: float = 15
     """
     Timeout applied to JSON-RPC requests.
     """
+    keystores: List[Annotated[Path, BeforeValidator(lambda p: Path(p).resolve())]] = []
+    """
+    Keystore directories searched for account aliases (in this order) before the global keystore.
+    """
     link_format: str = "vscode://file/{path}:{line}:{col}"
     """
     Format of links used in detectors and printers.
     """
+    max_log_data_size: Optional[int] = Field(default=None, ge=0)
+    """
+    Maximum number of bytes of event (log) data kept for each event of a transaction, `None` for unlimited.
+    """
+    max_return_data_size: Optional[int] = Field(default=None, ge=0)
+    """
+    Maximum number of bytes of return data kept for a transaction, `None` for unlimited.
+    """
+    max_revert_data_size: Optional[int] = Field(default=None, ge=0)
+    """
+    Maximum number of bytes of revert data kept for a transaction, `None` for unlimited.
+    """
+    tx_history_limit: Optional[int] = Field(default=None, gt=0)
+    """
+    Maximum number of transactions kept in `chain.txs`, `None` for unlimited.
+    """
+    verify_library_code: bool = True
+    """
+    Verify that linked library addresses contain code before deploying a contract.
+    """
 
 
 class PrintersConfig(WakeConfigModel):
@@ -432,6 +519,7 @@ class PrinterConfig(WakeConfigModel, extra="allow"):
 class TopLevelConfig(WakeConfigModel):
     subconfigs: List[Annotated[Path, BeforeValidator(lambda p: Path(p).resolve())]] = []
     api_keys: Dict[str, str] = {}
+    addresses: Dict[int, Dict[str, str]] = {}
     compiler: CompilerConfig = Field(default_factory=CompilerConfig)
     detectors: DetectorsConfig = Field(default_factory=DetectorsConfig)
     detector: DetectorConfig = Field(default_factory=DetectorConfig)
@@ -475,6 +475,14 @@ class WakeConfig:
         """
         return self.__config.api_keys
 
+    @property
+    def addresses(self) -> Dict[int, Dict[str, str]]:
+        """
+        Returns:
+            Named addresses per chain ID.
+        """
+        return self.__config.addresses
+
     @property
     def compiler(self) -> CompilerConfig:
         """
@@ -1,15 +1,75 @@
 from rich import print
 
-from wake.development.core import Abi, Account, Address, Eip712Domain, Wei, abi
-from wake.development.internal import UnknownEvent
+from wake.development.blocks import BlockLinkageError
+from wake.development.core import (
+    P256_VERIFY_ADDRESS,
+    Abi,
+    AccessList,
+    Account,
+    Address,
+    AddressBook,
+    Authorization,
+    CallResult,
+    ChainMismatchError,
+    CreateCollision,
+    CurveKey,
+    DataTruncatedError,
+    DeploymentFailed,
+    Eip712Domain,
+    FeeTooLow,
+    ForkInfo,
+    IncompatibleEvmVersion,
+    InitcodeSizeExceeded,
+    InvalidAuthorizationError,
+    KeyCurve,
+    KeystoreAlias,
+    LibraryNotDeployed,
+    NonceGap,
+    NonceMismatch,
+    NonceReuse,
+    ReplayProtectionError,
+    RevertToSnapshotFailedError,
+    Snapshot,
+    SourceLocation,
+    Wei,
+    abi,
+    adr36_prefix,
+    ens_namehash,
+    get_chains,
+    get_keystore_paths,
+    list_keystore_aliases,
+    register_error_abi,
+    register_error_type,
+    shutdown,
+    shutdown_on_exit,
+    signed_message_prefix,
+)
+from wake.development.internal import ExternalEvent, UnknownEvent
+from wake.development.json_rpc import (
+    ConnectionLost,
+    ExecutionTimeout,
+    ForkTimeout,
+    SigningTimeout,
+)
 from wake.development.primitive_types import *
 from wake.development.transactions import (
     Eip1559Transaction,
     Eip2930Transaction,
+    Eip7702Transaction,
+    EventGasInfo,
+    EventSubscription,
     Error,
+    ExternalError,
+    GasForwardingInfo,
+    Halt,
+    HaltReasonEnum,
     LegacyTransaction,
     Panic,
     PanicCodeEnum,
+    TokenFlows,
+    TokenGuard,
+    TokenGuardFinding,
+    TokenGuardFindingKind,
     TransactionAbc,
     TransactionRevertedError,
     UnknownTransactionRevertedError,
@@ -18,12 +78,20 @@ from wake.development.transactions import (
     on_revert,
 )
 from wake.development.utils import (
+    StorageField,
+    compute_storage_layout,
+    eip712_from_json,
+    eip712_to_json,
     get_create2_address_from_code,
     get_create2_address_from_hash,
     get_create_address,
     get_logic_contract,
     keccak256,
+    read_blob,
+    read_storage_field,
     read_storage_variable,
+    write_blob,
+    write_blob_chunks,
 )
 
 from .core import Chain, default_chain
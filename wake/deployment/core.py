@@ -1,10 +1,10 @@
 import os
 import time
+import weakref
 from contextlib import contextmanager, nullcontext
-from typing import Any, Dict, Iterable, Optional, Union, cast
+from typing import Dict, Iterable, Optional, Union
 from urllib.error import HTTPError
 
-import eth_utils
 from Crypto.Hash import keccak
 from rich.console import Group
 from rich.pretty import pprint
@@ -20,10 +20,10 @@ from wake.development.core import (
     Address,
     RequestType,
     RevertToSnapshotFailedError,
+    Snapshot,
     TransactionConfirmationFailedError,
     Wei,
     check_connected,
-    fix_library_abi,
 )
 from wake.development.globals import chain_interfaces_manager, get_config
 from wake.development.json_rpc.communicator import JsonRpcError
@@ -50,6 +50,7 @@ class Chain(wake.development.core.Chain):
         hardfork: Optional[str] = None,
         min_gas_price: Optional[Union[int, str]] = None,
         block_base_fee_per_gas: Optional[Union[int, str]] = None,
+        offline: bool = False,
     ):
         yield from self._connect(
             uri,
@@ -59,6 +60,7 @@ class Chain(wake.development.core.Chain):
             hardfork=hardfork,
             min_gas_price=min_gas_price,
             block_base_fee_per_gas=block_base_fee_per_gas,
+            offline=offline,
         )
 
     def _connect_setup(
@@ -80,35 +82,50 @@ class Chain(wake.development.core.Chain):
         return keccak.new(data=data, digest_bits=256).digest()
 
     @check_connected
-    def snapshot(self) -> str:
-        snapshot_id = self._chain_interface.snapshot()
-
-        self._snapshots[snapshot_id] = {
-            "nonces": self._nonces.copy(),
-            "accounts": self._accounts.copy(),
+    def snapshot(self) -> Snapshot:
+        raw_snapshot_id = self._chain_interface.snapshot()
+        checkpoint = self._journal.checkpoint()
+        snapshot_id = self._new_snapshot(raw_snapshot_id)
+
+        self._snapshots[snapshot_id.id] = {
+            # the `Snapshot` object is not kept alive, its block is only resolved while it is referenced
+            "object": weakref.ref(snapshot_id),
+            "journal": checkpoint,
+            "accounts": self._accounts,
+            "accounts_set": self._accounts_set,
             "default_call_account": self._default_call_account,
             "default_tx_account": self._default_tx_account,
-            "txs": dict(self._txs._transactions),
-            "tx_hashes": list(self._txs._tx_hashes),
-            "blocks": dict(self._blocks._blocks),
+            "tx_count": len(self._txs),
+            "chain_id": self._chain_id,
         }
+        self._run_snapshot_hooks(snapshot_id)
         return snapshot_id
 
     @check_connected
     def revert(self, snapshot_id: str) -> None:
-        reverted = self._chain_interface.revert(snapshot_id)
+        self._check_snapshot(snapshot_id)
+        # transactions collected for the batched callback are still readable before the revert
+        self.flush_tx_callback()
+        reverted = self._chain_interface.revert(str.__str__(snapshot_id))
         if not reverted:
             raise RevertToSnapshotFailedError()
 
+        self._revert_count += 1
+        self._drop_snapshots_after(snapshot_id)
         snapshot = self._snapshots[snapshot_id]
-        self._nonces = snapshot["nonces"]
+        self._journal.rollback(snapshot["journal"])
         self._accounts = snapshot["accounts"]
+        self._accounts_set = snapshot["accounts_set"]
         self._default_call_account = snapshot["default_call_account"]
         self._default_tx_account = snapshot["default_tx_account"]
-        self._txs._transactions = snapshot["txs"]
-        self._txs._tx_hashes = snapshot["tx_hashes"]
-        self._blocks._blocks = snapshot["blocks"]
+        self._txs._truncate(snapshot["tx_count"])
+        self._restore_chain_id(snapshot["chain_id"])
+        self._blocks._invalidate_head()
+        self._resolve_snapshot_block(snapshot)
+        # ENS records may have changed after the snapshot was taken
+        self._ens_cache.clear()
         del self._snapshots[snapshot_id]
+        self._run_revert_hooks(snapshot_id)
 
     @property
     @check_connected
@@ -129,7 +146,7 @@ class Chain(wake.development.core.Chain):
 
     @gas_price.setter
     @check_connected
-    def gas_price(self, value: int) -> None:
+    def gas_price(self, value: Union[int, str]) -> None:
         raise NotImplementedError(
             "Cannot set gas price in deployment"
         )  # TODO do nothing instead?
@@ -141,7 +158,7 @@ class Chain(wake.development.core.Chain):
 
     @max_priority_fee_per_gas.setter
     @check_connected
-    def max_priority_fee_per_gas(self, value: int) -> None:
+    def max_priority_fee_per_gas(self, value: Union[int, str]) -> None:
         raise NotImplementedError(
             "Cannot set max priority fee per gas in deployment"
         )  # TODO do nothing instead?
@@ -153,8 +170,12 @@ class Chain(wake.development.core.Chain):
         arguments: Iterable,
         abi: Optional[Dict],
     ) -> TxParams:
+        view = self._in_view_request()
+        if not view:
+            self._last_gas_estimate = None
+        gas_estimate: Optional[int] = None
         tx_type = params.get("type", self._default_tx_type)
-        if tx_type not in {0, 1, 2}:
+        if tx_type not in {0, 1, 2, 4}:
             raise ValueError("Invalid transaction type")
 
         if tx_type == 0 and (
@@ -171,8 +192,23 @@ class Chain(wake.development.core.Chain):
             raise ValueError(
                 "Cannot specify maxFeePerGas or maxPriorityFeePerGas for type 1 transaction"
             )
-        elif tx_type == 2 and "gasPrice" in params:
-            raise ValueError("Cannot specify gasPrice for type 2 transaction")
+        elif tx_type in {2, 4} and "gasPrice" in params:
+            raise ValueError(f"Cannot specify gasPrice for type {tx_type} transaction")
+
+        if tx_type == 4:
+            if not params.get("authorizationList"):
+                raise ValueError(
+                    "authorizationList must be specified and non-empty for type 4 transaction"
+                )
+            if "to" not in params:
+                raise ValueError("Type 4 transaction cannot create a contract")
+        elif "authorizationList" in params:
+            raise ValueError(
+                f"Cannot specify authorizationList for type {tx_type} transaction"
+            )
+
+        if request_type == "tx":
+            self._check_fee_caps(params)
 
         if "from" in params:
             sender = params["from"]
@@ -201,20 +237,16 @@ class Chain(wake.development.core.Chain):
         if abi is None:
             params["data"] += Abi.encode([], [])
         else:
-            arguments = [self._convert_to_web3_type(arg) for arg in arguments]
-            types = [
-                eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-                for arg in fix_library_abi(abi["inputs"])
-            ]
-            params["data"] += Abi.encode(types, arguments)
-
-        n = self._nonces[Address(sender)]
+            params["data"] += self._encode_arguments(abi, arguments)
+
         tx: TxParams = {
-            "nonce": n,
             "from": sender,
             "value": params["value"] if "value" in params else 0,
             "data": params["data"],
         }
+        if not view:
+            # calls do not need a nonce, views must not insert it into the journaled nonces
+            tx["nonce"] = self._nonces[Address(sender)]
         if tx_type != 0:
             tx["type"] = tx_type
 
@@ -230,8 +262,10 @@ class Chain(wake.development.core.Chain):
             tx["gasPrice"] = (
                 params["gasPrice"] if "gasPrice" in params else self.gas_price
             )
-        elif tx_type == 2:
+        elif tx_type in {2, 4}:
             tx["chainId"] = self._chain_id
+            if tx_type == 4:
+                tx["authorizationList"] = params["authorizationList"]
             tx["maxPriorityFeePerGas"] = (
                 params["maxPriorityFeePerGas"]
                 if "maxPriorityFeePerGas" in params
@@ -259,7 +293,10 @@ class Chain(wake.development.core.Chain):
                 tx_copy.pop("gasPrice", None)
                 tx_copy.pop("maxPriorityFeePerGas", None)
                 tx_copy.pop("maxFeePerGas", None)
-                tx["gas"] = int(self._chain_interface.estimate_gas(tx_copy) * 1.1)
+                gas_estimate = self._chain_interface.estimate_gas(tx_copy)
+                tx["gas"] = int(
+                    gas_estimate * get_config().general.gas_limit_multiplier
+                )
             except JsonRpcError as e:
                 raise self._process_call_revert(e) from None
         elif isinstance(params["gas"], int):
@@ -268,13 +305,16 @@ class Chain(wake.development.core.Chain):
             raise ValueError(f"Invalid gas value: {params['gas']}")
 
         if (
-            tx_type in {1, 2}
+            tx_type in {1, 2, 4}
             and ("accessList" not in params or params["accessList"] == "auto")
             and request_type != "access_list"
         ):
             try:
                 response = self._chain_interface.create_access_list(tx)
-                gas_used = int(int(response["gasUsed"], 16) * 1.1)
+                gas_used = int(
+                    int(response["gasUsed"], 16)
+                    * get_config().general.gas_limit_multiplier
+                )
 
                 if params.get("accessList", None) == "auto" or (
                     "accessList" not in params and gas_used <= tx["gas"]
@@ -282,6 +322,7 @@ class Chain(wake.development.core.Chain):
                     tx["accessList"] = response["accessList"]
 
                     if "gas" not in params or params["gas"] == "auto":
+                        gas_estimate = int(response["gasUsed"], 16)
                         tx["gas"] = gas_used
             except (JsonRpcError, HTTPError) as e:
                 try:
@@ -301,6 +342,8 @@ class Chain(wake.development.core.Chain):
                     else:
                         raise
 
+        if not view:
+            self._last_gas_estimate = gas_estimate
         return tx
 
     def _wait_for_transaction(
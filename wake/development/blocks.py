@@ -1,6 +1,7 @@
 from __future__ import annotations
 
-from typing import TYPE_CHECKING, Any, Dict, Iterator, List, Optional, Union
+from collections import OrderedDict
+from typing import TYPE_CHECKING, Any, Dict, Iterator, List, Optional, Set, Union
 
 from typing_extensions import Literal
 
@@ -9,13 +10,162 @@ if TYPE_CHECKING:
     from .transactions import TransactionAbc
 
 
+class BlockLinkageError(Exception):
+    block_number: int
+    parent_hash: str
+    expected_parent_hash: str
+
+    def __init__(self, block_number: int, parent_hash: str, expected_parent_hash: str):
+        self.block_number = block_number
+        self.parent_hash = parent_hash
+        self.expected_parent_hash = expected_parent_hash
+        super().__init__(
+            f"Parent hash {parent_hash} of block {block_number} does not match "
+            f"hash {expected_parent_hash} of block {block_number - 1}"
+        )
+
+
 class ChainBlocks:
     _chain: Chain
-    _blocks: Dict[int, Block]
+    _blocks: OrderedDict[int, Block]
+    _history_limit: Optional[int]
+    _pinned: Set[int]
+    # the chain head may have moved backwards (e.g. after a revert) and cached blocks must be validated before use
+    _head_stale: bool
 
     def __init__(self, chain: Chain):
         self._chain = chain
-        self._blocks = {}
+        self._blocks = OrderedDict()
+        self._history_limit = None
+        self._pinned = set()
+        self._head_stale = False
+
+    @property
+    def history_limit(self) -> Optional[int]:
+        """
+        Maximum number of blocks cached in memory, including pinned blocks. The least recently cached blocks
+        (except pinned ones) are evicted first. `None` means no limit.
+        """
+        return self._history_limit
+
+    @history_limit.setter
+    def history_limit(self, value: Optional[int]) -> None:
+        if value is not None and value <= 0:
+            raise ValueError("history_limit must be positive or None")
+        self._history_limit = value
+        self._prune()
+
+    def pin(self, block: Union[Block, int]) -> None:
+        """
+        Keep the block in the cache regardless of `history_limit`.
+        """
+        if isinstance(block, int):
+            block = self[block]
+        elif self._head_stale:
+            self._validate_head()
+        # pinned blocks are always cached
+        self._blocks.setdefault(block.number, block)
+        self._pinned.add(block.number)
+
+    def unpin(self, block: Union[Block, int]) -> None:
+        if isinstance(block, Block):
+            block = block.number
+        if block in self._pinned:
+            self._pinned.discard(block)
+            # the block becomes the first one to be evicted
+            self._blocks.move_to_end(block, last=False)
+            self._prune()
+
+    def verify(self, start: Optional[int] = None, end: Optional[int] = None) -> None:
+        """
+        Check that the parent hash of every block matches the hash of the preceding block.
+        If neither `start` nor `end` is given, only consecutive cached blocks and the latest block are checked
+        without fetching other blocks. Otherwise, all blocks in the inclusive range are checked,
+        `start` defaulting to the fork block on forked chains (the earliest block otherwise) and `end` to the latest block.
+
+        Raises:
+            BlockLinkageError: If a parent hash does not match.
+        """
+        cached_only = start is None and end is None
+        if cached_only:
+            self._validate_head()
+            numbers = sorted(self._blocks.keys())
+        else:
+            if start is None:
+                fork_info = self._chain.fork_info
+                if fork_info is not None and fork_info.block_number is not None:
+                    start = fork_info.block_number
+                else:
+                    start = self["earliest"].number
+            if end is None:
+                end = self["latest"].number
+            numbers = range(start, end + 1)
+
+        prev: Optional[Block] = None
+        for number in numbers:
+            block = self._blocks[number] if cached_only else self[number]
+            if prev is not None and prev.number == number - 1:
+                if block.parent_hash != prev.hash:
+                    raise BlockLinkageError(number, block.parent_hash, prev.hash)
+            prev = block
+
+    def _link(self, block: Block) -> None:
+        # replace cached blocks from an abandoned branch, e.g. after a reorg
+        child = self._blocks.get(block.number + 1)
+        if child is not None and child.parent_hash != block.hash:
+            for number in [n for n in self._blocks.keys() if n > block.number]:
+                del self._blocks[number]
+                self._pinned.discard(number)
+
+        while True:
+            parent = self._blocks.get(block.number - 1)
+            if parent is None or parent.hash == block.parent_hash:
+                break
+            data = self._chain.chain_interface.get_block(block.number - 1)
+            if data is None:
+                del self._blocks[block.number - 1]
+                self._pinned.discard(block.number - 1)
+                break
+            block = Block(self._chain, data)
+            self._blocks[block.number] = block
+
+    def _invalidate_head(self) -> None:
+        # called after the chain head may have moved backwards, e.g. after a revert,
+        # the head is fetched and validated on the next access to the cache
+        self._head_stale = True
+
+    def _validate_head(self) -> Optional[Block]:
+        self._head_stale = False
+        data = self._chain.chain_interface.get_block("latest")
+        if data is None:
+            return None
+        head = Block(self._chain, data)
+        for number in [n for n in self._blocks.keys() if n > head.number]:
+            del self._blocks[number]
+            self._pinned.discard(number)
+        cached = self._blocks.get(head.number)
+        if cached is None or cached.hash != head.hash:
+            self._blocks[head.number] = head
+        self._link(head)
+        return self._blocks.get(head.number, head)
+
+    def _clear(self) -> None:
+        # drop all cached blocks, e.g. when the chain state was replaced
+        self._blocks = OrderedDict()
+        self._pinned = set()
+        self._head_stale = False
+
+    def _prune(self) -> None:
+        if self._history_limit is None:
+            return
+
+        # the cache is insertion-ordered, evict the oldest blocks first and move pinned ones to the end
+        while len(self._blocks) > max(self._history_limit, len(self._pinned)):
+            number = next(iter(self._blocks))
+            if number in self._pinned:
+                self._blocks.move_to_end(number)
+            else:
+                del self._blocks[number]
 
     def __getitem__(
         self,
@@ -28,6 +178,8 @@ class ChainBlocks:
             Literal["finalized"],
         ],
     ) -> Block:
+        if self._head_stale:
+            self._validate_head()
         if isinstance(key, int) and key < 0:
             key = self._chain.chain_interface.get_block_number() + key + 1
         if key not in self._blocks:
@@ -44,6 +196,8 @@ class ChainBlocks:
 
                 if block.number <= self._chain.chain_interface.get_block_number():
                     self._blocks[block.number] = block
+                    self._link(block)
+                    self._prune()
         else:
             block = self._blocks[key]
         return block
@@ -76,6 +230,15 @@ class Block:
     def parent_hash(self) -> str:
         return self._block_data["parentHash"]
 
+    @property
+    def parent(self) -> Optional[Block]:
+        """
+        Block preceding this block or `None` for the genesis block.
+        """
+        if self.number == 0:
+            return None
+        return self._chain.blocks[self.number - 1]
+
     @property
     def sha3_uncles(self) -> str:
         return self._block_data["sha3Uncles"]
@@ -6,19 +6,18 @@ from collections import ChainMap
 from typing import (
     TYPE_CHECKING,
     Any,
+    Callable,
     Dict,
     Iterable,
     List,
     Optional,
     Tuple,
     Union,
-    cast,
 )
 
 import eth_abi
 import eth_abi.abi
 import eth_abi.exceptions
-import eth_utils
 from rich.console import Console
 from rich.highlighter import ReprHighlighter
 from rich.text import Text
@@ -30,12 +29,13 @@ from ..utils.formatters import format_wei
 from . import hardhat_console
 from .chain_interfaces import TxParams
 from .core import (
+    _HARDFORKS,
     Account,
     Address,
     Chain,
     Contract,
     Wei,
-    fix_library_abi,
+    _get_abi_types,
     get_contracts_by_fqn,
     get_fqn_from_address,
     get_fqn_from_creation_code,
@@ -49,6 +49,10 @@ if TYPE_CHECKING:
     from .transactions import TransactionAbc
 
 
+def is_precompiled(addr: Address) -> bool:
+    return Address(0) < addr <= Address(10)
+
+
 def get_precompiled_info(
     addr: Address, data: bytes
 ) -> Tuple[str, Optional[Tuple[Any, ...]], Optional[Tuple[Optional[str], ...]]]:
@@ -146,14 +150,132 @@ def get_precompiled_info(
             (rounds, h, m, t, f),
             ("rounds", "h", "m", "t", "f"),
         )
+    elif addr == Address(10):
+        if len(data) != 192:
+            return "pointEvaluation", None, None
+        return (
+            "pointEvaluation",
+            (data[:32], data[32:64], data[64:96], data[96:144], data[144:192]),
+            ("versionedHash", "z", "y", "commitment", "proof"),
+        )
     else:
         raise ValueError(f"Unknown precompiled contract address: {addr}")
 
 
+def _modexp_gas_cost(data: bytes, hardfork: str) -> int:
+    data = data.ljust(96, b"\x00")
+    base_length = int.from_bytes(data[:32], "big")
+    exp_length = int.from_bytes(data[32:64], "big")
+    mod_length = int.from_bytes(data[64:96], "big")
+    max_length = max(base_length, mod_length)
+
+    exp_start = 96 + base_length
+    exp_head = int.from_bytes(
+        data[exp_start : exp_start + min(exp_length, 32)].ljust(
+            min(exp_length, 32), b"\x00"
+        ),
+        "big",
+    )
+    head_bits = max(exp_head.bit_length() - 1, 0)
+
+    if _is_active(hardfork, "OSAKA"):
+        # EIP-7883
+        iteration_count = max(
+            head_bits if exp_length <= 32 else 16 * (exp_length - 32) + head_bits, 1
+        )
+        words = (max_length + 7) // 8
+        complexity = 16 if max_length <= 32 else 2 * words**2
+        return max(500, complexity * iteration_count)
+
+    iteration_count = max(
+        head_bits if exp_length <= 32 else 8 * (exp_length - 32) + head_bits, 1
+    )
+    if _is_active(hardfork, "BERLIN"):
+        # EIP-2565
+        words = (max_length + 7) // 8
+        return max(200, words**2 * iteration_count // 3)
+
+    # EIP-198
+    if max_length <= 64:
+        complexity = max_length**2
+    elif max_length <= 1024:
+        complexity = max_length**2 // 4 + 96 * max_length - 3072
+    else:
+        complexity = max_length**2 // 16 + 480 * max_length - 199680
+    return complexity * iteration_count // 20
+
+
+def _is_active(hardfork: str, introduced: str) -> bool:
+    # hardforks unknown to Wake are newer than all known hardforks
+    if hardfork not in _HARDFORKS:
+        return True
+    return _HARDFORKS.index(hardfork) >= _HARDFORKS.index(introduced)
+
+
+def get_precompiled_gas_cost(
+    addr: Address, data: bytes, hardfork: Optional[str] = None
+) -> Optional[int]:
+    """
+    Return gas charged by a precompiled contract for the given input data according to the rules of `hardfork`
+    (revm SpecId name, e.g. `CANCUN`; the latest hardfork if `None`).
+    Returns `None` if the address is not a precompile in the hardfork or its cost is not known to Wake
+    (BLS12-381 multi-scalar multiplications).
+    """
+    if hardfork is None:
+        hardfork = _HARDFORKS[-1]
+
+    words = (len(data) + 31) // 32
+    if addr == Address(1):
+        return 3000
+    elif addr == Address(2):
+        return 60 + 12 * words
+    elif addr == Address(3):
+        return 600 + 120 * words
+    elif addr == Address(4):
+        return 15 + 3 * words
+    elif not _is_active(hardfork, "BYZANTIUM"):
+        return None
+    elif addr == Address(5):
+        return _modexp_gas_cost(data, hardfork)
+    elif addr == Address(6):
+        # EIP-1108 repriced the alt_bn128 precompiles in Istanbul
+        return 150 if _is_active(hardfork, "ISTANBUL") else 500
+    elif addr == Address(7):
+        return 6000 if _is_active(hardfork, "ISTANBUL") else 40000
+    elif addr == Address(8):
+        if _is_active(hardfork, "ISTANBUL"):
+            return 45000 + 34000 * (len(data) // 192)
+        return 100000 + 80000 * (len(data) // 192)
+    elif addr == Address(9):
+        if not _is_active(hardfork, "ISTANBUL"):
+            return None
+        return int.from_bytes(data[:4], "big") if len(data) >= 4 else 0
+    elif addr == Address(10):
+        return 50000 if _is_active(hardfork, "CANCUN") else None
+    elif Address(0x0B) <= addr <= Address(0x11):
+        if not _is_active(hardfork, "PRAGUE"):
+            return None
+        # EIP-2537, MSM costs depend on a discount table and are not computed
+        return {
+            0x0B: 375,  # G1ADD
+            0x0D: 600,  # G2ADD
+            0x0F: 37700 + 32600 * (len(data) // 384),  # PAIRING_CHECK
+            0x10: 5500,  # MAP_FP_TO_G1
+            0x11: 23800,  # MAP_FP2_TO_G2
+        }.get(int.from_bytes(bytes(addr), "big"))
+    elif addr == Address(0x100):
+        # EIP-7951 P256VERIFY
+        return 6900 if _is_active(hardfork, "OSAKA") else None
+    else:
+        return None
+
+
 def _decode_precompiled(
     addr: Optional[Address], data: bytes
 ) -> Tuple[List[Any], List[Optional[str]]]:
-    if addr == Address(1):
+    if addr is None:
+        raise ValueError("Precompiled contract address not set")
+    elif addr == Address(1):
         assert len(data) >= 20
         return [Address(data[-20:].hex())], [None]
     elif Address(2) <= addr <= Address(4):
@@ -169,7 +291,16 @@ def _decode_precompiled(
     elif addr == Address(8):
         return [int.from_bytes(data, "big") > 0], ["success"]
     elif addr == Address(9):
-        return [data], [None]
+        assert len(data) == 64
+        return [
+            tuple(int.from_bytes(data[i : i + 8], "little") for i in range(0, 64, 8))
+        ], ["h"]
+    elif addr == Address(10):
+        assert len(data) == 64
+        return [
+            int.from_bytes(data[:32], "big"),
+            int.from_bytes(data[32:], "big"),
+        ], ["fieldElements", "blsModulus"]
     else:
         raise ValueError(f"Unknown precompiled contract address: {addr}")
 
@@ -208,10 +339,7 @@ def _decode_args(
         else:
             return arg
 
-    input_types = [
-        eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-        for arg in fix_library_abi(abi)
-    ]
+    _, input_types = _get_abi_types(abi)
     args = list(
         normalize(arg, type)
         for arg, type in zip(eth_abi.abi.decode(input_types, data), abi)
@@ -221,6 +349,72 @@ def _decode_args(
     return args, arg_names
 
 
+class _LazyDecoded:
+    """
+    ABI-encoded values decoded on first access, so that rendering or inspecting only a part of a deep call trace
+    does not pay for decoding all of it.
+    """
+
+    _decode: Optional[
+        Callable[[], Tuple[Optional[List], Optional[List[Optional[str]]]]]
+    ]
+    _result: Tuple[Optional[List], Optional[List[Optional[str]]]]
+
+    def __init__(
+        self,
+        decode: Callable[[], Tuple[Optional[List], Optional[List[Optional[str]]]]],
+    ):
+        self._decode = decode
+        self._result = (None, None)
+
+    def get(self) -> Tuple[Optional[List], Optional[List[Optional[str]]]]:
+        if self._decode is not None:
+            self._result = self._decode()
+            self._decode = None
+        return self._result
+
+
+def _lazy_decode_args(abi, data: bytes, chain: Chain) -> _LazyDecoded:
+    def decode():
+        try:
+            return _decode_args(abi, data, chain)
+        except Exception:
+            return None, None
+
+    return _LazyDecoded(decode)
+
+
+def _lazy_decode_return_value(
+    output_abi: Optional[List[Dict[str, Any]]],
+    address: Optional[Address],
+    data: bytes,
+    chain: Chain,
+) -> _LazyDecoded:
+    def decode():
+        try:
+            if output_abi is not None:
+                return _decode_args(output_abi, data, chain)
+            else:
+                return _decode_precompiled(address, data)
+        except Exception:
+            return [data], [None]
+
+    return _LazyDecoded(decode)
+
+
+def _lazy_decode_stop_return_value(
+    output_abi: List[Dict[str, Any]], chain: Chain
+) -> _LazyDecoded:
+    def decode():
+        try:
+            # just use a large enough zeroed buffer instead of evaluating the exact size
+            return _decode_args(output_abi, b"\x00" * 100_000, chain)
+        except Exception:
+            return None, None
+
+    return _LazyDecoded(decode)
+
+
 class CallTraceKind(StrEnum):
     CALL = "CALL"
     DELEGATECALL = "DELEGATECALL"
@@ -295,6 +489,7 @@ class CallTrace:
     _function_is_special: bool
     _arguments: Optional[List]
     _argument_names: Optional[List[Optional[str]]]
+    _lazy_arguments: Optional[_LazyDecoded]
     _status: bool
     _gas: int
     _value: Wei
@@ -311,8 +506,11 @@ class CallTrace:
     _revert_data: Optional[bytes]
     _return_value: Optional[List]
     _return_names: Optional[List[Optional[str]]]
+    _lazy_return_value: Optional[_LazyDecoded]
     _abi: Dict[bytes, Any]  # used for error decoding
     _output_abi: Optional[List[Dict[str, Any]]]  # used for return value decoding
+    # (number of subtraces preceding the event, topic0) of events not ignored by `Chain.ignore_event`
+    _events: List[Tuple[int, Optional[bytes]]]
 
     def __init__(
         self,
@@ -321,7 +519,7 @@ class CallTrace:
         function_name: Optional[str],
         selector: Optional[bytes],
         address: Optional[Address],
-        arguments: Optional[Iterable],
+        arguments: Optional[Union[Iterable, _LazyDecoded]],
         argument_names: Optional[Iterable[Optional[str]]],
         gas: int,
         value: int,
@@ -338,10 +536,16 @@ class CallTrace:
         self._function_name = function_name
         self._selector = selector
         self._address = address
-        self._arguments = list(arguments) if arguments is not None else None
-        self._argument_names = (
-            list(argument_names) if argument_names is not None else None
-        )
+        if isinstance(arguments, _LazyDecoded):
+            self._lazy_arguments = arguments
+            self._arguments = None
+            self._argument_names = None
+        else:
+            self._lazy_arguments = None
+            self._arguments = list(arguments) if arguments is not None else None
+            self._argument_names = (
+                list(argument_names) if argument_names is not None else None
+            )
         self._gas = gas
         self._value = Wei(value)
         self._kind = kind
@@ -351,6 +555,7 @@ class CallTrace:
         self._function_is_special = function_is_special
         self._status = True
         self._subtraces = []
+        self._events = []
         self._parent = None
         self._error_name = None
         self._error_arguments = None
@@ -358,6 +563,7 @@ class CallTrace:
         self._revert_data = None
         self._return_value = None
         self._return_names = None
+        self._lazy_return_value = None
         self._output_abi = output_abi
         self._abi = abi
         self._abi[bytes.fromhex("08c379a0")] = {
@@ -385,9 +591,38 @@ class CallTrace:
         return tree
 
     def _into_tree(self, tree: Tree):
-        for subtrace in self._subtraces:
+        events_iter = iter(self._events)
+        event = next(events_iter, None)
+        for i, subtrace in enumerate(self._subtraces):
+            while event is not None and event[0] <= i:
+                tree.add(self._get_event_label(event[1]))
+                event = next(events_iter, None)
             t = tree.add(subtrace._get_label())
             subtrace._into_tree(t)
+        while event is not None:
+            tree.add(self._get_event_label(event[1]))
+            event = next(events_iter, None)
+
+    def _add_event(self, selector: Optional[bytes]) -> None:
+        address = self.storage_address
+        if address is None or not self._chain._is_event_ignored(selector, address):
+            self._events.append((len(self._subtraces), selector))
+
+    @staticmethod
+    def _get_event_label(selector: Optional[bytes]) -> Text:
+        # the index is replaced when pytypes are imported
+        from .core import events
+
+        if selector is not None and selector in events:
+            _, attrs = next(iter(events[selector].values()))
+            name = attrs[-1]
+        elif selector is not None:
+            name = "0x" + selector.hex()
+        else:
+            name = "<anonymous>"
+        return Text.from_markup(
+            f"[yellow]emit[/yellow] [bright_magenta]{name}[/bright_magenta]"
+        )
 
     def _get_label(self) -> Text:
         options = get_config().general.call_trace_options
@@ -482,7 +717,17 @@ class CallTrace:
             )
 
         if "call_type" in options:
-            if self.kind != CallTraceKind.CALL:
+            if self.kind in {CallTraceKind.DELEGATECALL, CallTraceKind.CALLCODE}:
+                storage_address = self.storage_address
+                label = None
+                if storage_address is not None:
+                    label = Account(storage_address, self.chain).label
+                ret.append_text(
+                    Text.from_markup(
+                        f" [yellow]\[{self.kind} in {label or storage_address}][/yellow]"  # pyright: ignore reportInvalidStringEscapeSequence
+                    )
+                )
+            elif self.kind != CallTraceKind.CALL:
                 ret.append_text(
                     Text.from_markup(
                         f" [yellow]\[{self.kind}][/yellow]"  # pyright: ignore reportInvalidStringEscapeSequence
@@ -513,12 +758,13 @@ class CallTrace:
                 )
 
         if "return_value" in options:
-            if self._return_value is not None and len(self._return_value) > 0:
+            return_value = self.return_value
+            if return_value is not None and len(return_value) > 0:
                 assert self._return_names is not None
 
                 ret.append("\n➞ ")
                 for i, (arg, arg_name) in enumerate(
-                    zip(self._return_value, self._return_names)
+                    zip(return_value, self._return_names)
                 ):
                     if get_verbosity() > 0:
                         r = repr(arg)
@@ -531,7 +777,7 @@ class CallTrace:
                         t = Text(r)
                     ReprHighlighter().highlight(t)
                     ret.append_text(t)
-                    if i < len(self._return_value) - 1:
+                    if i < len(return_value) - 1:
                         ret.append(", ")
 
         if "error" in options:
@@ -556,8 +802,35 @@ class CallTrace:
                         ret.append(", ")
                 ret.append(")")
 
+                if self._error_name == "Panic":
+                    origin = self._panic_origin()
+                    if origin is not None and origin is not self:
+                        ret.append_text(
+                            Text.from_markup(f" [red]in {origin._location}[/red]")
+                        )
+
         return ret
 
+    def _panic_origin(self) -> Optional[CallTrace]:
+        if self._status or self._error_name != "Panic":
+            return None
+
+        # the deepest failed call with the same error is the origin of the panic
+        trace = self
+        found = True
+        while found:
+            found = False
+            for subtrace in reversed(trace._subtraces):
+                if not subtrace._status and subtrace._error_name == "Panic":
+                    trace = subtrace
+                    found = True
+                    break
+        return trace
+
+    @property
+    def _location(self) -> str:
+        return f"{self._contract_name or 'Unknown'}.{self._function_name or '???'}"
+
     @property
     def subtraces(self) -> Tuple[CallTrace, ...]:
         return tuple(self._subtraces)
@@ -582,18 +855,55 @@ class CallTrace:
     def function_is_special(self) -> bool:
         return self._function_is_special
 
+    @property
+    def is_precompiled(self) -> bool:
+        return self._contract_name == "<precompiled>"
+
     @property
     def address(self) -> Optional[Address]:
         return self._address
 
+    @property
+    def code_address(self) -> Optional[Address]:
+        """
+        Address of the account whose code is executed. Same as `address`.
+        """
+        return self._address
+
+    @property
+    def storage_address(self) -> Optional[Address]:
+        """
+        Address of the account whose storage and balance are used by the call. Differs from `code_address`
+        for `DELEGATECALL` and `CALLCODE` calls, where the storage of the calling account is used.
+        """
+        current_trace = self
+        while (
+            current_trace.kind in {CallTraceKind.DELEGATECALL, CallTraceKind.CALLCODE}
+            and current_trace.parent is not None
+        ):
+            current_trace = current_trace.parent
+        return current_trace.address
+
+    def _resolve_arguments(self) -> None:
+        if self._lazy_arguments is not None:
+            self._arguments, self._argument_names = self._lazy_arguments.get()
+            self._lazy_arguments = None
+
+    def _resolve_return_value(self) -> None:
+        if self._lazy_return_value is not None:
+            self._return_value, self._return_names = self._lazy_return_value.get()
+            self._lazy_return_value = None
+
     @property
     def arguments(self) -> Optional[Tuple]:
+        self._resolve_arguments()
         if self._arguments is None:
             return None
         return tuple(self._arguments)
 
     @property
     def argument_names(self) -> Optional[Tuple[Optional[str], ...]]:
+        self._resolve_arguments()
         if self._argument_names is None:
             return None
         return tuple(self._argument_names)
@@ -626,8 +936,10 @@ class CallTrace:
         if current_trace is None or current_trace.parent is None:
             return self._origin
 
-        if current_trace.parent.address is not None:
-            return Account(current_trace.parent.address, current_trace.parent.chain)
+        # calls from a delegatecall frame are sent by the account owning the storage
+        sender = current_trace.parent.storage_address
+        if sender is not None:
+            return Account(sender, current_trace.parent.chain)
         return None
 
     @property
@@ -650,10 +962,12 @@ class CallTrace:
 
     @property
     def return_value(self) -> Optional[List]:
+        self._resolve_return_value()
         return self._return_value
 
     @property
     def return_names(self) -> Optional[Tuple[Optional[str], ...]]:
+        self._resolve_return_value()
         if self._return_names is None:
             return None
         return tuple(self._return_names)
@@ -668,6 +982,7 @@ class CallTrace:
     ):
         from .transactions import PanicCodeEnum
 
+        options = get_config().general.call_trace_options
         fqn_overrides: ChainMap[Address, Optional[str]] = ChainMap()
 
         # process fqn_overrides for all txs before this one in the same block
@@ -712,11 +1027,11 @@ class CallTrace:
         if (
             origin_fqn is None or origin_fqn not in contracts_by_fqn
         ) and tx.to is not None:
-            if Address(0) < tx.to.address <= Address(9):
+            if is_precompiled(tx.to.address):
                 precompiled_info = get_precompiled_info(
                     tx.to.address, b"" if "data" not in tx_params else tx_params["data"]
                 )
-            elif tx.chain._fork is not None:
+            elif tx.chain._fork is not None and not tx.chain.offline:
                 explorer_info = get_contract_info_from_explorer(
                     tx.to.address,
                     tx.chain._forked_chain_id
@@ -790,15 +1105,12 @@ class CallTrace:
                         tx_params["data"]
                     )
                     fn_abi = contract_abi["constructor"]
-                    try:
-                        args, arg_names = _decode_args(
-                            fn_abi["inputs"],
-                            tx_params["data"][constructor_offset:],
-                            tx.chain,
-                        )
-                    except Exception:
-                        args = None
-                        arg_names = None
+                    args = _lazy_decode_args(
+                        fn_abi["inputs"],
+                        tx_params["data"][constructor_offset:],
+                        tx.chain,
+                    )
+                    arg_names = None
                 root_trace = CallTrace(
                     obj,
                     contract_name,
@@ -888,13 +1200,10 @@ class CallTrace:
                     )
             else:
                 fn_abi = contract_abi[tx_params["data"][:4]]
-                try:
-                    args, arg_names = _decode_args(
-                        fn_abi["inputs"], tx_params["data"][4:], tx.chain
-                    )
-                except Exception:
-                    args = None
-                    arg_names = None
+                args = _lazy_decode_args(
+                    fn_abi["inputs"], tx_params["data"][4:], tx.chain
+                )
+                arg_names = None
                 root_trace = CallTrace(
                     obj,
                     contract_name,
@@ -938,22 +1247,11 @@ class CallTrace:
 
                     data = bytes(read_from_memory(ret_offset, ret_size, log["memory"]))
 
-                    try:
-                        if current_trace._output_abi is not None:
-                            return_value, return_names = _decode_args(
-                                current_trace._output_abi, data, tx.chain
-                            )
-                        else:
-                            return_value, return_names = _decode_precompiled(
-                                current_trace.address, data
-                            )
-                    except Exception:
-                        return_value = [data]
-                        return_names = [None]
-
-                    current_trace._return_value = return_value
-                    current_trace._return_names = (
-                        return_names  # pyright: ignore reportAttributeAccessIssue
+                    current_trace._lazy_return_value = _lazy_decode_return_value(
+                        current_trace._output_abi,
+                        current_trace.address,
+                        data,
+                        tx.chain,
                     )
                     current_trace._status = status
 
@@ -1014,9 +1312,9 @@ class CallTrace:
                 if fqn is None and addr != Address(
                     "0x000000000000000000636F6e736F6c652e6c6f67"
                 ):
-                    if Address(0) < addr <= Address(9):
+                    if is_precompiled(addr):
                         precompiled_info = get_precompiled_info(addr, data)
-                    elif tx.chain._fork is not None:
+                    elif tx.chain._fork is not None and not tx.chain.offline:
                         explorer_info = get_contract_info_from_explorer(
                             addr,
                             tx.chain._forked_chain_id
@@ -1028,13 +1326,8 @@ class CallTrace:
                     if addr == Address("0x000000000000000000636F6e736F6c652e6c6f67"):
                         if data[:4] in hardhat_console.abis:
                             fn_abi = hardhat_console.abis[data[:4]]
-                            try:
-                                args, arg_names = _decode_args(
-                                    fn_abi, data[4:], tx.chain
-                                )
-                            except Exception:
-                                args = None
-                                arg_names = None
+                            args = _lazy_decode_args(fn_abi, data[4:], tx.chain)
+                            arg_names = None
                         else:
                             args = [data]
                             arg_names = [None]
@@ -1111,13 +1404,10 @@ class CallTrace:
                         selector = data[:4]
                         if selector in contract_abi:
                             fn_abi = contract_abi[selector]
-                            try:
-                                args, arg_names = _decode_args(
-                                    fn_abi["inputs"], data[4:], tx.chain
-                                )
-                            except Exception:
-                                args = None
-                                arg_names = None
+                            args = _lazy_decode_args(
+                                fn_abi["inputs"], data[4:], tx.chain
+                            )
+                            arg_names = None
                             output_abi = (
                                 fn_abi["outputs"] if "outputs" in fn_abi else []
                             )
@@ -1187,6 +1477,17 @@ class CallTrace:
                 contracts.append(fqn)
                 values.append(value)
                 fqn_overrides.maps.insert(0, {})
+            elif (
+                log["op"] in {"LOG0", "LOG1", "LOG2", "LOG3", "LOG4"}
+                and "events" in options
+            ):
+                assert current_trace is not None
+                selector = (
+                    int(log["stack"][-3], 16).to_bytes(32, "big")
+                    if log["op"] != "LOG0"
+                    else None
+                )
+                current_trace._add_event(selector)
             elif log["op"] in {"INVALID", "RETURN", "REVERT", "STOP", "SELFDESTRUCT"}:
                 if log["op"] in {"INVALID", "REVERT"}:
                     status = False
@@ -1210,22 +1511,11 @@ class CallTrace:
                         read_from_memory(data_offset, data_size, log["memory"])
                     )
 
-                    try:
-                        if current_trace._output_abi is not None:
-                            return_value, return_names = _decode_args(
-                                current_trace._output_abi, data, tx.chain
-                            )
-                        else:
-                            return_value, return_names = _decode_precompiled(
-                                current_trace.address, data
-                            )
-                    except Exception:
-                        return_value = [data]
-                        return_names = [None]
-
-                    current_trace._return_value = return_value
-                    current_trace._return_names = (
-                        return_names  # pyright: ignore reportAttributeAccessIssue
+                    current_trace._lazy_return_value = _lazy_decode_return_value(
+                        current_trace._output_abi,
+                        current_trace.address,
+                        data,
+                        tx.chain,
                     )
                 elif log["op"] == "REVERT":
                     data_offset = int(log["stack"][-1], 16)
@@ -1275,19 +1565,13 @@ class CallTrace:
                             current_trace._error_names = [None]
                 else:
                     if current_trace._output_abi is not None:
-                        try:
-                            # just use a large enough zeroed buffer instead of evaluating the exact size
-                            return_value, return_names = _decode_args(
-                                current_trace._output_abi, b"\x00" * 100_000, tx.chain
-                            )
-                        except Exception:
-                            return_value = None
-                            return_names = None
+                        current_trace._lazy_return_value = _lazy_decode_stop_return_value(
+                            current_trace._output_abi, tx.chain
+                        )
                     else:
                         # should not really happen
-                        return_value, return_names = [], []
-                    current_trace._return_value = return_value
-                    current_trace._return_names = return_names
+                        current_trace._return_value = []
+                        current_trace._return_names = []
 
                 assert current_trace is not None
                 if (
@@ -1336,15 +1620,12 @@ class CallTrace:
                         arg_names = []
                     else:
                         fn_abi = contract_abi["constructor"]
-                        try:
-                            args, arg_names = _decode_args(
-                                fn_abi["inputs"],
-                                creation_code[constructor_offset:],
-                                tx.chain,
-                            )
-                        except Exception:
-                            args = None
-                            arg_names = None
+                        args = _lazy_decode_args(
+                            fn_abi["inputs"],
+                            bytes(creation_code[constructor_offset:]),
+                            tx.chain,
+                        )
+                        arg_names = None
                 except ValueError:
                     fqn = None
                     obj = None
@@ -1,11 +1,14 @@
 from __future__ import annotations
 
+import gzip
+import json
 import subprocess
 import time
 from abc import ABC, abstractmethod
-from typing import Any, Dict, List, Optional, Union
+from typing import Any, Dict, List, Optional, Tuple, Union
 from urllib.error import URLError
 
+from Crypto.Hash import keccak
 from typing_extensions import Literal, TypedDict
 
 from wake.cli.console import console
@@ -14,6 +17,22 @@ from wake.utils.networking import get_free_port
 
 from .json_rpc.communicator import JsonRpcCommunicator
 
+# base genesis timestamp of Anvil chains launched with deterministic timestamps
+DETERMINISTIC_GENESIS_TIMESTAMP = 1_700_000_000
+
+
+def get_deterministic_genesis_timestamp(seed: Optional[bytes]) -> int:
+    """
+    Return the genesis timestamp of chains launched with deterministic timestamps, derived from the random seed
+    of the test run (within a year after `DETERMINISTIC_GENESIS_TIMESTAMP`).
+    """
+    if seed is None:
+        return DETERMINISTIC_GENESIS_TIMESTAMP
+    h = keccak.new(data=b"genesis-timestamp" + seed, digest_bits=256).digest()
+    return DETERMINISTIC_GENESIS_TIMESTAMP + int.from_bytes(h[:8], "big") % (
+        365 * 24 * 60 * 60
+    )
+
 TxParams = TypedDict(
     "TxParams",
     {
@@ -29,6 +48,7 @@ TxParams = TypedDict(
         "maxFeePerGas": int,
         "accessList": Union[List, Literal["auto"]],
         "chainId": int,
+        "authorizationList": List,
     },
     total=False,
 )
@@ -49,6 +69,22 @@ class ChainInterfaceAbc(ABC):
         self._communicator = communicator
         self._process = process
 
+    @property
+    def execution_timeout(self) -> Optional[float]:
+        return self._communicator.execution_timeout
+
+    @execution_timeout.setter
+    def execution_timeout(self, value: Optional[float]) -> None:
+        self._communicator.execution_timeout = value
+
+    @property
+    def signing_timeout(self) -> Optional[float]:
+        return self._communicator.signing_timeout
+
+    @signing_timeout.setter
+    def signing_timeout(self, value: Optional[float]) -> None:
+        self._communicator.signing_timeout = value
+
     @staticmethod
     def _encode_tx_params(transaction: TxParams) -> Dict:
         tx = {}
@@ -78,6 +114,8 @@ class ChainInterfaceAbc(ABC):
             tx["accessList"] = transaction["accessList"]
         if "chainId" in transaction:
             tx["chainId"] = hex(transaction["chainId"])
+        if "authorizationList" in transaction:
+            tx["authorizationList"] = transaction["authorizationList"]
         return tx
 
     @staticmethod
@@ -98,7 +136,13 @@ class ChainInterfaceAbc(ABC):
         chain_id: Optional[int] = None,
         fork: Optional[str] = None,
         hardfork: Optional[str] = None,
+        genesis_timestamp: Optional[int] = None,
     ) -> ChainInterfaceAbc:
+        if genesis_timestamp is not None and config.testing.cmd != "anvil":
+            raise NotImplementedError(
+                f"Setting genesis timestamp is not supported with {config.testing.cmd}"
+            )
+
         if config.testing.cmd == "anvil":
             args = ["anvil"] + config.testing.anvil.cmd_args.split()
             constructor = AnvilChainInterface
@@ -184,6 +228,16 @@ class ChainInterfaceAbc(ABC):
                 args += ["--chain.chainId", str(chain_id)]
         if fork is not None and not fork_set:
             args += ["-f", fork]
+        if (
+            config.testing.cmd == "anvil"
+            and config.testing.fork_timeout is not None
+            and any(arg in {"-f", "--fork-url", "--rpc-url"} for arg in args)
+            and "--timeout" not in args
+        ):
+            # anvil expects the fork request timeout in milliseconds
+            args += ["--timeout", str(int(config.testing.fork_timeout * 1000))]
+        if genesis_timestamp is not None and "--timestamp" not in args:
+            args += ["--timestamp", str(genesis_timestamp)]
         if hardfork is not None and not hardfork_set:
             if config.testing.cmd == "anvil":
                 args += ["--hardfork", hardfork]
@@ -284,6 +338,15 @@ class ChainInterfaceAbc(ABC):
             )[2:]
         )
 
+    def get_code_many(
+        self, addresses: List[str], block_identifier: Union[int, str] = "latest"
+    ) -> List[bytes]:
+        block = self._encode_block_identifier(block_identifier)
+        results = self._communicator.send_batch_request(
+            [("eth_getCode", [address, block]) for address in addresses]
+        )
+        return [bytes.fromhex(r[2:]) for r in results]
+
     def get_coinbase(self) -> str:
         return self._communicator.send_request("eth_coinbase")
 
@@ -425,6 +488,25 @@ class ChainInterfaceAbc(ABC):
             ],
         )
 
+    def debug_storage_range_at(
+        self,
+        block_hash: str,
+        tx_index: int,
+        address: str,
+        start_key: int,
+        max_results: int,
+    ) -> Dict[str, Any]:
+        return self._communicator.send_request(
+            "debug_storageRangeAt",
+            [
+                block_hash,
+                tx_index,
+                address,
+                "0x" + start_key.to_bytes(32, "big").hex(),
+                max_results,
+            ],
+        )
+
     def trace_transaction(self, tx_hash: str) -> List:
         return self._communicator.send_request("trace_transaction", [tx_hash])
 
@@ -442,13 +524,27 @@ class ChainInterfaceAbc(ABC):
             )[2:]
         )
 
+    def get_storage_at_many(
+        self,
+        requests: List[Tuple[str, int]],
+        block_identifier: Union[int, str] = "latest",
+    ) -> List[bytes]:
+        block = self._encode_block_identifier(block_identifier)
+        results = self._communicator.send_batch_request(
+            [
+                ("eth_getStorageAt", [address, hex(position), block])
+                for address, position in requests
+            ]
+        )
+        return [bytes.fromhex(r[2:]) for r in results]
+
     def get_logs(
         self,
         *,
         from_block: Optional[Union[int, str]] = None,
         to_block: Optional[Union[int, str]] = None,
-        address: Optional[str] = None,
-        topics: Optional[List[str]] = None,
+        address: Optional[Union[str, List[str]]] = None,
+        topics: Optional[List[Optional[Union[str, List[str]]]]] = None,
     ) -> List:
         params = {}
         if from_block is not None:
@@ -535,6 +631,9 @@ class HardhatChainInterface(ChainInterfaceAbc):
     def stop_impersonating_account(self, address: str) -> None:
         self._communicator.send_request("hardhat_stopImpersonatingAccount", [address])
 
+    def drop_transaction(self, tx_hash: str) -> None:
+        self._communicator.send_request("hardhat_dropTransaction", [tx_hash])
+
     def set_block_gas_limit(self, gas_limit: int) -> None:
         self._communicator.send_request("evm_setBlockGasLimit", [hex(gas_limit)])
 
@@ -606,6 +705,9 @@ class AnvilChainInterface(ChainInterfaceAbc):
     def stop_impersonating_account(self, address: str) -> None:
         self._communicator.send_request("anvil_stopImpersonatingAccount", [address])
 
+    def drop_transaction(self, tx_hash: str) -> None:
+        self._communicator.send_request("anvil_dropTransaction", [tx_hash])
+
     def set_block_gas_limit(self, gas_limit: int) -> None:
         self._communicator.send_request("evm_setBlockGasLimit", [hex(gas_limit)])
 
@@ -643,6 +745,9 @@ class AnvilChainInterface(ChainInterfaceAbc):
     def set_min_gas_price(self, value: int) -> None:
         self._communicator.send_request("anvil_setMinGasPrice", [hex(value)])
 
+    def set_chain_id(self, chain_id: int) -> None:
+        self._communicator.send_request("anvil_setChainId", [chain_id])
+
     def set_storage_at(self, address: str, position: int, value: bytes) -> None:
         self._communicator.send_request(
             "anvil_setStorageAt", [address, hex(position), "0x" + value.hex()]
@@ -651,6 +756,23 @@ class AnvilChainInterface(ChainInterfaceAbc):
     def node_info(self) -> Dict[str, Any]:
         return self._communicator.send_request("anvil_nodeInfo")
 
+    def set_block_timestamp_interval(self, interval: int) -> None:
+        self._communicator.send_request("anvil_setBlockTimestampInterval", [interval])
+
+    def remove_block_timestamp_interval(self) -> bool:
+        return self._communicator.send_request("anvil_removeBlockTimestampInterval")
+
+    def dump_state(self) -> Dict[str, Any]:
+        # anvil returns hex-encoded gzip-compressed JSON
+        data = self._communicator.send_request("anvil_dumpState")
+        if data.startswith("0x"):
+            data = data[2:]
+        return json.loads(gzip.decompress(bytes.fromhex(data)))
+
+    def load_state(self, state: Dict[str, Any]) -> bool:
+        data = gzip.compress(json.dumps(state).encode("utf-8"))
+        return self._communicator.send_request("anvil_loadState", ["0x" + data.hex()])
+
     def mine_many(self, num_blocks: int, timestamp_change: Optional[int]) -> None:
         self._communicator.send_request(
             "anvil_mine",
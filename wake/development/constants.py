@@ -8,7 +8,7 @@ import dataclasses
 from typing import List, Dict, Optional, overload, Union, Callable, Tuple
 from typing_extensions import Literal
 
-from wake.development.core import Contract, Library, Address, Account, Chain, RequestType
+from wake.development.core import Contract, Library, Address, Account, CallResult, Chain, RequestType
 from wake.development.primitive_types import *
 from wake.development.transactions import TransactionAbc, TransactionRevertedError
 """
@@ -27,6 +27,9 @@ wake.development.core.contracts_by_metadata = {contracts_by_metadata}
 wake.development.core.contracts_inheritance = {contracts_inheritance}
 wake.development.core.contracts_revert_constructor_index = {contracts_revert_constructor_index}
 wake.development.core.contracts_revert_index = {contracts_revert_index}
+wake.development.core.contracts_revert_constructor_locations = {contracts_revert_constructor_locations}
+wake.development.core.contracts_revert_locations = {contracts_revert_locations}
 wake.development.core.creation_code_index = {creation_code_index}
 wake.development.core.user_defined_value_types_index = {user_defined_value_types_index}
+wake.development.core.enums_index = {enums_index}
 """
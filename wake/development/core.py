@@ -1,17 +1,25 @@
 from __future__ import annotations
 
+import base64
 import dataclasses
 import functools
+import hashlib
 import importlib
 import json
 import math
+import os
 import re
 import sys
+import threading
+import time
+import warnings
+import weakref
 from abc import ABC, abstractmethod
 from bdb import BdbQuit
-from collections import ChainMap, defaultdict
-from contextlib import contextmanager
+from collections import ChainMap, OrderedDict, defaultdict
+from contextlib import contextmanager, nullcontext
 from copy import deepcopy
+from decimal import Decimal, InvalidOperation, localcontext
 from enum import Enum, IntEnum
 from os import PathLike
 from pathlib import Path
@@ -21,18 +29,23 @@ from typing import (
     Callable,
     DefaultDict,
     Dict,
+    Generic,
     Iterable,
+    Iterator,
     List,
+    Mapping,
     Optional,
     Sequence,
     Set,
     Tuple,
     Type,
+    TypeVar,
     Union,
     cast,
     overload,
 )
 from urllib.error import HTTPError
+from urllib.parse import parse_qsl, urlencode, urlsplit, urlunsplit
 
 import eth_abi
 import eth_abi.abi
@@ -41,7 +54,12 @@ import eth_abi.packed
 import eth_account
 import eth_account.messages
 import eth_utils
-from Crypto.Hash import BLAKE2b, keccak
+import rlp
+from Crypto.Cipher import AES
+from Crypto.Hash import SHA256, BLAKE2b, keccak
+from Crypto.PublicKey import ECC
+from Crypto.Random import get_random_bytes
+from Crypto.Signature import DSS, eddsa
 from typing_extensions import (
     Annotated,
     Literal,
@@ -53,9 +71,16 @@ from typing_extensions import (
 
 from wake.utils import StrEnum, get_class_that_defined_method
 
-from ..utils.keyed_default_dict import KeyedDefaultDict
+from ..utils.journal import (
+    Journal,
+    JournaledDict,
+    JournaledKeyedDefaultDict,
+    JournaledList,
+    JournaledSet,
+    JournalStats,
+)
 from . import hardhat_console
-from .blocks import ChainBlocks
+from .blocks import Block, ChainBlocks
 from .chain_interfaces import (
     AnvilChainInterface,
     ChainInterfaceAbc,
@@ -70,9 +95,10 @@ from .globals import (
     get_coverage_handler,
     get_exception_handler,
 )
-from .internal import UnknownEvent, read_from_memory
-from .json_rpc.communicator import JsonRpcError
+from .internal import ExternalEvent, UnknownEvent, read_from_memory
+from .json_rpc.communicator import ExecutionTimeout, JsonRpcError, SigningTimeout
 from .primitive_types import (
+    U256,
     FixedSizeBytes,
     FixedSizeList,
     Integer,
@@ -86,11 +112,17 @@ from .primitive_types import (
 if TYPE_CHECKING:
     from .transactions import (
         ChainTransactions,
+        EventSubscription,
+        TokenFlows,
+        TokenGuard,
         TransactionAbc,
         TransactionRevertedError,
     )
 
 
+T = TypeVar("T")
+ContractType = TypeVar("ContractType", bound="Contract")
+
 # selector => (contract_fqn => pytypes_object)
 errors: Dict[bytes, Dict[str, Any]] = {}
 # selector => (contract_fqn => pytypes_object)
@@ -105,15 +137,149 @@ contracts_inheritance: Dict[str, Tuple[str, ...]] = {}
 contracts_revert_constructor_index: Dict[str, Set[int]] = {}
 # contract_fqn => set of REVERT opcode PCs belonging to a revert statement
 contracts_revert_index: Dict[str, Set[int]] = {}
+# contract_fqn => REVERT/INVALID opcode PC => (source file path, line) for contract deployment/constructor
+contracts_revert_constructor_locations: Dict[str, Dict[int, Tuple[str, int]]] = {}
+# contract_fqn => REVERT/INVALID opcode PC => (source file path, line)
+contracts_revert_locations: Dict[str, Dict[int, Tuple[str, int]]] = {}
 # list of pairs of (creation code segments, contract_fqn)
 # where creation code segments is a tuple of (length, BLAKE2b hash)
 creation_code_index: List[Tuple[Tuple[Tuple[int, bytes], ...], str]] = []
 # user defined value type type identifier => underlying type type identifier
 user_defined_value_types_index: Dict[str, str] = {}
+# canonical name of a Solidity enum (as in ABI internalType) => list of (pytypes module name, attrs, number of members)
+enums_index: Dict[str, List[Tuple[str, Tuple[str, ...], int]]] = {}
+# selector => error type registered from pytypes of other projects
+external_errors: Dict[bytes, Any] = {}
+# selector => error ABI registered by `register_error_abi` or `Chain.register_abi`
+external_error_abis: Dict[bytes, Dict[str, Any]] = {}
+# paths of ABI files from config already loaded into external_error_abis
+_loaded_error_abis: Set[Path] = set()
 
 
 eth_account.Account.enable_unaudited_hdwallet_features()
 
+# class => (class annotations, include_extras => resolved type hints)
+# weak keys ensure that redefined classes (e.g. in notebooks) never hit stale entries and old classes can be collected
+_type_hints_cache: weakref.WeakKeyDictionary[
+    type, Tuple[Any, Dict[bool, Dict[str, Any]]]
+] = weakref.WeakKeyDictionary()
+
+
+def _get_type_hints(t: Any, include_extras: bool = False) -> Dict[str, Any]:
+    try:
+        annotations, hints = _type_hints_cache[t]
+    except (KeyError, TypeError):
+        annotations, hints = None, None
+
+    # annotations replaced in place invalidate the cached entry as well
+    if hints is None or annotations is not t.__dict__.get("__annotations__"):
+        hints = {}
+        try:
+            _type_hints_cache[t] = (t.__dict__.get("__annotations__"), hints)
+        except TypeError:
+            pass
+
+    if include_extras not in hints:
+        hints[include_extras] = get_type_hints(t, include_extras=include_extras)
+    return hints[include_extras]
+
+
+# id of an ABI parameter list => (the list, fixed parameters, canonical types)
+# the list is kept in the entry so that its id cannot be reused while cached
+_abi_types_cache: OrderedDict[
+    int, Tuple[List[Dict[str, Any]], List[Dict[str, Any]], List[str]]
+] = OrderedDict()
+_ABI_TYPES_CACHE_SIZE = 4096
+
+
+def _get_abi_types(
+    params: List[Dict[str, Any]]
+) -> Tuple[List[Dict[str, Any]], List[str]]:
+    """
+    Return ABI parameters processed by `fix_library_abi` and their canonical types used for encoding and decoding.
+    Results are cached per parameter list, so repeated calls of the same functions skip the processing.
+    The returned lists must not be modified.
+    """
+    key = id(params)
+    entry = _abi_types_cache.get(key)
+    if entry is not None and entry[0] is params:
+        try:
+            _abi_types_cache.move_to_end(key)
+        except KeyError:
+            pass
+        return entry[1], entry[2]
+
+    fixed = fix_library_abi(params)
+    types = [
+        eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg)) for arg in fixed
+    ]
+    _abi_types_cache[key] = (params, fixed, types)
+    while len(_abi_types_cache) > _ABI_TYPES_CACHE_SIZE:
+        try:
+            _abi_types_cache.popitem(last=False)
+        except KeyError:
+            break
+    return fixed, types
+
+
+def register_error_type(error_type: Type) -> None:
+    """
+    Register a custom error type (e.g. from `pytypes` of another project) used to decode reverts with unknown selectors.
+    """
+    external_errors[error_type.selector] = error_type
+
+
+def register_error_abi(
+    abi: Union[str, PathLike, List[Dict[str, Any]], Dict[str, Any]]
+) -> None:
+    """
+    Register custom errors from a JSON ABI used to decode reverts with unknown selectors into `ExternalError` instances.
+
+    Args:
+        abi: Path to a JSON file or already loaded ABI; compilation artifacts with an `abi` key are accepted as well.
+    """
+    if isinstance(abi, (str, PathLike)):
+        abi = json.loads(Path(abi).read_text())
+    if isinstance(abi, dict):
+        abi = abi["abi"]
+    assert isinstance(abi, list)
+
+    for item in abi:
+        if item.get("type") != "error":
+            continue
+
+        selector = eth_utils.abi.function_abi_to_4byte_selector(item)
+        external_error_abis.setdefault(selector, item)
+
+
+def unload_pytypes() -> None:
+    """
+    Reset the indexes populated by generated `pytypes` and remove `pytypes` modules from `sys.modules`,
+    so that regenerated `pytypes` can be imported again in the same interpreter (e.g. in notebooks)
+    without stale contract, error and event references.
+    """
+    global errors, events, contracts_by_fqn, contracts_by_metadata, contracts_inheritance
+    global contracts_revert_constructor_index, contracts_revert_index, creation_code_index
+    global contracts_revert_constructor_locations, contracts_revert_locations
+    global user_defined_value_types_index, enums_index
+
+    errors = {}
+    events = {}
+    contracts_by_fqn = {}
+    contracts_by_metadata = {}
+    contracts_inheritance = {}
+    contracts_revert_constructor_index = {}
+    contracts_revert_index = {}
+    contracts_revert_constructor_locations = {}
+    contracts_revert_locations = {}
+    creation_code_index = []
+    user_defined_value_types_index = {}
+    enums_index = {}
+
+    for module_name in list(sys.modules.keys()):
+        if module_name == "pytypes" or module_name.startswith("pytypes."):
+            del sys.modules[module_name]
+
 
 def get_contracts_by_fqn() -> Dict[str, Any]:
     return contracts_by_fqn
@@ -127,6 +293,60 @@ class RevertToSnapshotFailedError(Exception):
     pass
 
 
+class Snapshot(str):
+    """
+    Snapshot of a chain state returned by `Chain.snapshot`.
+    For backward compatibility, it is a string equal to the snapshot ID.
+    The block the snapshot was taken at is only fetched when reverting to the snapshot
+    (and only while the `Snapshot` object is referenced), so taking a snapshot costs a single request.
+    """
+
+    _chain: Chain
+    _block_number: Optional[int]
+    _timestamp: Optional[int]
+
+    def __new__(cls, snapshot_id: str, chain: Chain):
+        obj = super().__new__(cls, snapshot_id)
+        obj._chain = chain
+        obj._block_number = None
+        obj._timestamp = None
+        return obj
+
+    def __repr__(self) -> str:
+        return (
+            f"Snapshot(id={self.id!r}, block_number={self._block_number}, "
+            f"timestamp={self._timestamp})"
+        )
+
+    @property
+    def id(self) -> str:
+        return str.__str__(self)
+
+    @property
+    def chain(self) -> Chain:
+        return self._chain
+
+    @property
+    def block_number(self) -> Optional[int]:
+        """
+        Number of the latest block at the time the snapshot was taken, `None` until the snapshot is reverted.
+        """
+        return self._block_number
+
+    @property
+    def timestamp(self) -> Optional[int]:
+        """
+        Timestamp of the latest block at the time the snapshot was taken, `None` until the snapshot is reverted.
+        """
+        return self._timestamp
+
+    def revert(self) -> None:
+        self._chain.revert(self)
+
+    def release(self) -> None:
+        self._chain.release(self)
+
+
 class NotConnectedError(Exception):
     pass
 
@@ -139,13 +359,544 @@ class TransactionConfirmationFailedError(Exception):
     pass
 
 
+class BlockGasLimitExceeded(Exception):
+    gas_limit: int
+    pending_gas_used: int
+    block_gas_limit: int
+
+    def __init__(self, gas_limit: int, pending_gas_used: int, block_gas_limit: int):
+        self.gas_limit = gas_limit
+        self.pending_gas_used = pending_gas_used
+        self.block_gas_limit = block_gas_limit
+        super().__init__(
+            f"Transaction gas limit {gas_limit} does not fit into the pending block "
+            f"({pending_gas_used} of {block_gas_limit} gas already used)"
+        )
+
+
+class FeeTooLow(Exception):
+    """
+    Raised when the fee caps of an EIP-1559 transaction would be rejected by a real node,
+    i.e. `max_fee_per_gas` is lower than the base fee of the pending block or than `max_priority_fee_per_gas`.
+    """
+
+    max_fee_per_gas: int
+    max_priority_fee_per_gas: Optional[int]
+    base_fee_per_gas: Optional[int]
+
+    def __init__(
+        self,
+        max_fee_per_gas: int,
+        max_priority_fee_per_gas: Optional[int],
+        base_fee_per_gas: Optional[int],
+    ):
+        self.max_fee_per_gas = max_fee_per_gas
+        self.max_priority_fee_per_gas = max_priority_fee_per_gas
+        self.base_fee_per_gas = base_fee_per_gas
+
+        if (
+            max_priority_fee_per_gas is not None
+            and max_priority_fee_per_gas > max_fee_per_gas
+        ):
+            message = (
+                f"Max priority fee per gas {max_priority_fee_per_gas} "
+                f"is higher than max fee per gas {max_fee_per_gas}"
+            )
+        else:
+            message = (
+                f"Max fee per gas {max_fee_per_gas} "
+                f"is lower than the pending block base fee {base_fee_per_gas}"
+            )
+        super().__init__(message)
+
+
+class NonceMismatch(Exception):
+    account: Address
+    nonce: int
+    expected_nonce: int
+    tx_params: TxParams
+
+    def __init__(
+        self, account: Address, nonce: int, expected_nonce: int, tx_params: TxParams
+    ):
+        self.account = account
+        self.nonce = nonce
+        self.expected_nonce = expected_nonce
+        self.tx_params = tx_params
+
+        if "to" in tx_params:
+            target = f"to {tx_params['to']}"
+            if len(tx_params.get("data", b"")) >= 4:
+                target += f" (selector 0x{tx_params['data'][:4].hex()})"
+        else:
+            target = "creating a contract"
+        super().__init__(
+            f"Transaction from {account} {target} uses nonce {nonce} "
+            f"but the next nonce of the account on chain is {expected_nonce}"
+        )
+
+
+class NonceGap(NonceMismatch):
+    pass
+
+
+class NonceReuse(NonceMismatch):
+    pass
+
+
+class ReplayProtectionError(Exception):
+    tx_params: TxParams
+
+    def __init__(self, message: str, tx_params: TxParams):
+        self.tx_params = tx_params
+        super().__init__(message)
+
+
+class ChainMismatchError(ValueError):
+    """
+    Raised when an `Account` (or a contract) bound to one chain is used with another chain.
+    `account.on(chain)` returns the account with the same address bound to the other chain.
+    """
+
+    account: Account
+    expected_chain: Chain
+
+    def __init__(self, account: Account, expected_chain: Chain, role: str = "Account"):
+        self.account = account
+        self.expected_chain = expected_chain
+        super().__init__(
+            f"{role} {account.address} belongs to {account.chain._describe()} "
+            f"but is used with {expected_chain._describe()}, "
+            f"use `.on(chain)` to bind the address to the other chain"
+        )
+
+
+class LibraryNotDeployed(Exception):
+    name: str
+    address: Address
+
+    def __init__(self, name: str, address: Address):
+        self.name = name
+        self.address = address
+        super().__init__(f"Library {name} is not deployed at {address}")
+
+
+class IncompatibleEvmVersion(Exception):
+    """
+    Raised when deploying a contract compiled for an EVM version newer than the hardfork of the chain
+    that contains opcodes not available in the hardfork, listed in `opcodes`.
+    """
+
+    contract: str
+    evm_version: str
+    hardfork: str
+    opcodes: List[str]
+
+    def __init__(
+        self, contract: str, evm_version: str, hardfork: str, opcodes: List[str]
+    ):
+        self.contract = contract
+        self.evm_version = evm_version
+        self.hardfork = hardfork
+        self.opcodes = opcodes
+        message = f"{contract} was compiled for EVM version {evm_version} but the chain runs {hardfork}"
+        if len(opcodes) > 0:
+            message += f"; unsupported opcodes: {', '.join(opcodes)}"
+        super().__init__(message)
+
+
+class InitcodeSizeExceeded(Exception):
+    """
+    Raised when deploying a contract with initcode (creation code including constructor arguments) exceeding
+    the EIP-3860 initcode size limit of the active hardfork.
+    """
+
+    contract: str
+    size: int
+    limit: int
+
+    def __init__(self, contract: str, size: int, limit: int):
+        self.contract = contract
+        self.size = size
+        self.limit = limit
+        super().__init__(
+            f"Initcode of {contract} has {size} bytes, exceeding the initcode size limit of {limit} bytes"
+        )
+
+
+class DataTruncatedError(ValueError):
+    """
+    Raised when decoding transaction data truncated by one of the `general.max_*_data_size` config options.
+    """
+
+
+class DeploymentFailed(Exception):
+    """
+    Raised when a contract deployment sent with automine disabled would revert in the pending block.
+    The transaction is not sent, so the nonce of the deployer is not incremented and the pending block is not affected.
+    """
+
+    contract: Optional[str]
+    deployer: Address
+    nonce: int
+    error: TransactionRevertedError
+
+    def __init__(
+        self,
+        contract: Optional[str],
+        deployer: Address,
+        nonce: int,
+        error: TransactionRevertedError,
+    ):
+        self.contract = contract
+        self.deployer = deployer
+        self.nonce = nonce
+        self.error = error
+        super().__init__(
+            f"Deployment of {contract or 'unknown contract'} by {deployer} with nonce {nonce} "
+            f"would revert in the pending block: {error}"
+        )
+
+
+class CreateCollision(Exception):
+    """
+    Raised when a contract deployed on a forked chain would be created at an address already holding code or nonce
+    on the forked chain, e.g. because the nonce of an impersonated deployer is out of sync with the remote chain.
+    """
+
+    address: Address
+    deployer: Address
+    nonce: int
+    chain_nonce: int
+    contract: Optional[str]
+    existing: Optional[str]
+
+    def __init__(
+        self,
+        address: Address,
+        deployer: Address,
+        nonce: int,
+        chain_nonce: int,
+        contract: Optional[str],
+        existing: Optional[str],
+    ):
+        self.address = address
+        self.deployer = deployer
+        self.nonce = nonce
+        self.chain_nonce = chain_nonce
+        self.contract = contract
+        self.existing = existing
+
+        message = (
+            f"Deployment of {contract or 'a contract'} by {deployer} with nonce {nonce} "
+            f"collides with existing {existing or 'account'} at {address} on the forked chain"
+        )
+        if chain_nonce != nonce:
+            message += f"; the nonce of {deployer} on chain is {chain_nonce}"
+        super().__init__(message)
+
+
+class InvalidAuthorizationError(ValueError):
+    authorization: Authorization
+
+    def __init__(self, authorization: Authorization, message: str):
+        self.authorization = authorization
+        super().__init__(message)
+
+
+_SECP256K1_N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+
+
+@dataclasses.dataclass(frozen=True)
+class Authorization:
+    """
+    Signed [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization tuple.
+    Sets the code of the signing account (authority) to a delegation to `address`.
+    """
+
+    chain_id: int
+    address: Address
+    nonce: int
+    y_parity: int
+    r: int
+    s: int
+
+    @staticmethod
+    def signing_hash(chain_id: int, address: Address, nonce: int) -> bytes:
+        return keccak.new(
+            data=b"\x05" + rlp.encode([chain_id, bytes(address), nonce]),
+            digest_bits=256,
+        ).digest()
+
+    @property
+    def authority(self) -> Address:
+        """
+        Recover the account that signed the authorization.
+        Raises `InvalidAuthorizationError` if the signature is not valid.
+        """
+        if self.y_parity not in {0, 1}:
+            raise InvalidAuthorizationError(
+                self, f"Authorization y_parity must be 0 or 1, got {self.y_parity}"
+            )
+        if not 0 < self.r < _SECP256K1_N or not 0 < self.s <= _SECP256K1_N // 2:
+            raise InvalidAuthorizationError(
+                self, "Authorization signature r or s value is out of range"
+            )
+
+        try:
+            signer = eth_account.Account._recover_hash(
+                self.signing_hash(self.chain_id, self.address, self.nonce),
+                vrs=(self.y_parity, self.r, self.s),
+            )
+        except Exception as e:
+            raise InvalidAuthorizationError(
+                self, f"Authorization signature is not valid: {e}"
+            ) from None
+        return Address(signer)
+
+    def to_json(self) -> Dict[str, str]:
+        return {
+            "chainId": hex(self.chain_id),
+            "address": str(self.address),
+            "nonce": hex(self.nonce),
+            "yParity": hex(self.y_parity),
+            "r": hex(self.r),
+            "s": hex(self.s),
+        }
+
+    @classmethod
+    def from_json(cls, data: Dict[str, str]) -> Authorization:
+        return cls(
+            chain_id=int(data["chainId"], 16),
+            address=Address(data["address"]),
+            nonce=int(data["nonce"], 16),
+            y_parity=int(data["yParity"], 16),
+            r=int(data["r"], 16),
+            s=int(data["s"], 16),
+        )
+
+
+def _sign_eip7702_transaction(
+    tx_params: TxParams, key: Union[bytes, bytearray]
+) -> bytes:
+    """
+    Sign a type 4 (EIP-7702) transaction and return the raw transaction. `eth_account` does not support type 4 transactions.
+    """
+    fields = [
+        tx_params["chainId"],
+        tx_params["nonce"],
+        tx_params["maxPriorityFeePerGas"],
+        tx_params["maxFeePerGas"],
+        tx_params["gas"],
+        bytes(Address(tx_params["to"])),
+        tx_params.get("value", 0),
+        bytes(tx_params.get("data", b"")),
+        [
+            [
+                bytes(Address(entry["address"])),
+                [bytes.fromhex(k[2:].zfill(64)) for k in entry["storageKeys"]],
+            ]
+            for entry in tx_params.get("accessList", [])
+        ],
+        [
+            [a.chain_id, bytes(a.address), a.nonce, a.y_parity, a.r, a.s]
+            for a in map(Authorization.from_json, tx_params["authorizationList"])
+        ],
+    ]
+    signed = eth_account.Account.signHash(
+        keccak.new(data=b"\x04" + rlp.encode(fields), digest_bits=256).digest(), key
+    )
+    return b"\x04" + rlp.encode(fields + [signed.v - 27, signed.r, signed.s])
+
+
+@dataclasses.dataclass(frozen=True)
+class Withdrawal:
+    """
+    Validator withdrawal from the beacon chain ([EIP-4895](https://eips.ethereum.org/EIPS/eip-4895)).
+    """
+
+    index: int
+    validator_index: int
+    address: Address
+    amount: int
+    "Withdrawn amount in Gwei"
+
+
+@dataclasses.dataclass(frozen=True)
+class ForkInfo:
+    """
+    Metadata of the network a chain was forked from.
+    """
+
+    url: Optional[str]
+    "URL of the forked network with secrets (credentials, API keys) redacted"
+    block_number: Optional[int]
+    block_hash: Optional[str]
+    chain_id: Optional[int]
+    client_version: Optional[str]
+    "Client version of the forked network node, `None` if the node is unreachable"
+
+    def to_json(self) -> Dict[str, Any]:
+        return dataclasses.asdict(self)
+
+
+_URL_SECRET_RE = re.compile(r"^[A-Za-z0-9_\-]{20,}$")
+
+
+def _redact_url(url: str) -> str:
+    parts = urlsplit(url)
+    netloc = parts.netloc
+    if "@" in netloc:
+        netloc = "***@" + netloc.rsplit("@", 1)[1]
+    path = "/".join(
+        "***" if _URL_SECRET_RE.match(segment) else segment
+        for segment in parts.path.split("/")
+    )
+    query = urlencode([(k, "***") for k, _ in parse_qsl(parts.query)], safe="*")
+    return urlunsplit((parts.scheme, netloc, path, query, parts.fragment))
+
+
+# EIP-4788 beacon roots contract address and runtime code
+BEACON_ROOTS_ADDRESS = "0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02"
+_BEACON_ROOTS_CODE = bytes.fromhex(
+    "3373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35"
+    "801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b"
+    "62001fff42064281555f359062001fff015500"
+)
+_BEACON_ROOTS_HISTORY_LENGTH = 8191
+
+# RIP-7212 secp256r1 signature verification precompile address
+P256_VERIFY_ADDRESS = "0x0000000000000000000000000000000000000100"
+
+
+class AccessList(Dict["Address", List[int]]):
+    """
+    [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list mapping addresses to accessed storage slots.
+    Returned by `access_list` requests and accepted by the `access_list` keyword argument of transactions and calls.
+    Can be constructed from a mapping with `Account`, `Address` or `str` keys or from the JSON-RPC list of dictionaries format.
+    """
+
+    def __init__(
+        self,
+        access_list: Union[
+            Mapping[Union[Account, Address, str], Iterable[int]],
+            Iterable[Dict[str, Any]],
+            None,
+        ] = None,
+    ):
+        super().__init__()
+        if access_list is None:
+            return
+
+        if isinstance(access_list, Mapping):
+            items = access_list.items()
+        else:
+            items = [
+                (
+                    e["address"],
+                    [
+                        int(k, 16) if isinstance(k, str) else k
+                        for k in e.get("storageKeys", [])
+                    ],
+                )
+                for e in access_list
+            ]
+
+        for k, v in items:
+            if isinstance(k, Account):
+                k = k.address
+            elif isinstance(k, str):
+                k = Address(k)
+            elif not isinstance(k, Address):
+                raise TypeError("access_list keys must be Account, Address or str")
+            slots = self.setdefault(k, [])
+            for slot in v:
+                if slot not in slots:
+                    slots.append(slot)
+
+    @classmethod
+    def from_json(cls, data: Iterable[Dict[str, Any]]) -> AccessList:
+        return cls(data)
+
+    def to_json(self) -> List[Dict[str, Any]]:
+        return [
+            {"address": str(k), "storageKeys": [hex(i) for i in v]}
+            for k, v in self.items()
+        ]
+
+    def merge(
+        self, other: Mapping[Union[Account, Address, str], Iterable[int]]
+    ) -> AccessList:
+        """
+        Return a new access list containing addresses and storage slots of both access lists.
+        """
+        ret = AccessList(self)
+        for k, v in AccessList(other).items():
+            slots = ret.setdefault(k, [])
+            slots.extend(slot for slot in v if slot not in slots)
+        return ret
+
+    def __or__(self, other: Mapping) -> AccessList:
+        return self.merge(other)
+
+    def __ior__(self, other: Mapping) -> AccessList:
+        for k, v in AccessList(other).items():
+            slots = self.setdefault(k, [])
+            slots.extend(slot for slot in v if slot not in slots)
+        return self
+
+    @property
+    def storage_keys_count(self) -> int:
+        return sum(len(v) for v in self.values())
+
+    def __str__(self) -> str:
+        lines = ["AccessList("]
+        for k, v in self.items():
+            lines.append(f"  {k}: [{', '.join(hex(i) for i in v)}]")
+        lines.append(")")
+        return "\n".join(lines) if len(self) > 0 else "AccessList()"
+
+    def __repr__(self) -> str:
+        return f"AccessList({dict.__repr__(self)})"
+
+
 class RequestType(StrEnum):
     ACCESS_LIST = "access_list"
     CALL = "call"
+    CALL_RESULT = "call_result"
     ESTIMATE = "estimate"
     TX = "tx"
 
 
+@dataclasses.dataclass(frozen=True)
+class CallResult(Generic[T]):
+    """
+    Result of a `request_type="call_result"` request.
+    """
+
+    value: T
+    """Decoded return value (creation output for deployments)."""
+    raw: bytes
+    """Raw return data."""
+    gas_used: int
+    """Gas used by the simulated call, including the intrinsic gas."""
+
+
+def _decode_bytes_view(data: bytes) -> Optional[memoryview]:
+    """
+    Decode ABI-encoded `bytes` as a `memoryview` of `data` without copying, `None` if the encoding is invalid.
+    """
+    if len(data) < 64:
+        return None
+    offset = int.from_bytes(data[:32], "big")
+    if offset + 32 > len(data):
+        return None
+    length = int.from_bytes(data[offset : offset + 32], "big")
+    if offset + 32 + length > len(data):
+        return None
+    return memoryview(data)[offset + 32 : offset + 32 + length]
+
+
 def fix_library_abi(args: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
     ret = []
     for arg in args:
@@ -168,6 +919,34 @@ def fix_library_abi(args: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
     return ret
 
 
+def _as_buffer(data: Any) -> Union[bytes, bytearray, memoryview]:
+    """
+    Return `bytes` and `bytearray` unchanged and a flat byte `memoryview` (sharing memory) of any other object
+    supporting the buffer protocol, e.g. `memoryview` or a C-contiguous `numpy` array.
+    """
+    if isinstance(data, (bytes, bytearray)):
+        return data
+    try:
+        view = memoryview(data)
+    except TypeError:
+        raise TypeError(
+            f"Expected a bytes-like object, got {type(data).__name__}"
+        ) from None
+
+    if view.ndim == 1 and view.format == "B":
+        return view
+    try:
+        return view.cast("B")
+    except TypeError:
+        # non-contiguous or non-native format, a copy cannot be avoided
+        return view.tobytes()
+
+
+def _as_bytes(data: Any) -> Union[bytes, bytearray]:
+    buffer = _as_buffer(data)
+    return buffer.tobytes() if isinstance(buffer, memoryview) else buffer
+
+
 class abi:
     @classmethod
     def _normalize_input(cls, arguments: Iterable) -> List:
@@ -177,6 +956,8 @@ class abi:
                 ret.append(str(arg))
             elif isinstance(arg, Account):
                 ret.append(str(arg.address))
+            elif isinstance(arg, memoryview):
+                ret.append(_as_bytes(arg))
             elif isinstance(arg, (list, tuple)):
                 ret.append(cls._normalize_input(arg))
             elif dataclasses.is_dataclass(arg):
@@ -224,7 +1005,7 @@ class abi:
                 ret.append(t(arg))
             elif dataclasses.is_dataclass(t):
                 assert isinstance(arg, tuple)
-                resolved_types = get_type_hints(
+                resolved_types = _get_type_hints(
                     t  # pyright: ignore reportGeneralTypeIssues
                 )
                 field_types = [
@@ -272,7 +1053,7 @@ class abi:
         elif issubclass(t, (Account, Address)):
             return "address"
         elif dataclasses.is_dataclass(t):
-            hints = get_type_hints(
+            hints = _get_type_hints(
                 t,  # pyright: ignore reportGeneralTypeIssues
                 include_extras=True,
             )
@@ -303,7 +1084,7 @@ class abi:
             return "address"
         elif isinstance(args, str):
             return "string"
-        elif isinstance(args, (bytes, bytearray)):
+        elif isinstance(args, (bytes, bytearray, memoryview)):
             if hasattr(args, "length"):
                 return f"bytes{getattr(args, 'length')}"
             else:
@@ -370,10 +1151,7 @@ class abi:
         ):
             abi = args[0]._abi["inputs"]
             if len(abi) > 0:
-                types = [
-                    eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-                    for arg in fix_library_abi(abi)
-                ]
+                _, types = _get_abi_types(abi)
                 return args[0].selector + eth_abi.abi.encode(
                     types, cls._normalize_input(args)[0]
                 )
@@ -413,23 +1191,22 @@ class abi:
         selector = func.selector
         contract = get_class_that_defined_method(func)
         assert selector in contract._abi  # pyright: ignore reportGeneralTypeIssues
-        types = [
-            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-            for arg in fix_library_abi(
-                contract._abi[selector][  # pyright: ignore reportGeneralTypeIssues
-                    "inputs"
-                ]
-            )
-        ]
+        _, types = _get_abi_types(
+            contract._abi[selector]["inputs"]  # pyright: ignore reportGeneralTypeIssues
+        )
         return selector + eth_abi.abi.encode(types, cls._normalize_input(args))
 
     @classmethod
-    def decode(cls, data: bytes, types: Sequence[Type]) -> Any:
+    def decode(
+        cls, data: Union[bytes, bytearray, memoryview], types: Sequence[Type]
+    ) -> Any:
         from .transactions import (
             TransactionRevertedError,
             UnknownTransactionRevertedError,
         )
 
+        data = _as_bytes(data)
+
         if (
             len(types) == 1
             and hasattr(types[0], "_abi")
@@ -445,7 +1222,7 @@ class abi:
                 return types[0]()
             else:
                 t = types[0]
-                hints = get_type_hints(
+                hints = _get_type_hints(
                     t,  # pyright: ignore reportGeneralTypeIssues
                     include_extras=True,
                 )
@@ -484,6 +1261,8 @@ class Abi:
                 ret.append(str(arg))
             elif isinstance(arg, Account):
                 ret.append(str(arg.address))
+            elif isinstance(arg, memoryview):
+                ret.append(_as_bytes(arg))
             elif isinstance(arg, (list, tuple)):
                 ret.append(Abi._normalize_input(arg))
             else:
@@ -538,6 +1317,13 @@ class Abi:
 
     @classmethod
     def encode(cls, types: Iterable, arguments: Iterable) -> bytes:
+        types = list(types)
+        arguments = list(arguments)
+        if len(types) == len(arguments) and all(
+            t == "uint256" and isinstance(arg, U256) for t, arg in zip(types, arguments)
+        ):
+            # `U256` values are range checked when created, encode them without eth_abi validation
+            return b"".join(int.to_bytes(arg, 32, "big") for arg in arguments)
         return eth_abi.abi.encode(types, cls._normalize_input(arguments))
 
     @classmethod
@@ -564,22 +1350,157 @@ class Abi:
         selector = func.selector
         contract = get_class_that_defined_method(func)
         assert selector in contract._abi  # pyright: ignore reportGeneralTypeIssues
-        types = [
-            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-            for arg in fix_library_abi(
-                contract._abi[selector][  # pyright: ignore reportGeneralTypeIssues
-                    "inputs"
-                ]
-            )
-        ]
+        _, types = _get_abi_types(
+            contract._abi[selector]["inputs"]  # pyright: ignore reportGeneralTypeIssues
+        )
         return cls.encode_with_selector(selector, types, arguments)
 
     @classmethod
-    def decode(cls, types: Sequence[str], data: bytes) -> Any:
-        return cls._normalize_output(types, eth_abi.abi.decode(types, data))
+    def decode(
+        cls, types: Sequence[str], data: Union[bytes, bytearray, memoryview]
+    ) -> Any:
+        return cls._normalize_output(
+            types, eth_abi.abi.decode(types, _as_bytes(data))
+        )
+
+
+_WEI_UNITS = {
+    "wei": 0,
+    "kwei": 3,
+    "babbage": 3,
+    "femtoether": 3,
+    "mwei": 6,
+    "lovelace": 6,
+    "picoether": 6,
+    "gwei": 9,
+    "shannon": 9,
+    "nanoether": 9,
+    "nano": 9,
+    "szabo": 12,
+    "microether": 12,
+    "micro": 12,
+    "finney": 15,
+    "milliether": 15,
+    "milli": 15,
+    "ether": 18,
+    "eth": 18,
+    "kether": 21,
+    "grand": 21,
+    "mether": 24,
+    "gether": 27,
+    "tether": 30,
+}
+
+_WEI_STR_RE = re.compile(
+    r"^\s*(?P<amount>[+-]?[0-9_.]+(?:[eE][+-]?[0-9]+)?)\s*(?P<unit>[a-zA-Z]*)\s*$"
+)
+
+
+def _parse_wei(value: str) -> int:
+    if value.strip().lower().startswith(("0x", "-0x")):
+        return int(value, 16)
+
+    match = _WEI_STR_RE.match(value)
+    if match is None:
+        raise ValueError(f"Cannot parse '{value}' as an amount of Wei")
+
+    unit = match.group("unit").lower() or "wei"
+    if unit not in _WEI_UNITS:
+        raise ValueError(f"Unknown unit '{match.group('unit')}' in '{value}'")
+
+    with localcontext() as ctx:
+        ctx.prec = 200
+        try:
+            amount = Decimal(match.group("amount").replace("_", ""))
+        except InvalidOperation:
+            raise ValueError(f"Cannot parse '{value}' as an amount of Wei") from None
+        amount = amount.scaleb(_WEI_UNITS[unit])
+
+    if amount != amount.to_integral_value():
+        raise ValueError(f"'{value}' is not a whole number of Wei")
+    return int(amount)
+
+
+def get_keystore_paths() -> List[Path]:
+    """
+    Keystore directories searched for account aliases, in order of precedence: directories listed in the `WAKE_KEYSTORES`
+    environment variable (separated by `os.pathsep`), directories from the `general.keystores` config option
+    and the global keystore in the Wake data directory.
+    """
+    paths = [
+        Path(p).resolve()
+        for p in os.environ.get("WAKE_KEYSTORES", "").split(os.pathsep)
+        if len(p) > 0
+    ]
+    paths.extend(get_config().general.keystores)
+    paths.append(Path(get_config().global_data_path) / "keystore")
+    # remove duplicates while preserving precedence
+    return list(dict.fromkeys(paths))
+
+
+@dataclasses.dataclass(frozen=True)
+class KeystoreAlias:
+    alias: str
+    address: Address
+    path: Path
+    shadowed: bool
+    """
+    Whether a keystore with higher precedence contains the same alias.
+    """
+
+
+def list_keystore_aliases(
+    keystores: Optional[Iterable[PathLike]] = None,
+) -> List[KeystoreAlias]:
+    """
+    List all aliases in the given keystores (the keystore search path by default, see `get_keystore_paths`) in order of precedence.
+    """
+    if keystores is None:
+        paths = get_keystore_paths()
+    else:
+        paths = [Path(k) for k in keystores]
+
+    ret = []
+    seen = set()
+    for keystore in paths:
+        if not keystore.is_dir():
+            continue
+        for file in sorted(keystore.iterdir()):
+            if file.suffix != ".json" or not file.is_file():
+                continue
+            try:
+                address = json.loads(file.read_text())["address"]
+            except (json.JSONDecodeError, KeyError, TypeError):
+                continue
+            if not address.startswith("0x"):
+                address = "0x" + address
+            ret.append(
+                KeystoreAlias(
+                    file.stem, Address(address), file, file.stem in seen
+                )
+            )
+            seen.add(file.stem)
+    return ret
 
 
 class Wei(int):
+    def __new__(cls, value: Union[int, str] = 0, *args, **kwargs):
+        if isinstance(value, str) and len(args) == 0 and len(kwargs) == 0:
+            return super().__new__(cls, _parse_wei(value))
+        return super().__new__(cls, value, *args, **kwargs)
+
+    def to_string(self, unit: str = "ether") -> str:
+        """
+        Format the value in the given unit without loss of precision, e.g. `Wei(1_500_000_000_000_000_000).to_string()` returns `"1.5 ether"`.
+        """
+        if unit.lower() not in _WEI_UNITS:
+            raise ValueError(f"Unknown unit '{unit}'")
+
+        with localcontext() as ctx:
+            ctx.prec = 200
+            amount = Decimal(int(self)).scaleb(-_WEI_UNITS[unit.lower()]).normalize()
+        return f"{amount:f} {unit}"
+
     def to_ether(self) -> float:
         return self / 10**18
 
@@ -596,8 +1517,10 @@ class Wei(int):
 
     @classmethod
     def from_str(cls, value: str) -> Wei:
-        count, unit = value.split()
-        return cls(eth_utils.currency.to_wei(float(count), unit))
+        """
+        Parse an amount with an optional unit, e.g. `"1.5 ether"`, `"30gwei"` or `"1e18"` (Wei if no unit is given).
+        """
+        return cls(_parse_wei(value))
 
 
 @functools.total_ordering
@@ -680,42 +1603,38 @@ class Address:
         cls,
         alias: str,
         password: Optional[str] = None,
-        keystore: Optional[PathLike] = None,
+        keystore: Optional[Union[PathLike, Iterable[PathLike]]] = None,
     ) -> Address:
         global _private_keys_index
 
-        if keystore is None:
-            path = Path(get_config().global_data_path) / "keystore"
-        else:
-            path = Path(keystore)
-        if not path.is_dir():
-            raise ValueError(f"Keystore path {path} is not a directory")
-
-        path = path / f"{alias}.json"
-        if not path.exists():
-            raise ValueError(f"Alias {alias} not found in keystore {path}")
+        ret, key = _decrypt_alias(alias, password, keystore)
+        _private_keys_index[ret] = key
+        return ret
 
-        with path.open() as f:
-            data = json.load(f)
-
-        if not data["address"].startswith("0x"):
-            data["address"] = "0x" + data["address"]
-
-        if password is None:
-            import click
-
-            password = click.prompt(
-                f"Password for account {alias}", default="", hide_input=True
-            )
-
-        key = eth_account.Account.decrypt(data, password)
-
-        ret = cls(data["address"])
-        _private_keys_index[ret] = bytes(key)
-        return ret
+    @property
+    def has_signer(self) -> bool:
+        """
+        Whether the private key of the address is known and can be used for signing.
+        """
+        return self in _private_keys_index
 
     @property
     def private_key(self) -> Optional[bytes]:
+        """
+        Deprecated, use `export_private_key()` to export the key or `has_signer` to check for signing capability.
+        """
+        warnings.warn(
+            "`private_key` is deprecated, use `export_private_key()` or `has_signer` instead",
+            DeprecationWarning,
+            stacklevel=2,
+        )
+        return self.export_private_key()
+
+    def export_private_key(self) -> Optional[bytes]:
+        """
+        Export the raw private key of the address (if known). The returned copy is not protected in memory,
+        prefer `has_signer` when only checking for signing capability.
+        """
         return _private_keys_index.get(self, None)
 
 
@@ -753,21 +1672,88 @@ def detect_default_chain() -> Chain:
         raise NotConnectedError("default_chain not connected")
 
 
+_ENS_REGISTRY = Address("0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e")
+
+
+def _is_ens_name(name: str) -> bool:
+    return "." in name and not name.startswith(("0x", "0X"))
+
+
+def ens_namehash(name: str) -> bytes:
+    """
+    Compute the [ENS namehash](https://docs.ens.domains/resolution/names#namehash) of a name.
+    Labels are only lowercased, full UTS-46 normalization is not performed.
+    """
+    node = b"\x00" * 32
+    if name == "":
+        return node
+    for label in reversed(name.lower().split(".")):
+        label_hash = keccak.new(data=label.encode("utf-8"), digest_bits=256).digest()
+        node = keccak.new(data=node + label_hash, digest_bits=256).digest()
+    return node
+
+
+def _decrypt_alias(
+    alias: str,
+    password: Optional[str],
+    keystore: Optional[Union[PathLike, Iterable[PathLike]]],
+) -> Tuple[Address, bytes]:
+    if keystore is None:
+        keystores = get_keystore_paths()
+    elif isinstance(keystore, (str, PathLike)):
+        keystores = [Path(keystore)]
+        if not keystores[0].is_dir():
+            raise ValueError(f"Keystore path {keystores[0]} is not a directory")
+    else:
+        keystores = [Path(k) for k in keystore]
+
+    path = next(
+        (k / f"{alias}.json" for k in keystores if (k / f"{alias}.json").is_file()),
+        None,
+    )
+    if path is None:
+        raise ValueError(
+            f"Alias {alias} not found in keystores {', '.join(str(k) for k in keystores)}"
+        )
+
+    with path.open() as f:
+        data = json.load(f)
+
+    if not data["address"].startswith("0x"):
+        data["address"] = "0x" + data["address"]
+
+    if password is None:
+        import click
+
+        password = click.prompt(
+            f"Password for account {alias}", default="", hide_input=True
+        )
+
+    key = eth_account.Account.decrypt(data, password)
+    return Address(data["address"]), bytes(key)
+
+
 @functools.total_ordering
 class Account:
     _address: Address
     _chain: Chain
 
     def __init__(
-        self, address: Union[Address, str, int], chain: Optional[Chain] = None
+        self,
+        address: Union[Account, Address, str, int],
+        chain: Optional[Chain] = None,
     ) -> None:
+        if isinstance(address, Account):
+            if chain is None:
+                chain = address.chain
+            elif address.chain != chain:
+                raise ChainMismatchError(address, chain)
+            address = address.address
+
         if chain is None:
             chain = detect_default_chain()
 
-        if isinstance(address, Address):
-            self._address = address
-        else:
-            self._address = Address(address)
+        self._address = chain._resolve_address(address)
         self._chain = chain
 
     def __str__(self) -> str:
@@ -775,6 +1761,20 @@ class Account:
 
     __repr__ = __str__
 
+    def as_(self, contract_type: Type[ContractType]) -> ContractType:
+        """
+        Return the account as an instance of the given `pytypes` contract type bound to the same chain.
+        """
+        return contract_type(self._address, self._chain)
+
+    def on(self, chain: Chain) -> Account:
+        """
+        Return the account (of the same type, e.g. a contract) with the same address bound to the given chain.
+        """
+        if chain == self._chain:
+            return self
+        return self.__class__(self._address, chain)
+
     def __eq__(self, other: Any) -> bool:
         if isinstance(other, Account):
             return self._address == other._address and self._chain == other._chain
@@ -802,19 +1802,49 @@ class Account:
         return hash((self._address, self._chain))
 
     @classmethod
-    def new(cls, chain: Optional[Chain] = None, extra_entropy: bytes = b"") -> Account:
+    def new(
+        cls,
+        chain: Optional[Chain] = None,
+        extra_entropy: bytes = b"",
+        *,
+        per_chain: bool = True,
+    ) -> Account:
         if chain is None:
             chain = detect_default_chain()
 
         private_key = chain._new_private_key(extra_entropy)
-        address = Address.from_key(private_key)
+        return cls._with_key(private_key, chain, per_chain)
+
+    @classmethod
+    def _with_key(
+        cls, private_key: Union[str, int, bytes], chain: Chain, per_chain: bool
+    ) -> Account:
+        acc = eth_account.Account.from_key(private_key)
+        address = Address(acc.address)
+        cls._register_key(address, bytes(acc.key), chain, per_chain)
         return cls(address, chain)
 
+    @staticmethod
+    def _register_key(
+        address: Address, private_key: bytes, chain: Chain, per_chain: bool
+    ) -> None:
+        # keys are registered only on the chain of the account unless `per_chain=False` is given
+        if per_chain:
+            chain._private_keys_index[address] = private_key
+        else:
+            _private_keys_index[address] = private_key
+
     @classmethod
     def from_key(
-        cls, private_key: Union[str, int, bytes], chain: Optional[Chain] = None
+        cls,
+        private_key: Union[str, int, bytes],
+        chain: Optional[Chain] = None,
+        *,
+        per_chain: bool = True,
     ) -> Account:
-        return cls(Address.from_key(private_key), chain)
+        if chain is None:
+            chain = detect_default_chain()
+        return cls._with_key(private_key, chain, per_chain)
 
     @classmethod
     def from_mnemonic(
@@ -823,22 +1853,61 @@ class Account:
         passphrase: str = "",
         path: str = "m/44'/60'/0'/0/0",
         chain: Optional[Chain] = None,
+        *,
+        per_chain: bool = True,
     ) -> Account:
-        return cls(Address.from_mnemonic(mnemonic, passphrase, path), chain)
+        if chain is None:
+            chain = detect_default_chain()
+        acc = eth_account.Account.from_mnemonic(mnemonic, passphrase, path)
+        return cls._with_key(bytes(acc.key), chain, per_chain)
 
     @classmethod
     def from_alias(
         cls,
         alias: str,
         password: Optional[str] = None,
-        keystore: Optional[PathLike] = None,
+        keystore: Optional[Union[PathLike, Iterable[PathLike]]] = None,
         chain: Optional[Chain] = None,
+        *,
+        per_chain: bool = True,
     ) -> Account:
-        return cls(Address.from_alias(alias, password, keystore), chain)
+        if chain is None:
+            chain = detect_default_chain()
+        address, key = _decrypt_alias(alias, password, keystore)
+        cls._register_key(address, key, chain, per_chain)
+        return cls(address, chain)
+
+    def _private_keys(self) -> _PrivateKeysIndex:
+        # keys registered only on the account chain take precedence over global keys
+        if self._address in self._chain._private_keys_index:
+            return self._chain._private_keys_index
+        return _private_keys_index
+
+    @property
+    def has_signer(self) -> bool:
+        """
+        Whether the private key of the account is known and can be used for signing.
+        """
+        return self._address in self._private_keys()
 
     @property
     def private_key(self) -> Optional[bytes]:
-        return _private_keys_index.get(self._address, None)
+        """
+        Deprecated, use `export_private_key()` to export the key or `has_signer` to check for signing capability.
+        """
+        warnings.warn(
+            "`private_key` is deprecated, use `export_private_key()` or `has_signer` instead",
+            DeprecationWarning,
+            stacklevel=2,
+        )
+        return self.export_private_key()
+
+    def export_private_key(self) -> Optional[bytes]:
+        """
+        Export the raw private key of the account (if known). The returned copy is not protected in memory,
+        prefer `has_signer` when only checking for signing capability.
+        """
+        return self._private_keys().get(self._address, None)
 
     @property
     def address(self) -> Address:
@@ -877,11 +1946,75 @@ class Account:
         return self._chain.chain_interface.get_code(str(self._address))
 
     @code.setter
-    def code(self, value: Union[bytes, bytearray]) -> None:
-        if not isinstance(value, (bytes, bytearray)):
-            raise TypeError("value must be a bytes object")
+    def code(self, value: Union[bytes, bytearray, memoryview]) -> None:
+        try:
+            value = _as_buffer(value)
+        except TypeError:
+            raise TypeError("value must be a bytes-like object") from None
         self._chain.chain_interface.set_code(str(self.address), value)
 
+    def _query_code(self, opcode: int) -> int:
+        # init code executing `opcode` (EXTCODESIZE or EXTCODEHASH) on the account and returning the result,
+        # so that the code itself is not transferred over JSON-RPC
+        # the result is prefixed with a zero byte, as returned code must not start with 0xEF (EIP-3541)
+        init_code = (
+            b"\x73"
+            + bytes(self._address)
+            + bytes([opcode])
+            + bytes.fromhex("60015260216000f3")
+        )
+        output = self._chain.chain_interface.call({"data": init_code})
+        return int.from_bytes(output, "big")
+
+    @property
+    def code_size(self) -> int:
+        """
+        Size of the account code in bytes, read with `EXTCODESIZE` without fetching the code.
+        """
+        return self._query_code(0x3B)
+
+    @property
+    def code_hash(self) -> bytes:
+        """
+        Keccak-256 hash of the account code, read with `EXTCODEHASH` without fetching the code.
+        The hash of empty code is returned for accounts that do not exist.
+        """
+        code_hash = self._query_code(0x3F)
+        if code_hash == 0:
+            return keccak.new(data=b"", digest_bits=256).digest()
+        return code_hash.to_bytes(32, "big")
+
+    @property
+    def is_contract(self) -> bool:
+        """
+        Whether the account has code other than an EIP-7702 delegation designator.
+        """
+        size = self.code_size
+        if size == 23:
+            return self.delegated_to is None
+        return size > 0
+
+    @property
+    def delegated_to(self) -> Optional[Account]:
+        """
+        Account the code of this account is delegated to using an EIP-7702 delegation designator (`0xef0100 || address`),
+        or `None` if the account is not delegated.
+        """
+        code = self.code
+        if len(code) != 23 or code[:3] != b"\xef\x01\x00":
+            return None
+        return Account(Address(code[3:].hex()), chain=self._chain)
+
+    @property
+    def implementation(self) -> Optional[Account]:
+        """
+        Implementation (logic contract) of a proxy read from EIP-1967 (or legacy zeppelinos) storage slots,
+        or `None` if the account is not a recognized proxy.
+        """
+        from .utils import get_proxy_implementation
+
+        return get_proxy_implementation(self)
+
     @property
     def chain(self) -> Chain:
         return self._chain
@@ -899,10 +2032,184 @@ class Account:
         self._chain.chain_interface.set_nonce(str(self.address), value)
         self._chain._update_nonce(self.address, value)
 
+    @property
+    def is_empty(self) -> bool:
+        """
+        Whether the account is empty as defined by [EIP-161](https://eips.ethereum.org/EIPS/eip-161), i.e. has zero nonce,
+        zero balance and no code. Accounts that do not exist (e.g. cleared by EIP-161) are reported as empty as well.
+        Read with a single `EXTCODEHASH` query, which returns zero exactly for empty and non-existent accounts.
+        """
+        if self._chain._hardfork in _HARDFORKS[: _HARDFORKS.index("CONSTANTINOPLE")]:
+            # EXTCODEHASH is not available
+            return self.nonce == 0 and self.balance == 0 and self.code_size == 0
+        return self._query_code(0x3F) == 0
+
+    def balance_at(
+        self,
+        block: Union[
+            int,
+            Literal["latest"],
+            Literal["pending"],
+            Literal["earliest"],
+            Literal["safe"],
+            Literal["finalized"],
+        ],
+    ) -> Wei:
+        """
+        Returns:
+            Balance of the account at the given block (number or tag).
+        """
+        return Wei(self._chain.chain_interface.get_balance(str(self._address), block))
+
+    def code_at(
+        self,
+        block: Union[
+            int,
+            Literal["latest"],
+            Literal["pending"],
+            Literal["earliest"],
+            Literal["safe"],
+            Literal["finalized"],
+        ],
+    ) -> bytes:
+        """
+        Returns:
+            Code of the account at the given block (number or tag).
+        """
+        return self._chain.chain_interface.get_code(str(self._address), block)
+
+    def nonce_at(
+        self,
+        block: Union[
+            int,
+            Literal["latest"],
+            Literal["pending"],
+            Literal["earliest"],
+            Literal["safe"],
+            Literal["finalized"],
+        ],
+    ) -> int:
+        """
+        Returns:
+            Nonce of the account at the given block (number or tag).
+        """
+        return self._chain.chain_interface.get_transaction_count(
+            str(self._address), block
+        )
+
+    def storage_dump(
+        self,
+        limit: Optional[int] = None,
+        start: int = 0,
+        slots: Optional[Iterable[int]] = None,
+    ) -> Dict[int, bytes]:
+        """
+        Return all known non-zero storage slots of the account with slot numbers greater than or equal to `start`,
+        ordered by slot number and truncated to `limit` entries.
+        If `slots` is given, only these slots are read with a batch of `eth_getStorageAt` requests.
+        Otherwise, Anvil state dump of the whole chain is used when connected to a non-forked Anvil chain,
+        `debug_storageRangeAt` otherwise (slots with unknown preimages are omitted).
+        A call with `start` following the last slot returned by a truncated previous call of the same account
+        reuses the storage fetched by the previous call, as long as no block was mined and the chain was not reverted in between.
+        """
+        if limit is not None and limit < 0:
+            raise ValueError("limit must be non-negative")
+
+        chain_interface = self._chain.chain_interface
+        storage: Dict[int, bytes] = {}
+        cache_key: Optional[Tuple[Address, str, int]] = None
+
+        if slots is not None:
+            requested = sorted({slot for slot in slots if slot >= start})
+            values = chain_interface.get_storage_at_many(
+                [(str(self._address), slot) for slot in requested]
+            )
+            for slot, value in zip(requested, values):
+                if any(value):
+                    storage[slot] = value.rjust(32, b"\x00")
+        else:
+            block = chain_interface.get_block("latest")
+            cache_key = (self._address, block["hash"], self._chain._revert_count)
+            cache = self._chain._storage_dump_cache
+
+            if cache is not None and cache[0] == cache_key and start > cache[1]:
+                # next page of the previous call
+                storage = cache[2]
+            elif (
+                isinstance(chain_interface, AnvilChainInterface)
+                and self._chain._forked_chain_id is None
+            ):
+                accounts = {
+                    Address(k): v
+                    for k, v in chain_interface.dump_state()["accounts"].items()
+                }
+                if self._address in accounts:
+                    for k, v in accounts[self._address].get("storage", {}).items():
+                        value = int(v, 16)
+                        if value != 0:
+                            storage[int(k, 16)] = value.to_bytes(32, "big")
+            else:
+                # the state dump of a forked chain does not contain slots not touched locally
+                next_key: Optional[int] = 0
+                while next_key is not None:
+                    response = chain_interface.debug_storage_range_at(
+                        block["hash"],
+                        len(block["transactions"]),
+                        str(self._address),
+                        next_key,
+                        1024,
+                    )
+                    for entry in response["storage"].values():
+                        if entry["key"] is None:
+                            continue
+                        value = int(entry["value"], 16)
+                        if value != 0:
+                            storage[int(entry["key"], 16)] = value.to_bytes(
+                                32, "big"
+                            )
+                    next_key = (
+                        int(response["nextKey"], 16)
+                        if response.get("nextKey", None) is not None
+                        else None
+                    )
+
+        ret = {}
+        truncated = False
+        for slot in sorted(k for k in storage.keys() if k >= start):
+            if limit is not None and len(ret) >= limit:
+                truncated = True
+                break
+            ret[slot] = storage[slot]
+
+        if cache_key is not None:
+            # keep the storage for the next page only if the result was truncated
+            if truncated and len(ret) > 0:
+                self._chain._storage_dump_cache = (cache_key, max(ret), storage)
+            else:
+                self._chain._storage_dump_cache = None
+        return ret
+
+    def next_create_address(self, offset: int = 0) -> Address:
+        """
+        Compute the address of a contract created by this account using the `CREATE` opcode
+        (or a deployment transaction) `offset` creations from now.
+        """
+        from .utils import get_create_address
+
+        if offset < 0:
+            raise ValueError("offset must be non-negative")
+
+        # prefer locally tracked nonce (includes pending txs) over the on-chain one
+        if self._address in self._chain._nonces:
+            nonce = self._chain._nonces[self._address]
+        else:
+            nonce = self.nonce
+        return get_create_address(self._address, nonce + offset)
+
     def _setup_tx_params(
         self,
         request_type: RequestType,
-        data: Union[bytes, bytearray],
+        data: Union[bytes, bytearray, memoryview],
         value: Union[int, str],
         from_: Optional[Union[Account, Address, str]],
         gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]],
@@ -918,7 +2225,7 @@ class Account:
             value = Wei.from_str(value)
 
         params: TxParams = {
-            "data": data,
+            "data": _as_bytes(data),
             "value": value,
             "to": str(self._address),
         }
@@ -932,12 +2239,8 @@ class Account:
             elif request_type == RequestType.ACCESS_LIST:
                 from_ = self._chain.default_access_list_account
 
-        if isinstance(from_, Account):
-            if from_.chain != self._chain:
-                raise ValueError("`from_` account must belong to this chain")
-            params["from"] = str(from_.address)
-        elif isinstance(from_, (Address, str)):
-            params["from"] = str(from_)
+        if isinstance(from_, (Account, Address, str)):
+            params["from"] = str(self._chain._resolve_address(from_, "`from_` account"))
         else:
             raise TypeError("`from_` must be an Account, Address, or str")
 
@@ -970,21 +2273,7 @@ class Account:
         if access_list == "auto":
             params["accessList"] = "auto"
         elif access_list is not None:
-            # normalize access_list, all keys should be Address
-            tmp_access_list = defaultdict(list)
-            for k, v in access_list.items():
-                if isinstance(k, Account):
-                    k = k.address
-                elif isinstance(k, str):
-                    k = Address(k)
-                elif not isinstance(k, Address):
-                    raise TypeError("access_list keys must be Account, Address or str")
-                tmp_access_list[k].extend(v)
-            access_list = tmp_access_list
-            params["accessList"] = [
-                {"address": str(k), "storageKeys": [hex(i) for i in v]}
-                for k, v in access_list.items()
-            ]
+            params["accessList"] = AccessList(access_list).to_json()
 
         if type is not None:
             params["type"] = type
@@ -993,7 +2282,7 @@ class Account:
 
     def call(
         self,
-        data: Union[bytes, bytearray] = b"",
+        data: Union[bytes, bytearray, memoryview] = b"",
         value: Union[int, str] = 0,
         from_: Optional[Union[Account, Address, str]] = None,
         gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None,
@@ -1012,7 +2301,8 @@ class Account:
             Literal["safe"],
             Literal["finalized"],
         ] = "latest",
-    ) -> bytearray:
+        chain_id_override: Optional[int] = None,
+    ) -> Union[bytearray, memoryview]:
         params = self._setup_tx_params(
             RequestType.CALL,
             data,
@@ -1025,30 +2315,36 @@ class Account:
             access_list,
             type,
         )
-        params = self._chain._build_transaction(RequestType.CALL, params, [], None)
-
-        try:
-            coverage_handler = get_coverage_handler()
-            if coverage_handler is not None and self._chain._debug_trace_call_supported:
-                ret = self._chain.chain_interface.debug_trace_call(params, block)
-                coverage_handler.add_coverage(params, self._chain, ret)
+        with self._chain._chain_id_override(chain_id_override):
+            params = self._chain._build_transaction(RequestType.CALL, params, [], None)
 
-                ret_value = ret["returnValue"]
-                if ret_value.startswith("0x"):
-                    ret_value = ret_value[2:]
-                output = bytes.fromhex(ret_value)
-                if ret["failed"]:
-                    raise self._chain._process_revert_data(None, output) from None
-            else:
-                output = self._chain.chain_interface.call(params, block)
-        except JsonRpcError as e:
-            raise self._chain._process_call_revert(e) from None
+            try:
+                coverage_handler = get_coverage_handler()
+                if (
+                    coverage_handler is not None
+                    and self._chain._debug_trace_call_supported
+                ):
+                    ret = self._chain.chain_interface.debug_trace_call(params, block)
+                    coverage_handler.add_coverage(params, self._chain, ret)
+
+                    ret_value = ret["returnValue"]
+                    if ret_value.startswith("0x"):
+                        ret_value = ret_value[2:]
+                    output = bytes.fromhex(ret_value)
+                    if ret["failed"]:
+                        raise self._chain._process_revert_data(None, output) from None
+                else:
+                    output = self._chain.chain_interface.call(params, block)
+            except JsonRpcError as e:
+                raise self._chain._process_call_revert(e) from None
 
-        return bytearray(output)
+            if self._chain._zero_copy_returns:
+                return memoryview(output)
+            return bytearray(output)
 
     def estimate(
         self,
-        data: Union[bytes, bytearray] = b"",
+        data: Union[bytes, bytearray, memoryview] = b"",
         value: Union[int, str] = 0,
         from_: Optional[Union[Account, Address, str]] = None,
         gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None,
@@ -1067,6 +2363,8 @@ class Account:
             Literal["safe"],
             Literal["finalized"],
         ] = "pending",
+        exact: Optional[bool] = None,
+        chain_id_override: Optional[int] = None,
     ) -> int:
         params = self._setup_tx_params(
             RequestType.ESTIMATE,
@@ -1080,16 +2378,17 @@ class Account:
             access_list,
             type,
         )
-        params = self._chain._build_transaction(RequestType.CALL, params, [], None)
+        with self._chain._chain_id_override(chain_id_override):
+            params = self._chain._build_transaction(RequestType.CALL, params, [], None)
 
-        try:
-            return self._chain.chain_interface.estimate_gas(params, block)
-        except JsonRpcError as e:
-            raise self._chain._process_call_revert(e) from None
+            try:
+                return self._chain._estimate_gas(params, block, exact)
+            except JsonRpcError as e:
+                raise self._chain._process_call_revert(e) from None
 
     def access_list(
         self,
-        data: Union[bytes, bytearray] = b"",
+        data: Union[bytes, bytearray, memoryview] = b"",
         value: Union[int, str] = 0,
         from_: Optional[Union[Account, Address, str]] = None,
         gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None,
@@ -1105,6 +2404,7 @@ class Account:
             Literal["safe"],
             Literal["finalized"],
         ] = "pending",
+        chain_id_override: Optional[int] = None,
     ):
         params = self._setup_tx_params(
             RequestType.ACCESS_LIST,
@@ -1118,22 +2418,20 @@ class Account:
             {},
             type,
         )
-        params = self._chain._build_transaction(
-            RequestType.ACCESS_LIST, params, [], None
-        )
+        with self._chain._chain_id_override(chain_id_override):
+            params = self._chain._build_transaction(
+                RequestType.ACCESS_LIST, params, [], None
+            )
 
-        try:
-            response = self._chain.chain_interface.create_access_list(params, block)
-            return {
-                Address(e["address"]): [int(s, 16) for s in e["storageKeys"]]
-                for e in response["accessList"]
-            }, int(response["gasUsed"], 16)
-        except JsonRpcError as e:
-            raise self._chain._process_call_revert(e) from None
+            try:
+                response = self._chain.chain_interface.create_access_list(params, block)
+                return AccessList(response["accessList"]), int(response["gasUsed"], 16)
+            except JsonRpcError as e:
+                raise self._chain._process_call_revert(e) from None
 
     def transact(
         self,
-        data: Union[bytes, bytearray] = b"",
+        data: Union[bytes, bytearray, memoryview] = b"",
         value: Union[int, str] = 0,
         from_: Optional[Union[Account, Address, str]] = None,
         gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None,
@@ -1145,7 +2443,10 @@ class Account:
         ] = None,
         type: Optional[int] = None,
         confirmations: Optional[int] = None,
+        authorization_list: Optional[List[Authorization]] = None,
+        chain_id_override: Optional[int] = None,
     ) -> TransactionAbc[bytearray]:
+        self._chain._check_chain_id_override(chain_id_override, confirmations)
         tx_params = self._setup_tx_params(
             RequestType.TX,
             data,
@@ -1158,38 +2459,63 @@ class Account:
             access_list,
             type,
         )
-        tx_params = self._chain._build_transaction(
-            RequestType.CALL, tx_params, [], None
-        )
+        if authorization_list is not None:
+            # validated before building the transaction so that gas is estimated with the adjusted authorizations
+            authorization_list, authority_nonces = (
+                self._chain._validate_authorization_list(
+                    Address(tx_params["from"]), authorization_list
+                )
+            )
+            if type is None:
+                tx_params["type"] = 4
+            tx_params["authorizationList"] = [
+                a.to_json() for a in authorization_list
+            ]
+        else:
+            authority_nonces = {}
+        tx_params = self._chain._build_transaction(RequestType.CALL, tx_params, [], None)
 
-        tx_hash = self._chain._send_transaction(tx_params, from_)
+        if chain_id_override is not None and "chainId" in tx_params:
+            tx_params["chainId"] = chain_id_override
 
-        if "type" not in tx_params:
-            from .transactions import LegacyTransaction
+        # only signing and mining use the overridden chain ID
+        with self._chain._chain_id_override(chain_id_override):
+            tx_hash = self._chain._send_transaction(tx_params, from_)
+            for authority, nonce in authority_nonces.items():
+                self._chain._update_nonce(authority, nonce)
 
-            tx_type = LegacyTransaction[bytearray]
-        elif tx_params["type"] == 1:
-            from .transactions import Eip2930Transaction
+            if "type" not in tx_params:
+                from .transactions import LegacyTransaction
 
-            tx_type = Eip2930Transaction[bytearray]
-        elif tx_params["type"] == 2:
-            from .transactions import Eip1559Transaction
+                tx_type = LegacyTransaction[bytearray]
+            elif tx_params["type"] == 1:
+                from .transactions import Eip2930Transaction
 
-            tx_type = Eip1559Transaction[bytearray]
-        else:
-            raise ValueError(f"Unknown transaction type {tx_params['type']}")
+                tx_type = Eip2930Transaction[bytearray]
+            elif tx_params["type"] == 2:
+                from .transactions import Eip1559Transaction
 
-        tx = tx_type(
-            tx_hash,
-            tx_params,
-            None,
-            bytearray,
-            self.chain,
-        )
+                tx_type = Eip1559Transaction[bytearray]
+            elif tx_params["type"] == 4:
+                from .transactions import Eip7702Transaction
 
-        if confirmations != 0:
-            tx.wait(confirmations)
+                tx_type = Eip7702Transaction[bytearray]
+            else:
+                raise ValueError(f"Unknown transaction type {tx_params['type']}")
+
+            tx = tx_type(
+                tx_hash,
+                tx_params,
+                None,
+                bytearray,
+                self.chain,
+            )
+            tx._gas_estimate = self._chain._last_gas_estimate
 
+            if confirmations != 0:
+                tx.wait(confirmations)
+
+        if confirmations != 0:
             coverage_handler = get_coverage_handler()
             if coverage_handler is not None:
                 tx._fetch_debug_trace_transaction()
@@ -1199,28 +2525,95 @@ class Account:
                     tx._debug_trace_transaction,  # pyright: ignore reportGeneralTypeIssues
                 )
 
+            if self._chain._detect_uninitialized_reads:
+                self._chain._process_uninitialized_reads(tx)
+
             if self._chain.tx_callback is not None:
                 self._chain.tx_callback(tx)
+            if self._chain._batched_tx_callback is not None:
+                self._chain._add_to_tx_batch(tx)
+
+            self._chain._run_block_mined_hooks()
 
             if tx.error is not None:
                 raise tx.error
 
         return tx
 
-    def sign(self, data: bytes) -> bytes:
+    def sign(
+        self, data: bytes, *, prefix: Optional[Callable[[bytes], bytes]] = None
+    ) -> bytes:
         """
         Sign raw data according to EIP-191 type 0x45.
         Specifically, sign(keccak256(b"\x19Ethereum Signed Message:\n" + len(data) + data)) is returned.
+
+        A custom `prefix` scheme (e.g. `signed_message_prefix("TRON")` or `adr36_prefix(signer)`) maps the data
+        to the 32-byte digest that is signed instead.
         """
-        if self.private_key is None:
+        if prefix is not None:
+            if not self.has_signer:
+                raise NotImplementedError(
+                    "Signing with a custom prefix is not supported for accounts without supplied private key"
+                )
+            return self.sign_hash(prefix(data))
+
+        if not self.has_signer:
             return self._chain.chain_interface.sign(str(self._address), data)
         else:
-            return bytes(
-                eth_account.Account.sign_message(
-                    eth_account.messages.encode_defunct(data),
-                    self.private_key,
-                ).signature
+            with self._private_keys().use(self._address) as key:
+                return bytes(
+                    eth_account.Account.sign_message(
+                        eth_account.messages.encode_defunct(data),
+                        key,
+                    ).signature
+                )
+
+    def sign_intended_validator(
+        self, validator: Union[Account, Address, str], data: bytes
+    ) -> bytes:
+        """
+        Sign data with intended validator according to EIP-191 version 0x00.
+        Specifically, sign(keccak256(b"\x19\x00" + validator + data)) is returned.
+        """
+        if isinstance(validator, Account):
+            validator = validator.address
+        elif not isinstance(validator, Address):
+            validator = Address(validator)
+
+        if not self.has_signer:
+            raise NotImplementedError(
+                "Signing data with intended validator (EIP-191 version 0x00) is not supported for accounts without supplied private key"
+            )
+        else:
+            with self._private_keys().use(self._address) as key:
+                return bytes(
+                    eth_account.Account.sign_message(
+                        eth_account.messages.encode_intended_validator(
+                            str(validator), data
+                        ),
+                        key,
+                    ).signature
+                )
+
+    def verify_intended_validator(
+        self, validator: Union[Account, Address, str], data: bytes, signature: bytes
+    ) -> bool:
+        """
+        Check that `signature` of data with intended validator (EIP-191 version 0x00) was created by this account.
+        """
+        if isinstance(validator, Account):
+            validator = validator.address
+        elif not isinstance(validator, Address):
+            validator = Address(validator)
+
+        try:
+            signer = eth_account.Account.recover_message(
+                eth_account.messages.encode_intended_validator(str(validator), data),
+                signature=signature,
             )
+        except Exception:
+            return False
+        return Address(signer) == self._address
 
     def sign_hash(self, data_hash: bytes) -> bytes:
         """
@@ -1228,148 +2621,60 @@ class Account:
         This is not recommended for most use cases.
         Specifically, sign(data_hash) is returned.
         """
-        if self.private_key is None:
+        if not self.has_signer:
             raise NotImplementedError(
                 "Signing data hash without prefix (non EIP-191 compliant) is not supported for accounts without supplied private key"
             )
         else:
-            return bytes(
-                eth_account.Account.signHash(
-                    data_hash,
-                    self.private_key,
-                ).signature
-            )
-
-    def _prepare_eip712_dict(
-        self, message: Any, domain: Eip712Domain, client_signing: bool
-    ) -> Dict[str, Any]:
-        def _get_type(t: Type) -> str:
-            origin = get_origin(t)
+            with self._private_keys().use(self._address) as key:
+                return bytes(
+                    eth_account.Account.signHash(
+                        data_hash,
+                        key,
+                    ).signature
+                )
 
-            if isinstance(origin, type) and issubclass(origin, list):
-                if hasattr(origin, "length"):
-                    return f"{_get_type(get_args(t)[0])}[{getattr(origin, 'length')}]"
-                else:
-                    return f"{_get_type(get_args(t)[0])}[]"
-            elif isinstance(t, type) and issubclass(t, Integer):
-                if t.min == 0:
-                    bits = math.ceil(math.log2(t.max + 1))
-                    return f"uint{bits}"
-                else:
-                    bits = math.ceil(math.log2(t.max - t.min + 1))
-                    return f"int{bits}"
-            elif isinstance(t, type) and issubclass(t, FixedSizeBytes):
-                return f"bytes{t.length}"
-            elif t is int:
-                # fallback for int used directly
-                return "int256"
-            elif t is bytes or t is bytearray:
-                return "bytes"
-            elif t is str:
-                return "string"
-            elif issubclass(t, Enum):
-                return "uint8"
-            elif t is bool:
-                return "bool"
-            elif issubclass(t, (Account, Address)):
-                return "address"
-            elif dataclasses.is_dataclass(t):
-                return getattr(t, "original_name", t.__name__)
-            else:
-                raise ValueError(f"Unsupported type {t}")
+    def sign_authorization(
+        self,
+        address: Union[Account, Address, str],
+        *,
+        chain_id: Optional[int] = None,
+        nonce: Optional[int] = None,
+        self_sponsored: bool = False,
+    ) -> Authorization:
+        """
+        Sign an EIP-7702 authorization delegating the code of this account to `address`.
+        `chain_id` defaults to the chain ID of the account's chain (use 0 for a cross-chain authorization).
+        `nonce` defaults to the current nonce of the account, or to the current nonce + 1 if `self_sponsored` is set,
+        i.e. the authorization will be included in a transaction sent by this account.
+        """
+        if not self.has_signer:
+            raise NotImplementedError(
+                "Signing authorizations is not supported for accounts without supplied private key"
+            )
 
-        def _get_types(t: Type, types: Dict[str, List[Dict[str, str]]]) -> None:
-            if not dataclasses.is_dataclass(t):
-                return
+        if isinstance(address, Account):
+            address = address.address
+        elif isinstance(address, str):
+            address = Address(address)
 
-            name = getattr(t, "original_name", t.__name__)
-            if name in types:
-                return
+        if chain_id is None:
+            chain_id = self._chain.chain_id
+        if nonce is None:
+            nonce = self.nonce + 1 if self_sponsored else self.nonce
 
-            fields = []
-            hints = get_type_hints(
-                t,  # pyright: ignore reportGeneralTypeIssues
-                include_extras=True,
+        with self._private_keys().use(self._address) as key:
+            signed = eth_account.Account.signHash(
+                Authorization.signing_hash(chain_id, address, nonce), key
             )
-            for f in dataclasses.fields(t):
-                assert f.name in hints
-                fields.append(
-                    {
-                        "name": f.metadata.get("original_name", f.name),
-                        "type": _get_type(hints[f.name]),
-                    }
-                )
-
-            types[name] = fields
-
-            for f in dataclasses.fields(t):
-                assert f.name in hints
-                field_type = hints[f.name]
-                while isinstance(get_origin(field_type), type) and issubclass(
-                    get_origin(field_type), list
-                ):
-                    field_type = get_args(field_type)[0]
-                if dataclasses.is_dataclass(field_type):
-                    _get_types(field_type, types)
-
-        def _get_value(value: Any) -> Any:
-            if dataclasses.is_dataclass(value):
-                ret = {}
-                for f in dataclasses.fields(value):
-                    name = f.metadata.get("original_name", f.name)
-                    ret[name] = _get_value(getattr(value, f.name))
-                return ret
-            elif isinstance(value, (list, tuple)):
-                return [_get_value(v) for v in value]
-            elif isinstance(value, Account):
-                return str(value.address)
-            elif isinstance(value, Address):
-                return str(value)
-            elif isinstance(value, IntEnum):
-                return int(value)
-            elif isinstance(value, (bytes, bytearray)):
-                if client_signing:
-                    return "0x" + value.hex()
-                else:
-                    return value
-            else:
-                return value
-
-        types = {}
-        _get_types(type(message), types)
-
-        ret = {
-            "types": types,
-            "domain": {},
-            "primaryType": _get_type(type(message)),
-            "message": _get_value(message),
-        }
-
-        domain_type = []
-        if "name" in domain:
-            ret["domain"]["name"] = domain["name"]
-            domain_type.append({"name": "name", "type": "string"})
-        if "version" in domain:
-            ret["domain"]["version"] = domain["version"]
-            domain_type.append({"name": "version", "type": "string"})
-        if "chainId" in domain:
-            ret["domain"]["chainId"] = domain["chainId"]
-            domain_type.append({"name": "chainId", "type": "uint256"})
-        if "verifyingContract" in domain:
-            if isinstance(domain["verifyingContract"], Account):
-                ret["domain"]["verifyingContract"] = str(
-                    domain["verifyingContract"].address
-                )
-            else:
-                ret["domain"]["verifyingContract"] = str(domain["verifyingContract"])
-            domain_type.append({"name": "verifyingContract", "type": "address"})
-        if "salt" in domain:
-            ret["domain"]["salt"] = "0x" + domain["salt"].hex()
-            domain_type.append({"name": "salt", "type": "bytes32"})
-
-        ret["types"]["EIP712Domain"] = domain_type
-
-        return ret
+        return Authorization(
+            chain_id=chain_id,
+            address=address,
+            nonce=nonce,
+            y_parity=signed.v - 27,
+            r=signed.r,
+            s=signed.s,
+        )
 
     def sign_structured(
         self, message: Any, domain: Optional[Eip712Domain] = None
@@ -1379,7 +2684,7 @@ class Account:
         (https://eips.ethereum.org/EIPS/eip-712), or any ABI-compatible dataclass.
         """
 
-        client_signing = self.private_key is None
+        client_signing = not self.has_signer
 
         if isinstance(message, dict):
             if domain is not None:
@@ -1391,17 +2696,88 @@ class Account:
                 raise ValueError(
                     "Domain must be specified when message is not a dictionary"
                 )
-            message = self._prepare_eip712_dict(message, domain, client_signing)
+            message = _prepare_eip712_dict(message, domain, client_signing)
 
         if client_signing:
             return self._chain.chain_interface.sign_typed(str(self._address), message)
         else:
-            return bytes(
-                eth_account.Account.sign_message(
-                    eth_account.messages.encode_structured_data(message),
-                    self.private_key,
-                ).signature
+            with self._private_keys().use(self._address) as key:
+                return bytes(
+                    eth_account.Account.sign_message(
+                        eth_account.messages.encode_structured_data(message),
+                        key,
+                    ).signature
+                )
+
+    @staticmethod
+    def recover(
+        data: bytes,
+        signature: bytes,
+        *,
+        prefix: Optional[Callable[[bytes], bytes]] = None,
+    ) -> Address:
+        """
+        Recover the address of the account that signed `data` with `sign`, i.e. according to EIP-191 type 0x45
+        or with a custom `prefix` scheme.
+        Raises `ValueError` if the signature is not valid.
+        """
+        if prefix is not None:
+            return Account.recover_hash(prefix(data), signature)
+
+        try:
+            signer = eth_account.Account.recover_message(
+                eth_account.messages.encode_defunct(data), signature=signature
+            )
+        except Exception as e:
+            raise ValueError(f"Invalid signature: {e}") from None
+        return Address(signer)
+
+    @staticmethod
+    def recover_hash(data_hash: bytes, signature: bytes) -> Address:
+        """
+        Recover the address of the account that signed the 32-byte `data_hash` with `sign_hash`.
+        Raises `ValueError` if the signature is not valid.
+        """
+        if len(data_hash) != 32:
+            raise ValueError("Data hash must be 32 bytes long")
+
+        try:
+            signer = eth_account.Account._recover_hash(
+                bytes(data_hash), signature=signature
+            )
+        except Exception as e:
+            raise ValueError(f"Invalid signature: {e}") from None
+        return Address(signer)
+
+    @staticmethod
+    def recover_structured(
+        message: Any, signature: bytes, domain: Optional[Eip712Domain] = None
+    ) -> Address:
+        """
+        Recover the address of the account that signed structured data with `sign_structured` (EIP-712).
+        Message can be either a raw dictionary or any ABI-compatible dataclass together with `domain`.
+        Raises `ValueError` if the signature is not valid.
+        """
+        if isinstance(message, dict):
+            if domain is not None:
+                raise ValueError(
+                    "Domain cannot be specified when message is a dictionary"
+                )
+        else:
+            if domain is None:
+                raise ValueError(
+                    "Domain must be specified when message is not a dictionary"
+                )
+            message = _prepare_eip712_dict(message, domain, False)
+
+        try:
+            signer = eth_account.Account.recover_message(
+                eth_account.messages.encode_structured_data(message),
+                signature=signature,
             )
+        except Exception as e:
+            raise ValueError(f"Invalid signature: {e}") from None
+        return Address(signer)
 
 
 Eip712Domain = TypedDict(
@@ -1417,572 +2793,3114 @@ Eip712Domain = TypedDict(
 )
 
 
-def check_connected(f):
-    @functools.wraps(f)
-    def wrapper(*args, **kwargs):
-        if not args[0].connected:
-            raise NotConnectedError("Not connected to a chain")
-        return f(*args, **kwargs)
+def signed_message_prefix(name: str) -> Callable[[bytes], bytes]:
+    """
+    Returns:
+        Prefix scheme for `Account.sign` computing keccak256(b"\x19" + name + b" Signed Message:\n" + len(data) + data),
+        e.g. `signed_message_prefix("TRON")` for Tron signed messages.
+    """
+
+    def digest(data: bytes) -> bytes:
+        message = (
+            b"\x19"
+            + name.encode("utf-8")
+            + b" Signed Message:\n"
+            + str(len(data)).encode("utf-8")
+            + data
+        )
+        return keccak.new(data=message, digest_bits=256).digest()
+
+    return digest
+
+
+def adr36_prefix(signer: str) -> Callable[[bytes], bytes]:
+    """
+    Returns:
+        Prefix scheme for `Account.sign` computing sha256 of the Cosmos [ADR-36](https://docs.cosmos.network/main/build/architecture/adr-036-arbitrary-signature)
+        sign document wrapping the data, where `signer` is the bech32 address of the signer.
+    """
+
+    def digest(data: bytes) -> bytes:
+        sign_doc = {
+            "account_number": "0",
+            "chain_id": "",
+            "fee": {"amount": [], "gas": "0"},
+            "memo": "",
+            "msgs": [
+                {
+                    "type": "sign/MsgSignData",
+                    "value": {
+                        "data": base64.b64encode(data).decode("ascii"),
+                        "signer": signer,
+                    },
+                }
+            ],
+            "sequence": "0",
+        }
+        return hashlib.sha256(
+            json.dumps(sign_doc, sort_keys=True, separators=(",", ":")).encode("utf-8")
+        ).digest()
 
-    return wrapper
+    return digest
 
 
-_private_keys_index: Dict[Address, bytes] = {}
-_test_accounts_generated_count: int = 0
+class KeyCurve(StrEnum):
+    SECP256R1 = "secp256r1"
+    ED25519 = "ed25519"
 
 
-class Chain(ABC):
-    _connected: bool
-    _chain_interface: ChainInterfaceAbc
-    _accounts: List[Account]
-    _accounts_set: Set[Account]  # for faster lookup
-    _nonces: KeyedDefaultDict[Address, int]  # pyright: ignore reportGeneralTypeIssues
-    _default_call_account: Optional[Account]
-    _default_tx_account: Optional[Account]
-    _default_estimate_account: Optional[Account]
-    _default_access_list_account: Optional[Account]
-    _default_tx_type: int
-    _default_tx_confirmations: int
-    _deployed_libraries: DefaultDict[bytes, List[Library]]
-    _single_source_errors: Set[bytes]
-    _snapshots: Dict[str, Dict]
-    _blocks: ChainBlocks
-    _txs: ChainTransactions
-    _chain_id: int
-    _labels: Dict[Address, str]
-    _require_signed_txs: bool
-    _fork: Optional[str]
-    _forked_chain_id: Optional[int]
-    _debug_trace_call_supported: bool
-    _client_version: str
+# secp256r1 (NIST P-256) curve order
+_P256_N = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
 
-    tx_callback: Optional[Callable[[TransactionAbc], None]]
 
-    @abstractmethod
-    def _connect_setup(
-        self, min_gas_price: Optional[int], block_base_fee_per_gas: Optional[int]
-    ) -> None:
-        ...
+class _PrehashedSha256:
+    """
+    SHA-256 hash object wrapping an already computed digest, so that DSS signs and verifies the digest as is.
+    """
 
-    @abstractmethod
-    def _connect_finalize(self) -> None:
-        ...
+    digest_size = SHA256.digest_size
+    block_size = SHA256.block_size
+    oid = SHA256.oid
 
-    @abstractmethod
-    def _new_private_key(self, extra_entropy: bytes = b"") -> bytes:
-        ...
+    def __init__(self, data_hash: bytes):
+        self._digest = bytes(data_hash)
 
-    @abstractmethod
-    def snapshot(self) -> str:
-        ...
+    def digest(self) -> bytes:
+        return self._digest
 
-    @abstractmethod
-    def revert(self, snapshot_id: str) -> None:
-        ...
+    @staticmethod
+    def new(data: Optional[bytes] = None):
+        # used by HMAC when deriving RFC 6979 nonces
+        return SHA256.new(data)
+
+
+class CurveKey:
+    """
+    Key pair on a curve other than secp256k1, e.g. a passkey (secp256r1) verified with the RIP-7212 precompile.
+    Curve keys can only be used to produce signatures, they cannot send transactions.
+    """
+
+    _curve: KeyCurve
+    _private_key: bytes
+
+    def __init__(self, curve: KeyCurve, private_key: bytes) -> None:
+        curve = KeyCurve(curve)
+        if len(private_key) != 32:
+            raise ValueError("Private key must be 32 bytes long")
+        if curve == KeyCurve.SECP256R1 and not (
+            0 < int.from_bytes(private_key, "big") < _P256_N
+        ):
+            raise ValueError("Private key is out of range")
+        self._curve = curve
+        self._private_key = bytes(private_key)
 
-    @abstractmethod
-    def _build_transaction(
-        self,
-        request_type: RequestType,
-        params: TxParams,
-        arguments: Iterable,
-        abi: Optional[Dict],
-    ) -> TxParams:
-        ...
+    def __repr__(self) -> str:
+        return f"CurveKey({self._curve}, 0x{self.public_key.hex()})"
 
-    @abstractmethod
-    def _wait_for_transaction(
-        self, tx: TransactionAbc, confirmations: Optional[int]
-    ) -> None:
-        ...
+    def __eq__(self, other: object) -> bool:
+        if isinstance(other, CurveKey):
+            return (
+                self._curve == other._curve and self._private_key == other._private_key
+            )
+        return NotImplemented
 
-    @abstractmethod
-    def _confirm_transaction(self, tx: TxParams) -> None:
-        ...
+    def __hash__(self) -> int:
+        return hash((self._curve, self._private_key))
 
-    @property
-    @abstractmethod
-    def block_gas_limit(self) -> int:
-        ...
+    @classmethod
+    def new(
+        cls,
+        curve: KeyCurve,
+        extra_entropy: bytes = b"",
+        *,
+        chain: Optional[Chain] = None,
+    ) -> CurveKey:
+        """
+        Generate a new key with the random number generator of `chain` (the default chain if not given),
+        so that keys are reproducible with the random seed like `Account.new`.
+        """
+        if chain is None:
+            chain = detect_default_chain()
 
-    @block_gas_limit.setter
-    @abstractmethod
-    def block_gas_limit(self, value: int) -> None:
-        ...
+        while True:
+            private_key = chain._new_private_key(extra_entropy)
+            if curve != KeyCurve.SECP256R1 or (
+                0 < int.from_bytes(private_key, "big") < _P256_N
+            ):
+                return cls(curve, private_key)
+
+    @classmethod
+    def from_key(cls, curve: KeyCurve, private_key: Union[str, int, bytes]) -> CurveKey:
+        if isinstance(private_key, str):
+            private_key = bytes.fromhex(
+                private_key[2:] if private_key.startswith("0x") else private_key
+            )
+        elif isinstance(private_key, int):
+            private_key = private_key.to_bytes(32, "big")
+        return cls(curve, private_key)
 
     @property
-    @abstractmethod
-    def gas_price(self) -> Wei:
-        ...
+    def curve(self) -> KeyCurve:
+        return self._curve
 
-    @gas_price.setter
-    @abstractmethod
-    def gas_price(self, value: int) -> None:
-        ...
+    @property
+    def private_key(self) -> bytes:
+        """
+        Raw private key (ed25519 seed).
+        """
+        return self._private_key
 
     @property
-    @abstractmethod
-    def max_priority_fee_per_gas(self) -> Wei:
-        ...
+    def public_key(self) -> bytes:
+        """
+        Public key, i.e. 64-byte `x || y` for secp256r1 and 32-byte RFC 8032 encoding for ed25519.
+        """
+        if self._curve == KeyCurve.SECP256R1:
+            x, y = self.public_key_xy
+            return x.to_bytes(32, "big") + y.to_bytes(32, "big")
+        else:
+            q = ECC.construct(curve="Ed25519", seed=self._private_key).pointQ
+            return (int(q.y) | ((int(q.x) & 1) << 255)).to_bytes(32, "little")
 
-    @max_priority_fee_per_gas.setter
-    @abstractmethod
-    def max_priority_fee_per_gas(self, value: int) -> None:
-        ...
+    @property
+    def public_key_xy(self) -> Tuple[int, int]:
+        """
+        Coordinates of the secp256r1 public key, typically passed to contracts verifying signatures.
+        """
+        if self._curve != KeyCurve.SECP256R1:
+            raise NotImplementedError(
+                "Public key coordinates are only supported for secp256r1 keys"
+            )
+        q = self._p256_key().pointQ
+        return int(q.x), int(q.y)
 
-    @contextmanager  # pyright: ignore reportGeneralTypeIssues
-    @abstractmethod
-    def connect(
-        self,
-        uri: Optional[str] = None,
-        *,
-        accounts: Optional[int] = None,
-        chain_id: Optional[int] = None,
-        fork: Optional[str] = None,
-        hardfork: Optional[str] = None,
-        min_gas_price: Optional[Union[int, str]],
-        block_base_fee_per_gas: Optional[Union[int, str]],
-    ):
-        ...
+    def _p256_key(self) -> ECC.EccKey:
+        return ECC.construct(curve="P-256", d=int.from_bytes(self._private_key, "big"))
 
-    def __init__(self):
-        self._connected = False
+    def sign(self, data: bytes) -> bytes:
+        """
+        Sign `data`, i.e. sign_hash(sha256(data)) for secp256r1 and a pure RFC 8032 signature for ed25519.
+        """
+        if self._curve == KeyCurve.SECP256R1:
+            return self.sign_hash(hashlib.sha256(data).digest())
+        else:
+            key = ECC.construct(curve="Ed25519", seed=self._private_key)
+            return eddsa.new(key, "rfc8032").sign(data)
 
-    def _connect(
-        self,
-        uri: Optional[str] = None,
-        *,
-        accounts: Optional[int],
-        chain_id: Optional[int],
-        fork: Optional[str],
-        hardfork: Optional[str],
-        min_gas_price: Optional[Union[int, str]],
-        block_base_fee_per_gas: Optional[Union[int, str]],
-    ):
-        global _test_accounts_generated_count
+    def sign_hash(self, data_hash: bytes) -> bytes:
+        """
+        Sign 32-byte `data_hash` with secp256r1 ECDSA (RFC 6979 nonces).
+        Returns 64-byte `r || s` signature with `s` normalized to the lower half of the curve order.
+        """
+        if self._curve != KeyCurve.SECP256R1:
+            raise NotImplementedError(
+                "Signing hashes is only supported for secp256r1 keys"
+            )
+        if len(data_hash) != 32:
+            raise ValueError("Data hash must be 32 bytes long")
 
-        if self._connected:
-            raise AlreadyConnectedError("Already connected to a chain")
+        signature = DSS.new(self._p256_key(), "deterministic-rfc6979").sign(
+            _PrehashedSha256(data_hash)
+        )
+        s = int.from_bytes(signature[32:], "big")
+        if s > _P256_N // 2:
+            s = _P256_N - s
+        return signature[:32] + s.to_bytes(32, "big")
 
-        if isinstance(min_gas_price, str):
-            min_gas_price = Wei.from_str(min_gas_price)
-        if isinstance(block_base_fee_per_gas, str):
-            block_base_fee_per_gas = Wei.from_str(block_base_fee_per_gas)
+    def verify(self, data: bytes, signature: bytes) -> bool:
+        """
+        Verify that `signature` of `data` was produced by `sign` of this key.
+        """
+        if self._curve == KeyCurve.SECP256R1:
+            return self.verify_hash(hashlib.sha256(data).digest(), signature)
+        else:
+            key = eddsa.import_public_key(self.public_key)
+            try:
+                eddsa.new(key, "rfc8032").verify(data, signature)
+            except ValueError:
+                return False
+            return True
 
-        self._chain_interface = chain_interfaces_manager.get_or_create(
-            uri, accounts=accounts, chain_id=chain_id, fork=fork, hardfork=hardfork
-        )
+    def verify_hash(self, data_hash: bytes, signature: bytes) -> bool:
+        """
+        Verify secp256r1 `signature` of 32-byte `data_hash` the same way as the RIP-7212 precompile.
+        """
+        if self._curve != KeyCurve.SECP256R1:
+            raise NotImplementedError(
+                "Verifying hashes is only supported for secp256r1 keys"
+            )
+        if len(data_hash) != 32 or len(signature) != 64:
+            return False
 
+        key = self._p256_key().public_key()
         try:
-            self._connected = True
-            self._client_version = self._chain_interface.get_client_version()
+            DSS.new(key, "fips-186-3").verify(_PrehashedSha256(data_hash), signature)
+        except ValueError:
+            return False
+        return True
 
-            try:
-                self._chain_interface.debug_trace_call(
-                    {
-                        "type": 0,
-                    }
-                )
-                self._debug_trace_call_supported = True
-            except (JsonRpcError, HTTPError):
-                self._debug_trace_call_supported = False
+    def precompile_input(self, data_hash: bytes, signature: bytes) -> bytes:
+        """
+        Returns:
+            160-byte `hash || r || s || x || y` input of the RIP-7212 precompile at `P256_VERIFY_ADDRESS`.
+        """
+        if len(data_hash) != 32:
+            raise ValueError("Data hash must be 32 bytes long")
+        if len(signature) != 64:
+            raise ValueError("Signature must be 64 bytes long")
+        return bytes(data_hash) + bytes(signature) + self.public_key
 
-            self._chain_id = self._chain_interface.get_chain_id()
 
-            # determine the forked chain id (if any)
-            # determine the chain hardfork to set the default tx type
-            if isinstance(self._chain_interface, AnvilChainInterface):
-                info = self._chain_interface.node_info()
+def _prepare_eip712_dict(
+    message: Any, domain: Eip712Domain, client_signing: bool
+) -> Dict[str, Any]:
+    def _get_type(t: Type) -> str:
+        origin = get_origin(t)
 
-                if (
-                    "forkConfig" in info
-                    and "forkUrl" in info["forkConfig"]
-                    and info["forkConfig"]["forkUrl"] is not None
-                ):
-                    forked_chain_interface = ChainInterfaceAbc.connect(
-                        get_config(), info["forkConfig"]["forkUrl"]
-                    )
-                    try:
-                        self._forked_chain_id = forked_chain_interface.get_chain_id()
-                    finally:
-                        forked_chain_interface.close()
-                else:
-                    self._forked_chain_id = None
+        if isinstance(origin, type) and issubclass(origin, list):
+            if hasattr(origin, "length"):
+                return f"{_get_type(get_args(t)[0])}[{getattr(origin, 'length')}]"
+            else:
+                return f"{_get_type(get_args(t)[0])}[]"
+        elif isinstance(t, type) and issubclass(t, Integer):
+            if t.min == 0:
+                bits = math.ceil(math.log2(t.max + 1))
+                return f"uint{bits}"
+            else:
+                bits = math.ceil(math.log2(t.max - t.min + 1))
+                return f"int{bits}"
+        elif isinstance(t, type) and issubclass(t, FixedSizeBytes):
+            return f"bytes{t.length}"
+        elif t is int:
+            # fallback for int used directly
+            return "int256"
+        elif t is bytes or t is bytearray:
+            return "bytes"
+        elif t is str:
+            return "string"
+        elif issubclass(t, Enum):
+            return "uint8"
+        elif t is bool:
+            return "bool"
+        elif issubclass(t, (Account, Address)):
+            return "address"
+        elif dataclasses.is_dataclass(t):
+            return getattr(t, "original_name", t.__name__)
+        else:
+            raise ValueError(f"Unsupported type {t}")
 
-                hardfork = info["hardFork"]
-                if hardfork in {
-                    "FRONTIER",
-                    "HOMESTEAD",
-                    "TANGERINE",
-                    "SPURIOUS_DRAGON",
-                    "BYZANTIUM",
-                    "CONSTANTINOPLE",
-                    "PETERSBURG",
-                    "ISTANBUL",
-                    "MUIR_GLACIER",
-                }:
-                    self._default_tx_type = 0
-                elif hardfork == "BERLIN":
-                    self._default_tx_type = 1
-                else:
-                    self._default_tx_type = 2
-            elif isinstance(
-                self._chain_interface,
-                (GethLikeChainInterfaceAbc, HardhatChainInterface),
+    def _get_types(t: Type, types: Dict[str, List[Dict[str, str]]]) -> None:
+        if not dataclasses.is_dataclass(t):
+            return
+
+        name = getattr(t, "original_name", t.__name__)
+        if name in types:
+            return
+
+        fields = []
+        hints = _get_type_hints(
+            t,  # pyright: ignore reportGeneralTypeIssues
+            include_extras=True,
+        )
+        for f in dataclasses.fields(t):
+            assert f.name in hints
+            fields.append(
+                {
+                    "name": f.metadata.get("original_name", f.name),
+                    "type": _get_type(hints[f.name]),
+                }
+            )
+
+        types[name] = fields
+
+        for f in dataclasses.fields(t):
+            assert f.name in hints
+            field_type = hints[f.name]
+            while isinstance(get_origin(field_type), type) and issubclass(
+                get_origin(field_type), list
             ):
-                if isinstance(self._chain_interface, GethLikeChainInterfaceAbc):
-                    self._forked_chain_id = None
-                else:
-                    metadata = self._chain_interface.hardhat_metadata()
-                    if (
-                        "forkedNetwork" in metadata
-                        and "chainId" in metadata["forkedNetwork"]
-                    ):
-                        self._forked_chain_id = metadata["forkedNetwork"]["chainId"]
-                    else:
-                        self._forked_chain_id = None
+                field_type = get_args(field_type)[0]
+            if dataclasses.is_dataclass(field_type):
+                _get_types(field_type, types)
 
-                if self._chain_id in {56, 97}:
-                    # BSC clients do not fail on the calls below
-                    self._default_tx_type = 1
-                else:
-                    # TODO this is not the correct flow for Hermez:
-                    # EIP-1559 txs should be supported
-                    # access lists should not be supported
-                    # `type` field in txs should not be used
-                    # because Hermez does not implement eth_maxPriorityFeePerGas, fallback to legacy txs
-                    try:
-                        self._chain_interface.call(
-                            {
-                                "type": 2,
-                                "maxPriorityFeePerGas": 0,
-                            }
-                        )
-                        self._default_tx_type = 2
-                    except JsonRpcError:
-                        try:
-                            self._chain_interface.call(
-                                {
-                                    "type": 1,
-                                    "accessList": [],
-                                }
-                            )
-                            self._default_tx_type = 1
-                        except JsonRpcError:
-                            self._default_tx_type = 0
-            elif isinstance(self._chain_interface, GanacheChainInterface):
-                if fork is not None:
-                    forked_chain_interface = ChainInterfaceAbc.connect(
-                        get_config(), fork.split("@")[0]
-                    )
-                    try:
-                        self._forked_chain_id = forked_chain_interface.get_chain_id()
-                    finally:
-                        forked_chain_interface.close()
-                else:
-                    self._forked_chain_id = None
+    def _get_value(value: Any) -> Any:
+        if dataclasses.is_dataclass(value):
+            ret = {}
+            for f in dataclasses.fields(value):
+                name = f.metadata.get("original_name", f.name)
+                ret[name] = _get_value(getattr(value, f.name))
+            return ret
+        elif isinstance(value, (list, tuple)):
+            return [_get_value(v) for v in value]
+        elif isinstance(value, Account):
+            return str(value.address)
+        elif isinstance(value, Address):
+            return str(value)
+        elif isinstance(value, IntEnum):
+            return int(value)
+        elif isinstance(value, (bytes, bytearray)):
+            if client_signing:
+                return "0x" + value.hex()
+            else:
+                return value
+        else:
+            return value
+
+    types = {}
+    _get_types(type(message), types)
+
+    ret = {
+        "types": types,
+        "domain": {},
+        "primaryType": _get_type(type(message)),
+        "message": _get_value(message),
+    }
+
+    domain_type = []
+    if "name" in domain:
+        ret["domain"]["name"] = domain["name"]
+        domain_type.append({"name": "name", "type": "string"})
+    if "version" in domain:
+        ret["domain"]["version"] = domain["version"]
+        domain_type.append({"name": "version", "type": "string"})
+    if "chainId" in domain:
+        ret["domain"]["chainId"] = domain["chainId"]
+        domain_type.append({"name": "chainId", "type": "uint256"})
+    if "verifyingContract" in domain:
+        if isinstance(domain["verifyingContract"], Account):
+            ret["domain"]["verifyingContract"] = str(
+                domain["verifyingContract"].address
+            )
+        else:
+            ret["domain"]["verifyingContract"] = str(domain["verifyingContract"])
+        domain_type.append({"name": "verifyingContract", "type": "address"})
+    if "salt" in domain:
+        ret["domain"]["salt"] = "0x" + domain["salt"].hex()
+        domain_type.append({"name": "salt", "type": "bytes32"})
+
+    ret["types"]["EIP712Domain"] = domain_type
+
+    return ret
+
+
+def check_connected(f):
+    @functools.wraps(f)
+    def wrapper(*args, **kwargs):
+        if not args[0].connected:
+            raise NotConnectedError("Not connected to a chain")
+        return f(*args, **kwargs)
+
+    return wrapper
+
+
+class _PrivateKeysIndex:
+    """
+    Private keys are kept encrypted with a random per-process key and decrypted only for the time of signing,
+    unless the `general.encrypt_private_keys` config option is disabled for better performance.
+    A decrypted key is passed to the signing library as a `bytearray` that is zeroed right after signing.
+    The signing library (eth_account) still converts it into immutable `bytes` internally (`HexBytes`, eth_keys `PrivateKey`),
+    and these copies cannot be erased, so keys are not protected against reading the process memory.
+    The module-level index holds keys usable on all chains (registered with `per_chain=False` or through `Address`).
+    Every chain also holds its own index for keys imported through `Account` (`per_chain=True` by default),
+    so that such keys are not usable on another chain.
+    """
+
+    _encryption_key: bytes
+    _keys: Dict[Address, Union[bytes, Tuple[bytes, bytes, bytes]]]
+    _lock: threading.Lock
+
+    def __init__(self):
+        self._encryption_key = get_random_bytes(32)
+        self._keys = {}
+        self._lock = threading.Lock()
+
+    def __contains__(self, address: Address) -> bool:
+        return address in self._keys
+
+    def __setitem__(self, address: Address, private_key: bytes) -> None:
+        with self._lock:
+            if get_config().general.encrypt_private_keys:
+                cipher = AES.new(self._encryption_key, AES.MODE_GCM)
+                ciphertext, tag = cipher.encrypt_and_digest(private_key)
+                self._keys[address] = (cipher.nonce, ciphertext, tag)
+            else:
+                self._keys[address] = private_key
+
+    def _decrypt(self, address: Address) -> Optional[bytearray]:
+        with self._lock:
+            entry = self._keys.get(address, None)
+            encryption_key = self._encryption_key
+        if entry is None:
+            return None
+        elif isinstance(entry, bytes):
+            return bytearray(entry)
+
+        nonce, ciphertext, tag = entry
+        cipher = AES.new(encryption_key, AES.MODE_GCM, nonce=nonce)
+        buffer = bytearray(len(ciphertext))
+        cipher.decrypt_and_verify(ciphertext, tag, output=buffer)
+        return buffer
+
+    def get(
+        self, address: Address, default: Optional[bytes] = None
+    ) -> Optional[bytes]:
+        buffer = self._decrypt(address)
+        if buffer is None:
+            return default
+        try:
+            return bytes(buffer)
+        finally:
+            buffer[:] = bytes(len(buffer))
+
+    @contextmanager
+    def use(self, address: Address) -> Iterator[Optional[bytearray]]:
+        # the yielded buffer is zeroed on exit and must not be kept
+        buffer = self._decrypt(address)
+        try:
+            yield buffer
+        finally:
+            if buffer is not None:
+                buffer[:] = bytes(len(buffer))
+
+    def clear(self) -> None:
+        with self._lock:
+            self._keys.clear()
+            self._encryption_key = get_random_bytes(32)
+
+
+_private_keys_index = _PrivateKeysIndex()
+_test_accounts_generated_count: int = 0
+
+
+# all created Chain instances in creation order
+_chain_registry: List[weakref.ref[Chain]] = []
+
+# `chain` attribute holds the chain of the `ChainView` request being executed in the current thread
+_view_requests = threading.local()
+
+
+def get_chains() -> List[Chain]:
+    """
+    Return all existing `Chain` instances (both connected and disconnected) in the order they were created.
+    """
+    chains = []
+    for ref in _chain_registry:
+        chain = ref()
+        if chain is not None:
+            chains.append(chain)
+    return chains
+
+
+def shutdown() -> None:
+    """
+    Release resources held by Wake in the current process, allowing clean embedding in long-lived processes
+    (e.g. notebooks or language servers). All connected chains are disconnected, all launched chains and JSON-RPC
+    connections are closed and all private keys are forgotten. Exiting the `connect` context manager of an already
+    disconnected chain is a no-op.
+    """
+    for chain in get_chains():
+        if chain.connected:
+            chain._disconnect()
+        chain._private_keys_index.clear()
+    chain_interfaces_manager.close_all()
+    _private_keys_index.clear()
+
+
+@contextmanager
+def shutdown_on_exit():
+    """
+    Context manager calling [shutdown][wake.development.core.shutdown] on exit.
+    """
+    try:
+        yield
+    finally:
+        shutdown()
+
+
+_INT_TYPE_RE = re.compile(r"^(u?)int(\d*)$")
+_ARRAY_SUFFIX_RE = re.compile(r"\[(\d*)\]$")
+
+
+def _validate_abi_value(arg_abi: Dict[str, Any], value: Any, path: str) -> None:
+    t = arg_abi["type"]
+    internal_type = arg_abi.get("internalType", "")
+
+    array_match = _ARRAY_SUFFIX_RE.search(t)
+    if array_match is not None:
+        if not isinstance(value, (list, tuple)):
+            return
+        if array_match.group(1) and len(value) != int(array_match.group(1)):
+            raise ValueError(
+                f"Argument {path} must have {array_match.group(1)} elements, got {len(value)}"
+            )
+        item_abi = dict(arg_abi)
+        item_abi["type"] = t[: array_match.start()]
+        item_abi["internalType"] = _ARRAY_SUFFIX_RE.sub("", internal_type)
+        for i, item in enumerate(value):
+            _validate_abi_value(item_abi, item, f"{path}[{i}]")
+        return
+
+    if t == "tuple":
+        if not isinstance(value, (list, tuple)):
+            return
+        for i, (component, item) in enumerate(zip(arg_abi["components"], value)):
+            _validate_abi_value(
+                component, item, f"{path}.{component.get('name') or i}"
+            )
+        return
+
+    int_match = _INT_TYPE_RE.match(t)
+    if int_match is None or not isinstance(value, int) or isinstance(value, bool):
+        return
+
+    if (
+        isinstance(value, U256)
+        and t == "uint256"
+        and not internal_type.startswith("enum ")
+    ):
+        # range checked when the value was created
+        return
+
+    bits = int(int_match.group(2) or 256)
+    if int_match.group(1) == "u":
+        low, high = 0, 2**bits - 1
+    else:
+        low, high = -(2 ** (bits - 1)), 2 ** (bits - 1) - 1
+
+    if internal_type.startswith("enum "):
+        enum_name = internal_type[len("enum ") :]
+        # enums not generated in pytypes (or generated by an older version) are only checked against the ABI type
+        candidates = enums_index.get(enum_name, [])
+        if isinstance(value, IntEnum) and len(candidates) > 0:
+            if not any(
+                value.__class__ is _get_enum_class(module_name, attrs)
+                for module_name, attrs, _ in candidates
+            ):
+                raise ValueError(
+                    f"Argument {path} must be a member of enum {enum_name}, got {value!r}"
+                )
+        if len(candidates) > 0:
+            high = min(high, max(count for _, _, count in candidates) - 1)
+        kind = f"enum {enum_name}"
+    elif internal_type not in {"", t}:
+        # user-defined value type
+        kind = f"{internal_type} ({t})"
+    else:
+        kind = t
+
+    if not low <= value <= high:
+        raise ValueError(
+            f"Argument {path} of type {kind} out of bounds: {value} not in [{low}, {high}]"
+        )
+
+
+def _get_enum_class(module_name: str, attrs: Tuple[str, ...]) -> Optional[type]:
+    module = sys.modules.get(module_name)
+    if module is None:
+        # enum values of modules not imported yet cannot be passed as arguments
+        return None
+    obj = module
+    for attr in attrs:
+        obj = getattr(obj, attr, None)
+    return obj
+
+
+def _widen_enum_abi(arg_abi: Dict[str, Any]) -> Dict[str, Any]:
+    # enums are encoded as full words to allow out-of-range values
+    ret = dict(arg_abi)
+    if ret.get("internalType", "").startswith("enum "):
+        suffix = ret["type"][len("uint8") :]
+        ret["type"] = "uint256" + suffix
+    if "components" in ret:
+        ret["components"] = [_widen_enum_abi(c) for c in ret["components"]]
+    return ret
+
+
+# hardforks in activation order, named after revm SpecId
+_HARDFORKS = (
+    "FRONTIER",
+    "FRONTIER_THAWING",
+    "HOMESTEAD",
+    "DAO_FORK",
+    "TANGERINE",
+    "SPURIOUS_DRAGON",
+    "BYZANTIUM",
+    "CONSTANTINOPLE",
+    "PETERSBURG",
+    "ISTANBUL",
+    "MUIR_GLACIER",
+    "BERLIN",
+    "LONDON",
+    "ARROW_GLACIER",
+    "GRAY_GLACIER",
+    "MERGE",
+    "SHANGHAI",
+    "CANCUN",
+    "PRAGUE",
+    "OSAKA",
+)
+
+_HARDFORK_ALIASES = {
+    "CHAINSTART": "FRONTIER",
+    "DAO": "DAO_FORK",
+    "TANGERINE_WHISTLE": "TANGERINE",
+    "PARIS": "MERGE",
+}
+
+# feature => hardfork introducing the feature
+_HARDFORK_FEATURES = {
+    "empty_account_cleanup": "SPURIOUS_DRAGON",
+    "create2": "CONSTANTINOPLE",
+    "bitwise_shifting": "CONSTANTINOPLE",
+    "chain_id": "ISTANBUL",
+    "access_lists": "BERLIN",
+    "eip1559": "LONDON",
+    "basefee": "LONDON",
+    "prevrandao": "MERGE",
+    "push0": "SHANGHAI",
+    "initcode_size_limit": "SHANGHAI",
+    "transient_storage": "CANCUN",
+    "mcopy": "CANCUN",
+    "blobs": "CANCUN",
+    "selfdestruct_only_in_same_tx": "CANCUN",
+    "set_code": "PRAGUE",
+    "bls12_381": "PRAGUE",
+}
+
+
+# opcode => (name, hardfork introducing the opcode)
+_OPCODE_HARDFORKS = {
+    0x1B: ("SHL", "CONSTANTINOPLE"),
+    0x1C: ("SHR", "CONSTANTINOPLE"),
+    0x1D: ("SAR", "CONSTANTINOPLE"),
+    0x3D: ("RETURNDATASIZE", "BYZANTIUM"),
+    0x3E: ("RETURNDATACOPY", "BYZANTIUM"),
+    0x3F: ("EXTCODEHASH", "CONSTANTINOPLE"),
+    0x46: ("CHAINID", "ISTANBUL"),
+    0x47: ("SELFBALANCE", "ISTANBUL"),
+    0x48: ("BASEFEE", "LONDON"),
+    0x49: ("BLOBHASH", "CANCUN"),
+    0x4A: ("BLOBBASEFEE", "CANCUN"),
+    0x5C: ("TLOAD", "CANCUN"),
+    0x5D: ("TSTORE", "CANCUN"),
+    0x5E: ("MCOPY", "CANCUN"),
+    0x5F: ("PUSH0", "SHANGHAI"),
+    0xF4: ("DELEGATECALL", "HOMESTEAD"),
+    0xF5: ("CREATE2", "CONSTANTINOPLE"),
+    0xFA: ("STATICCALL", "BYZANTIUM"),
+    0xFD: ("REVERT", "BYZANTIUM"),
+}
+
+# start of CBOR-encoded metadata appended by solc to (nested) bytecode
+_CBOR_METADATA_PREFIX_RE = re.compile(rb"[\xa1-\xa3](?:\x64ipfs|\x65bzzr[01]|\x64solc)")
+
+
+def _find_unsupported_opcodes(
+    code: Union[bytes, bytearray], hardfork: str
+) -> List[str]:
+    active = _HARDFORKS.index(hardfork)
+    unsupported: Dict[str, None] = {}
+    i = 0
+    while i < len(code):
+        op = code[i]
+        if _CBOR_METADATA_PREFIX_RE.match(code, i):
+            # metadata ends with its 2-byte big-endian length
+            for length in range(8, min(256, len(code) - i - 1)):
+                if int.from_bytes(code[i + length : i + length + 2], "big") == length:
+                    i += length + 2
+                    break
+            else:
+                i += 1
+            continue
+
+        if op in _OPCODE_HARDFORKS:
+            name, introduced = _OPCODE_HARDFORKS[op]
+            if _HARDFORKS.index(introduced) > active:
+                unsupported[name] = None
+        elif 0x60 <= op <= 0x7F:
+            # skip PUSH1-PUSH32 data
+            i += op - 0x5F
+        i += 1
+    return list(unsupported)
+
+
+def _build_patch_shim(fallback: Address, patches: Dict[bytes, Address]) -> bytes:
+    # dispatcher delegatecalling patched selectors to their implementations
+    # and everything else to `fallback` (a copy of the original runtime code)
+    call_offset = 37 + 37 * len(patches)
+    call = call_offset.to_bytes(2, "big")
+
+    # copy calldata to memory, load the selector
+    code = bytearray.fromhex("36600060003760003560e01c")
+    for i, selector in enumerate(patches):
+        target = (37 + 11 * len(patches) + 26 * i).to_bytes(2, "big")
+        # DUP1 PUSH4 selector EQ PUSH2 target JUMPI
+        code += b"\x80\x63" + selector + b"\x14\x61" + target + b"\x57"
+    # PUSH20 fallback PUSH2 call JUMP
+    code += b"\x73" + bytes(fallback) + b"\x61" + call + b"\x56"
+    for impl in patches.values():
+        # JUMPDEST PUSH20 impl PUSH2 call JUMP
+        code += b"\x5b\x73" + bytes(impl) + b"\x61" + call + b"\x56"
+    assert len(code) == call_offset
+
+    # JUMPDEST, DELEGATECALL(GAS, target, 0, CALLDATASIZE, 0, 0), copy return data
+    code += bytes.fromhex("5b60006000366000845af43d600060003e")
+    # PUSH2 ok JUMPI, REVERT(0, RETURNDATASIZE), ok: JUMPDEST RETURN(0, RETURNDATASIZE)
+    code += b"\x61" + (call_offset + 25).to_bytes(2, "big") + b"\x57"
+    code += bytes.fromhex("3d6000fd5b3d6000f3")
+    return bytes(code)
+
+
+def _get_log_frames(debug_trace: Dict[str, Any]) -> List[Optional[Tuple[int, ...]]]:
+    # call paths (indexes into `subtraces` from the top-level call) of logs emitted by
+    # call frames that did not revert, in the order of the logs in the receipt
+    struct_logs = debug_trace["structLogs"]
+    path: List[int] = []
+    calls_made = [0]
+    frame_logs: List[List[Optional[Tuple[int, ...]]]] = [[]]
+
+    for i, log in enumerate(struct_logs):
+        if i > 0 and log["depth"] < struct_logs[i - 1]["depth"] and len(path) > 0:
+            # the call opcode pushed a zero on failure
+            logs = frame_logs.pop()
+            calls_made.pop()
+            path.pop()
+            if int(log["stack"][-1], 16) != 0:
+                frame_logs[-1].extend(logs)
+
+        op = log["op"]
+        if op.startswith("LOG"):
+            frame_logs[-1].append(tuple(path))
+        elif op in {
+            "CALL",
+            "CALLCODE",
+            "DELEGATECALL",
+            "STATICCALL",
+            "CREATE",
+            "CREATE2",
+        }:
+            index = calls_made[-1]
+            calls_made[-1] += 1
+            if (
+                i + 1 < len(struct_logs)
+                and struct_logs[i + 1]["depth"] == log["depth"] + 1
+            ):
+                path.append(index)
+                calls_made.append(0)
+                frame_logs.append([])
+    return frame_logs[0]
+
+
+def _get_constructor_storage(debug_trace: Dict[str, Any]) -> Dict[int, int]:
+    # storage written by the top-level constructor (including DELEGATECALL and CALLCODE frames executed
+    # in its storage context), writes of frames that did not return successfully are discarded
+    struct_logs = debug_trace["structLogs"]
+    # `None` for frames executed in the storage context of another account
+    frame_writes: List[Optional[Dict[int, int]]] = [{}]
+
+    for i, log in enumerate(struct_logs):
+        if (
+            i > 0
+            and log["depth"] < struct_logs[i - 1]["depth"]
+            and len(frame_writes) > 1
+        ):
+            # the call opcode pushed a zero on failure
+            writes = frame_writes.pop()
+            if (
+                writes is not None
+                and frame_writes[-1] is not None
+                and int(log["stack"][-1], 16) != 0
+            ):
+                frame_writes[-1].update(writes)
+
+        op = log["op"]
+        if op == "SSTORE":
+            writes = frame_writes[-1]
+            if writes is not None:
+                writes[int(log["stack"][-1], 16)] = int(log["stack"][-2], 16)
+        elif op in {
+            "CALL",
+            "CALLCODE",
+            "DELEGATECALL",
+            "STATICCALL",
+            "CREATE",
+            "CREATE2",
+        }:
+            if (
+                i + 1 < len(struct_logs)
+                and struct_logs[i + 1]["depth"] == log["depth"] + 1
+            ):
+                frame_writes.append(
+                    {}
+                    if op in {"CALLCODE", "DELEGATECALL"}
+                    and frame_writes[-1] is not None
+                    else None
+                )
+    assert frame_writes[0] is not None
+    return frame_writes[0]
+
+
+def _normalize_hardfork(name: str) -> str:
+    # camelCase (hardhat) and lowercase (anvil) names to revm SpecId names
+    name = re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", name).upper()
+    return _HARDFORK_ALIASES.get(name, name)
+
+
+class Chain(ABC):
+    # index of the chain in the global chain registry, see `get_chains`
+    _registry_index: int
+    _connected: bool
+    _chain_interface: ChainInterfaceAbc
+    _accounts: List[Account]
+    _accounts_set: Set[Account]  # for faster lookup
+    _journal: Journal
+    _nonces: JournaledKeyedDefaultDict[Address, int]  # pyright: ignore reportGeneralTypeIssues
+    _default_call_account: Optional[Account]
+    _default_tx_account: Optional[Account]
+    _default_estimate_account: Optional[Account]
+    _default_access_list_account: Optional[Account]
+    _default_tx_type: int
+    _default_tx_confirmations: int
+    _deployed_libraries: DefaultDict[bytes, List[Library]]
+    _single_source_errors: Set[bytes]
+    _snapshots: Dict[str, Dict]
+    _blocks: ChainBlocks
+    _txs: ChainTransactions
+    _chain_id: int
+    _labels: Dict[Address, str]
+    _fresh_addresses: JournaledList[Account]  # pyright: ignore reportGeneralTypeIssues
+    _require_signed_txs: bool
+    _fork: Optional[str]
+    _forked_chain_id: Optional[int]
+    _fork_info: Optional[ForkInfo]
+    _fork_info_fetched: bool
+    _first_local_block: int
+    # address => (keccak256 of runtime code, fqn) overriding the contract found by metadata while the code is unchanged,
+    # set by `etch` and `patch_function`
+    _fqn_overrides: JournaledDict[Address, Tuple[bytes, str]]  # pyright: ignore reportGeneralTypeIssues
+    # keccak256 of patch shim code => (address of original code copy, selector => implementation)
+    _patched_functions: Dict[bytes, Tuple[Address, Dict[bytes, Address]]]
+    # (topic0, origin address) filters of events dropped from `tx.events`, `None` matching any
+    _ignored_events: List[Tuple[Optional[bytes], Optional[Address]]]
+    _addresses: Optional[AddressBook]
+    _offline: bool
+    # gas estimate used to compute the gas limit of the last built transaction
+    _last_gas_estimate: Optional[int]
+    _debug_trace_call_supported: bool
+    _client_version: str
+    _hardfork: Optional[str]
+    _revert_count: int
+    _strict_nonces: bool
+    _validate_arguments: bool
+    _evm_version_check: bool
+    _zero_copy_returns: bool
+    _exact_gas_estimates: bool
+    # automine flag of the client, fetched on first use
+    _automine: Optional[bool]
+    _event_frames: bool
+    _precompile_gas_overrides: Dict[Address, Union[int, Callable[[bytes], int]]]
+    _allow_unprotected_txs: bool
+    _detect_uninitialized_reads: bool
+    _token_guard: Optional[TokenGuard]
+    # (address, slot) pairs written by transactions in this chain session
+    _written_slots: JournaledSet[Tuple[Address, int]]  # pyright: ignore reportGeneralTypeIssues
+    # addresses of linked libraries found to have code in the pending block
+    _verified_libraries: JournaledSet[Address]  # pyright: ignore reportGeneralTypeIssues
+    _ens_cache: Dict[str, Address]
+    # (account, latest block hash, revert count), last returned slot and storage of the last truncated `Account.storage_dump`
+    _storage_dump_cache: Optional[
+        Tuple[Tuple[Address, str, int], int, Dict[int, bytes]]
+    ]
+    _private_keys_index: _PrivateKeysIndex
+    # address or contract metadata => selector => ABI item
+    _registered_event_abis: Dict[Union[Address, bytes], Dict[bytes, Dict]]
+
+    tx_callback: Optional[Callable[[TransactionAbc], None]]
+    _batched_tx_callback: Optional[Callable[[List[TransactionAbc]], None]]
+    _tx_batch: List[TransactionAbc]
+    _tx_batch_size: int
+    _tx_batch_interval: Optional[float]
+    _tx_batch_start: float
+    _block_mined_hooks: List[Callable[[Block], None]]
+    _snapshot_hooks: List[Callable[[Snapshot], None]]
+    _revert_hooks: List[Callable[[str], None]]
+    _last_hooked_block: Optional[int]
+
+    @abstractmethod
+    def _connect_setup(
+        self, min_gas_price: Optional[int], block_base_fee_per_gas: Optional[int]
+    ) -> None:
+        ...
+
+    @abstractmethod
+    def _connect_finalize(self) -> None:
+        ...
+
+    @abstractmethod
+    def _new_private_key(self, extra_entropy: bytes = b"") -> bytes:
+        ...
+
+    @abstractmethod
+    def snapshot(self) -> Snapshot:
+        ...
+
+    @abstractmethod
+    def revert(self, snapshot_id: str) -> None:
+        ...
+
+    def _new_snapshot(self, snapshot_id: str) -> Snapshot:
+        return Snapshot(snapshot_id, self)
+
+    def _resolve_snapshot_block(self, snapshot: Dict) -> None:
+        # called right after reverting, when the chain head is the block the snapshot was taken at
+        snapshot_object = snapshot["object"]()
+        if snapshot_object is None:
+            return
+        head = self._blocks._validate_head()
+        if head is not None:
+            snapshot_object._block_number = head.number
+            snapshot_object._timestamp = head.timestamp
+
+    def _on_journal_evict(self, checkpoints: List[int]) -> None:
+        evicted = [
+            snapshot_id
+            for snapshot_id, snapshot in self._snapshots.items()
+            if snapshot["journal"] in checkpoints
+        ]
+        for snapshot_id in evicted:
+            del self._snapshots[snapshot_id]
+
+        warnings.warn(
+            f"The journal exceeded chain.journal_limit, {len(checkpoints)} oldest checkpoint(s) were evicted"
+            + (f" including snapshots {', '.join(evicted)}" if len(evicted) > 0 else "")
+            + "; they can no longer be reverted",
+            RuntimeWarning,
+            stacklevel=2,
+        )
+
+    @check_connected
+    def release(self, snapshot_id: str) -> None:
+        """
+        Release a snapshot that will not be reverted anymore, so that the journal entries recorded for it can be dropped.
+        Reverting a released snapshot raises `RevertToSnapshotFailedError`.
+        """
+        self._check_snapshot(snapshot_id)
+        snapshot = self._snapshots.pop(snapshot_id)
+        self._journal.release(snapshot["journal"])
+
+    def _check_snapshot(self, snapshot_id: str) -> None:
+        if snapshot_id not in self._snapshots:
+            raise RevertToSnapshotFailedError(
+                f"Snapshot {str.__str__(snapshot_id)} was already reverted, invalidated by reverting "
+                "to an earlier snapshot or by Chain.load_state, released by Chain.release or evicted because the journal exceeded chain.journal_limit"
+            )
+
+    @abstractmethod
+    def _build_transaction(
+        self,
+        request_type: RequestType,
+        params: TxParams,
+        arguments: Iterable,
+        abi: Optional[Dict],
+    ) -> TxParams:
+        ...
+
+    @abstractmethod
+    def _wait_for_transaction(
+        self, tx: TransactionAbc, confirmations: Optional[int]
+    ) -> None:
+        ...
+
+    @abstractmethod
+    def _confirm_transaction(self, tx: TxParams) -> None:
+        ...
+
+    @property
+    @abstractmethod
+    def block_gas_limit(self) -> int:
+        ...
+
+    @block_gas_limit.setter
+    @abstractmethod
+    def block_gas_limit(self, value: int) -> None:
+        ...
+
+    @property
+    @abstractmethod
+    def gas_price(self) -> Wei:
+        ...
+
+    @gas_price.setter
+    @abstractmethod
+    def gas_price(self, value: int) -> None:
+        ...
+
+    @property
+    @abstractmethod
+    def max_priority_fee_per_gas(self) -> Wei:
+        ...
+
+    @max_priority_fee_per_gas.setter
+    @abstractmethod
+    def max_priority_fee_per_gas(self, value: int) -> None:
+        ...
+
+    @contextmanager  # pyright: ignore reportGeneralTypeIssues
+    @abstractmethod
+    def connect(
+        self,
+        uri: Optional[str] = None,
+        *,
+        accounts: Optional[int] = None,
+        chain_id: Optional[int] = None,
+        fork: Optional[str] = None,
+        hardfork: Optional[str] = None,
+        min_gas_price: Optional[Union[int, str]],
+        block_base_fee_per_gas: Optional[Union[int, str]],
+    ):
+        ...
+
+    def __init__(self):
+        self._connected = False
+        self._private_keys_index = _PrivateKeysIndex()
+        self._batched_tx_callback = None
+        self._tx_batch = []
+        self._registered_event_abis = {}
+        self._registry_index = len(_chain_registry)
+        _chain_registry.append(weakref.ref(self))
+
+    def _describe(self) -> str:
+        if self._connected:
+            return f"chain #{self._registry_index} (chain ID {self._chain_id})"
+        return f"chain #{self._registry_index} (not connected)"
+
+    def _connect(
+        self,
+        uri: Optional[str] = None,
+        *,
+        accounts: Optional[int],
+        chain_id: Optional[int],
+        fork: Optional[str],
+        hardfork: Optional[str],
+        min_gas_price: Optional[Union[int, str]],
+        block_base_fee_per_gas: Optional[Union[int, str]],
+        offline: bool = False,
+        genesis_timestamp: Optional[int] = None,
+    ):
+        global _test_accounts_generated_count
+
+        if self._connected:
+            raise AlreadyConnectedError("Already connected to a chain")
+
+        if isinstance(min_gas_price, str):
+            min_gas_price = Wei.from_str(min_gas_price)
+        if isinstance(block_base_fee_per_gas, str):
+            block_base_fee_per_gas = Wei.from_str(block_base_fee_per_gas)
+
+        self._chain_interface = chain_interfaces_manager.get_or_create(
+            uri,
+            accounts=accounts,
+            chain_id=chain_id,
+            fork=fork,
+            hardfork=hardfork,
+            genesis_timestamp=genesis_timestamp,
+        )
+
+        try:
+            self._connected = True
+            self._client_version = self._chain_interface.get_client_version()
+
+            try:
+                self._chain_interface.debug_trace_call(
+                    {
+                        "type": 0,
+                    }
+                )
+                self._debug_trace_call_supported = True
+            except (JsonRpcError, HTTPError):
+                self._debug_trace_call_supported = False
+
+            self._chain_id = self._chain_interface.get_chain_id()
+
+            # determine the forked chain id (if any)
+            # determine the chain hardfork to set the default tx type
+            if isinstance(self._chain_interface, AnvilChainInterface):
+                info = self._chain_interface.node_info()
+
+                if (
+                    "forkConfig" in info
+                    and "forkUrl" in info["forkConfig"]
+                    and info["forkConfig"]["forkUrl"] is not None
+                ):
+                    forked_chain_interface = ChainInterfaceAbc.connect(
+                        get_config(), info["forkConfig"]["forkUrl"]
+                    )
+                    try:
+                        self._forked_chain_id = forked_chain_interface.get_chain_id()
+                    finally:
+                        forked_chain_interface.close()
+                else:
+                    self._forked_chain_id = None
+
+                hardfork = _normalize_hardfork(info["hardFork"])
+                self._hardfork = hardfork
+                if hardfork in {
+                    "FRONTIER",
+                    "HOMESTEAD",
+                    "TANGERINE",
+                    "SPURIOUS_DRAGON",
+                    "BYZANTIUM",
+                    "CONSTANTINOPLE",
+                    "PETERSBURG",
+                    "ISTANBUL",
+                    "MUIR_GLACIER",
+                }:
+                    self._default_tx_type = 0
+                elif hardfork == "BERLIN":
+                    self._default_tx_type = 1
+                else:
+                    self._default_tx_type = 2
+            elif isinstance(
+                self._chain_interface,
+                (GethLikeChainInterfaceAbc, HardhatChainInterface),
+            ):
+                if isinstance(self._chain_interface, GethLikeChainInterfaceAbc):
+                    self._forked_chain_id = None
+                    self._hardfork = None
+                else:
+                    metadata = self._chain_interface.hardhat_metadata()
+                    self._hardfork = _normalize_hardfork(metadata["hardfork"])
+                    if (
+                        "forkedNetwork" in metadata
+                        and "chainId" in metadata["forkedNetwork"]
+                    ):
+                        self._forked_chain_id = metadata["forkedNetwork"]["chainId"]
+                    else:
+                        self._forked_chain_id = None
+
+                if self._chain_id in {56, 97}:
+                    # BSC clients do not fail on the calls below
+                    self._default_tx_type = 1
+                else:
+                    # TODO this is not the correct flow for Hermez:
+                    # EIP-1559 txs should be supported
+                    # access lists should not be supported
+                    # `type` field in txs should not be used
+                    # because Hermez does not implement eth_maxPriorityFeePerGas, fallback to legacy txs
+                    try:
+                        self._chain_interface.call(
+                            {
+                                "type": 2,
+                                "maxPriorityFeePerGas": 0,
+                            }
+                        )
+                        self._default_tx_type = 2
+                    except JsonRpcError:
+                        try:
+                            self._chain_interface.call(
+                                {
+                                    "type": 1,
+                                    "accessList": [],
+                                }
+                            )
+                            self._default_tx_type = 1
+                        except JsonRpcError:
+                            self._default_tx_type = 0
+            elif isinstance(self._chain_interface, GanacheChainInterface):
+                if fork is not None:
+                    forked_chain_interface = ChainInterfaceAbc.connect(
+                        get_config(), fork.split("@")[0]
+                    )
+                    try:
+                        self._forked_chain_id = forked_chain_interface.get_chain_id()
+                    finally:
+                        forked_chain_interface.close()
+                else:
+                    self._forked_chain_id = None
+
+                self._hardfork = None
+                self._default_tx_type = 0
+            else:
+                raise NotImplementedError(
+                    f"Unknown chain interface type: {type(self._chain_interface)}"
+                )
+
+            if block_base_fee_per_gas is not None and not isinstance(
+                self._chain_interface, GanacheChainInterface
+            ):
+                try:
+                    self._chain_interface.set_next_block_base_fee_per_gas(
+                        block_base_fee_per_gas
+                    )
+                except JsonRpcError:
+                    pass
+
+            from .transactions import ChainTransactions
+
+            self._txs = ChainTransactions(self)
+            self._txs.history_limit = get_config().general.tx_history_limit
+
+            self._accounts = [
+                Account(acc, self) for acc in self._chain_interface.get_accounts()
+            ]
+
+            if len(self._accounts) > _test_accounts_generated_count:
+                # generate private keys for accounts without private key
+                for i in range(_test_accounts_generated_count, len(self._accounts)):
+                    Address.from_mnemonic(
+                        "test test test test test test test test test test test junk",
+                        path=f"m/44'/60'/0'/0/{i}",
+                    )
+                _test_accounts_generated_count = len(self._accounts)
+
+            self._accounts_set = set(self._accounts)
+            self._journal = Journal(self._on_journal_evict)
+            self._nonces = JournaledKeyedDefaultDict(
+                self._journal,
+                lambda addr: self._chain_interface.get_transaction_count(  # pyright: ignore reportGeneralTypeIssues
+                    str(addr)
+                ),
+            )
+            self._snapshots = {}
+            self._revert_count = 0
+            self._deployed_libraries = defaultdict(list)
+
+            if len(self._accounts) > 0:
+                self.set_default_accounts(self._accounts[0])
+            else:
+                self.set_default_accounts(None)
+            self._default_tx_confirmations = 1
+            self._blocks = ChainBlocks(self)
+            self._blocks.history_limit = get_config().general.block_history_limit
+            self._labels = {}
+            self._fresh_addresses = JournaledList(self._journal)
+            self._fork = fork
+            self._fork_info = None
+            self._fork_info_fetched = False
+            # blocks up to the head at connection time were not mined through this chain instance
+            self._first_local_block = self._chain_interface.get_block_number() + 1
+            self._fqn_overrides = JournaledDict(self._journal)
+            self._patched_functions = {}
+            self._ignored_events = []
+            self._addresses = None
+            self._offline = offline
+            self._strict_nonces = False
+            self._validate_arguments = True
+            self._evm_version_check = True
+            self._zero_copy_returns = False
+            self._exact_gas_estimates = False
+            self._automine = None
+            self._event_frames = False
+            self._precompile_gas_overrides = {}
+            self._allow_unprotected_txs = False
+            self._detect_uninitialized_reads = False
+            self._token_guard = None
+            self._written_slots = JournaledSet(self._journal)
+            self._verified_libraries = JournaledSet(self._journal)
+            self._ens_cache = {}
+            self._storage_dump_cache = None
+            self._last_gas_estimate = None
+
+            self._single_source_errors = {
+                selector
+                for selector, sources in errors.items()
+                if len({source for fqn, source in sources.items()}) == 1
+            }
+
+            self.tx_callback = None
+            self._batched_tx_callback = None
+            self._tx_batch = []
+            self._tx_batch_size = 0
+            self._tx_batch_interval = None
+            self._tx_batch_start = 0.0
+            self._block_mined_hooks = []
+            self._snapshot_hooks = []
+            self._revert_hooks = []
+            self._last_hooked_block = None
+
+            self._connect_setup(min_gas_price, block_base_fee_per_gas)
+
+            yield self
+        except Exception as e:
+            if not isinstance(e, BdbQuit):
+                exception_handler = get_exception_handler()
+                if exception_handler is not None:
+                    exception_handler(*sys.exc_info())
+                raise
+        finally:
+            if self._connected:
+                try:
+                    self.flush_tx_callback()
+                finally:
+                    self._disconnect()
+
+    def _disconnect(self) -> None:
+        self._connect_finalize()
+        self._connected = False
+        # snapshots cannot be reverted after the session ends
+        self._snapshots = {}
+        self._journal.clear()
+
+    @property
+    def connected(self) -> bool:
+        return self._connected
+
+    @property
+    def client_version(self) -> str:
+        return self._client_version
+
+    @property
+    @check_connected
+    def addresses(self) -> AddressBook:
+        """
+        Address book with named addresses configured in the `addresses` config namespace for the chain ID
+        of the chain (or of the forked chain).
+        """
+        if self._addresses is None:
+            chain_id = (
+                self._forked_chain_id
+                if self._forked_chain_id is not None
+                else self._chain_id
+            )
+            self._addresses = AddressBook(
+                self, get_config().addresses.get(chain_id, {})
+            )
+        return self._addresses
+
+    @property
+    @check_connected
+    def fork_info(self) -> Optional[ForkInfo]:
+        """
+        Metadata of the forked network or `None` if the chain is not a fork. Fetched once per connection.
+        """
+        if self._fork_info_fetched:
+            return self._fork_info
+
+        url = None
+        block_number = None
+        block_hash = None
+
+        if isinstance(self._chain_interface, AnvilChainInterface):
+            fork_config = self._chain_interface.node_info().get("forkConfig", {})
+            url = fork_config.get("forkUrl")
+            block_number = fork_config.get("forkBlockNumber")
+        elif isinstance(self._chain_interface, HardhatChainInterface):
+            forked_network = self._chain_interface.hardhat_metadata().get(
+                "forkedNetwork", {}
+            )
+            if len(forked_network) > 0:
+                block_number = forked_network.get("forkBlockNumber")
+                block_hash = forked_network.get("forkBlockHash")
+        elif self._fork is not None:
+            url, _, block = self._fork.partition("@")
+            if block:
+                block_number = int(block)
+
+        if url is None and self._forked_chain_id is None:
+            self._fork_info = None
+        else:
+            if block_hash is None and block_number is not None:
+                block_info = self._chain_interface.get_block(block_number)
+                if block_info is not None:
+                    block_hash = block_info["hash"]
+
+            client_version = None
+            if url is not None:
+                try:
+                    forked_chain_interface = ChainInterfaceAbc.connect(
+                        get_config(), url
+                    )
+                    try:
+                        client_version = forked_chain_interface.get_client_version()
+                    finally:
+                        forked_chain_interface.close()
+                except Exception:
+                    pass
+
+            self._fork_info = ForkInfo(
+                _redact_url(url) if url is not None else None,
+                block_number,
+                block_hash,
+                self._forked_chain_id,
+                client_version,
+            )
+
+        self._fork_info_fetched = True
+        return self._fork_info
+
+    @property
+    @check_connected
+    def hardfork(self) -> Optional[str]:
+        """
+        Name of the active hardfork (revm SpecId name, e.g. `CANCUN`) or `None` if the client does not report it.
+        """
+        return self._hardfork
+
+    @check_connected
+    def supports(self, feature: str) -> bool:
+        """
+        Return whether the active hardfork supports `feature`, e.g. `transient_storage`, `push0` or `blobs`.
+        """
+        if feature not in _HARDFORK_FEATURES:
+            raise ValueError(
+                f"Unknown feature {feature!r}, expected one of: {', '.join(_HARDFORK_FEATURES)}"
+            )
+        if self._hardfork is None:
+            raise NotImplementedError(
+                f"Hardfork of {self._describe()} is unknown, cannot check feature support"
+            )
+
+        # hardforks unknown to Wake are newer than all known hardforks
+        active = (
+            _HARDFORKS.index(self._hardfork)
+            if self._hardfork in _HARDFORKS
+            else len(_HARDFORKS)
+        )
+        return active >= _HARDFORKS.index(_HARDFORK_FEATURES[feature])
+
+    def _initcode_size_limit_enforced(self) -> bool:
+        if self._hardfork is None or not self.supports("initcode_size_limit"):
+            return False
+        if isinstance(self._chain_interface, HardhatChainInterface):
+            # `allowUnlimitedContractSize` is only known to the Hardhat config
+            return False
+        if isinstance(
+            self._chain_interface, (AnvilChainInterface, GanacheChainInterface)
+        ):
+            # the limit may have been lifted by command line arguments, only known for chains launched by Wake
+            process = self._chain_interface._process
+            return process is not None and not any(
+                str(arg).startswith(_CODE_SIZE_LIMIT_ARGS) for arg in process.args
+            )
+        return True
+
+    def _check_initcode_size(
+        self, contract_type: Type, initcode: Union[bytes, bytearray]
+    ) -> None:
+        # initcode includes ABI-encoded constructor arguments
+        if len(initcode) > MAX_INITCODE_SIZE and self._initcode_size_limit_enforced():
+            raise InitcodeSizeExceeded(
+                getattr(contract_type, "__name__", "contract"),
+                len(initcode),
+                MAX_INITCODE_SIZE,
+            )
+
+    def _check_evm_version(
+        self, contract_type: Type, creation_code: Union[bytes, bytearray]
+    ) -> None:
+        evm_version = getattr(contract_type, "_evm_version", None)
+        if (
+            not self._evm_version_check
+            or evm_version is None
+            or self._hardfork not in _HARDFORKS
+        ):
+            return
+        required = _normalize_hardfork(evm_version)
+        if required not in _HARDFORKS or _HARDFORKS.index(
+            required
+        ) <= _HARDFORKS.index(self._hardfork):
+            return
+
+        opcodes = _find_unsupported_opcodes(creation_code, self._hardfork)
+        if len(opcodes) == 0:
+            # compilers default to the latest EVM version, most contracts do not need the new opcodes
+            warnings.warn(
+                f"{contract_type.__name__} was compiled for EVM version {evm_version} but the chain runs {self._hardfork}",
+                RuntimeWarning,
+                stacklevel=3,
+            )
+            return
+
+        raise IncompatibleEvmVersion(
+            contract_type.__name__, evm_version, self._hardfork, opcodes
+        )
+
+    @property
+    @check_connected
+    def chain_interface(self) -> ChainInterfaceAbc:
+        return self._chain_interface
+
+    @property
+    @check_connected
+    def chain_id(self) -> uint256:
+        return uint256(self._chain_id)
+
+    @property
+    @check_connected
+    def offline(self) -> bool:
+        """
+        Whether chain explorer lookups (contract names, ABIs and storage layouts of unknown contracts) are disabled.
+        """
+        return self._offline
+
+    @offline.setter
+    @check_connected
+    def offline(self, value: bool) -> None:
+        self._offline = value
+
+    @property
+    @check_connected
+    def accounts(self) -> Tuple[Account, ...]:
+        return tuple(self._accounts)
+
+    @property
+    @check_connected
+    def txs(self) -> ChainTransactions:
+        return self._txs
+
+    @property
+    @check_connected
+    def default_call_account(self) -> Optional[Account]:
+        return self._default_call_account
+
+    @default_call_account.setter
+    @check_connected
+    def default_call_account(self, account: Union[Account, Address, str]) -> None:
+        if isinstance(account, Account):
+            if account.chain != self:
+                raise ChainMismatchError(account, self)
+            self._default_call_account = account
+        else:
+            self._default_call_account = Account(account, self)
+
+    @property
+    @check_connected
+    def default_tx_account(self) -> Optional[Account]:
+        return self._default_tx_account
+
+    @default_tx_account.setter
+    @check_connected
+    def default_tx_account(self, account: Union[Account, Address, str]) -> None:
+        if isinstance(account, Account):
+            if account.chain != self:
+                raise ChainMismatchError(account, self)
+            self._default_tx_account = account
+        else:
+            self._default_tx_account = Account(account, self)
+
+    @property
+    @check_connected
+    def default_estimate_account(self) -> Optional[Account]:
+        return self._default_estimate_account
+
+    @default_estimate_account.setter
+    @check_connected
+    def default_estimate_account(self, account: Union[Account, Address, str]) -> None:
+        if isinstance(account, Account):
+            if account.chain != self:
+                raise ChainMismatchError(account, self)
+            self._default_estimate_account = account
+        else:
+            self._default_estimate_account = Account(account, self)
+
+    @property
+    @check_connected
+    def default_access_list_account(self) -> Optional[Account]:
+        return self._default_access_list_account
+
+    @default_access_list_account.setter
+    @check_connected
+    def default_access_list_account(
+        self, account: Union[Account, Address, str]
+    ) -> None:
+        if isinstance(account, Account):
+            if account.chain != self:
+                raise ChainMismatchError(account, self)
+            self._default_access_list_account = account
+        else:
+            self._default_access_list_account = Account(account, self)
+
+    @property
+    @check_connected
+    def coinbase(self) -> Account:
+        return Account(self._chain_interface.get_coinbase(), self)
+
+    @coinbase.setter
+    @check_connected
+    def coinbase(self, value: Union[Account, Address, str]) -> None:
+        if isinstance(value, Account):
+            if value.chain != self:
+                raise ChainMismatchError(value, self)
+            self._chain_interface.set_coinbase(str(value.address))
+        else:
+            self._chain_interface.set_coinbase(str(value))
+
+    @property
+    @check_connected
+    def blocks(self) -> ChainBlocks:
+        return self._blocks
+
+    @property
+    @check_connected
+    def require_signed_txs(self) -> bool:
+        return self._require_signed_txs
+
+    @require_signed_txs.setter
+    @check_connected
+    def require_signed_txs(self, value: bool) -> None:
+        self._require_signed_txs = value
+
+    @property
+    @check_connected
+    def strict_nonces(self) -> bool:
+        """
+        Whether to check the nonce of each transaction against the pending nonce of the sender on chain before sending it.
+        `NonceGap` is raised if the nonce is higher and `NonceReuse` if the nonce is lower than expected.
+        """
+        return self._strict_nonces
+
+    @strict_nonces.setter
+    @check_connected
+    def strict_nonces(self, value: bool) -> None:
+        self._strict_nonces = value
+
+    @property
+    @check_connected
+    def validate_arguments(self) -> bool:
+        """
+        Whether to check that integer, enum and user-defined value type arguments are within the bounds of their ABI types
+        before encoding them. If `False`, out-of-range enum values are encoded as full 32-byte words, e.g. to intentionally
+        send invalid enum values when fuzzing.
+        """
+        return self._validate_arguments
+
+    @validate_arguments.setter
+    @check_connected
+    def validate_arguments(self, value: bool) -> None:
+        self._validate_arguments = value
+
+    @property
+    @check_connected
+    def evm_version_check(self) -> bool:
+        """
+        Whether to check contracts compiled for an EVM version newer than the active hardfork before deploying them.
+        If `True` (default), `IncompatibleEvmVersion` is raised when the creation code contains opcodes not available
+        in the active hardfork, otherwise a `RuntimeWarning` is issued.
+        """
+        return self._evm_version_check
+
+    @evm_version_check.setter
+    @check_connected
+    def evm_version_check(self, value: bool) -> None:
+        self._evm_version_check = value
+
+    @property
+    @check_connected
+    def allow_unprotected_txs(self) -> bool:
+        """
+        Whether transactions signed with a private key may be replayable on other chains, i.e. legacy transactions
+        without [EIP-155](https://eips.ethereum.org/EIPS/eip-155) replay protection or transactions with a chain ID
+        different from the chain ID reported by the connected node.
+        If `False` (default), `ReplayProtectionError` is raised before signing such a transaction.
+        """
+        return self._allow_unprotected_txs
+
+    @allow_unprotected_txs.setter
+    @check_connected
+    def allow_unprotected_txs(self, value: bool) -> None:
+        self._allow_unprotected_txs = value
+
+    @property
+    @check_connected
+    def detect_uninitialized_reads(self) -> bool:
+        """
+        Whether to trace every mined transaction and record reads of storage slots never written in this chain session
+        (and holding zero, i.e. not initialized in a forked chain either) into `tx.uninitialized_reads`.
+        Slows down transactions considerably, intended mainly for fuzzing upgradeable contracts.
+        """
+        return self._detect_uninitialized_reads
+
+    @detect_uninitialized_reads.setter
+    @check_connected
+    def detect_uninitialized_reads(self, value: bool) -> None:
+        self._detect_uninitialized_reads = value
+
+    @property
+    @check_connected
+    def zero_copy_returns(self) -> bool:
+        """
+        Whether to return raw call output and `bytes` return values of calls as `memoryview` objects sharing memory
+        with the received data instead of copying them. Reduces peak memory when calls return multi-megabyte payloads.
+        """
+        return self._zero_copy_returns
+
+    @zero_copy_returns.setter
+    @check_connected
+    def zero_copy_returns(self, value: bool) -> None:
+        self._zero_copy_returns = value
+
+    @property
+    @check_connected
+    def event_frames(self) -> bool:
+        """
+        Whether to set `frame` of decoded events to the call path of the call that emitted the event.
+        Requires the `debug_traceTransaction` JSON-RPC method. If disabled, `frame` is `None`.
+        """
+        return self._event_frames
+
+    @event_frames.setter
+    @check_connected
+    def event_frames(self, value: bool) -> None:
+        self._event_frames = value
+
+    @property
+    @check_connected
+    def precompile_gas_overrides(
+        self,
+    ) -> Dict[Address, Union[int, Callable[[bytes], int]]]:
+        """
+        Gas costs of precompiled contracts on the target network (e.g. an L2 with cheaper `ecPairing`) keyed by precompile address.
+        A cost is either a constant or a function of the call input. The development chain still charges the native costs;
+        the overrides are reflected in `tx.precompile_gas_adjustment` and `tx.adjusted_gas_used`.
+        """
+        return self._precompile_gas_overrides
+
+    @precompile_gas_overrides.setter
+    @check_connected
+    def precompile_gas_overrides(
+        self,
+        value: Mapping[Union[Address, str, int], Union[int, Callable[[bytes], int]]],
+    ) -> None:
+        self._precompile_gas_overrides = {
+            Address(address): cost for address, cost in value.items()
+        }
+
+    @property
+    @check_connected
+    def token_guard(self) -> Optional[TokenGuard]:
+        """
+        ERC-20 safety checks reporting unbounded approvals and transfers to unexpected recipients made by the watched
+        contracts into `tx.token_guard_findings`. Disabled if `None`.
+        """
+        return self._token_guard
+
+    @token_guard.setter
+    @check_connected
+    def token_guard(self, value: Optional[TokenGuard]) -> None:
+        self._token_guard = value
+
+    def _process_uninitialized_reads(self, tx: TransactionAbc) -> None:
+        tx._fetch_debug_trace_transaction()
+        tx._uninitialized_reads = process_debug_trace_for_uninitialized_reads(
+            tx,
+            tx._debug_trace_transaction,  # pyright: ignore reportGeneralTypeIssues
+            self._written_slots,
+        )
+
+    def _check_replay_protection(self, tx_params: TxParams) -> None:
+        if self._allow_unprotected_txs:
+            return
+
+        if "chainId" not in tx_params:
+            raise ReplayProtectionError(
+                f"Refusing to sign a transaction from {tx_params['from']} without EIP-155 replay protection, "
+                "the signed transaction could be replayed on any chain. "
+                "Set chain.allow_unprotected_txs = True to sign it anyway.",
+                tx_params,
+            )
+
+        if tx_params["chainId"] != self._chain_id:
+            raise ReplayProtectionError(
+                f"Refusing to sign a transaction from {tx_params['from']} with chain ID {tx_params['chainId']}, "
+                f"the chain ID of the connected chain is {self._chain_id}. "
+                "Set chain.allow_unprotected_txs = True to sign it anyway.",
+                tx_params,
+            )
+
+    @property
+    @check_connected
+    def default_tx_type(self) -> int:
+        return self._default_tx_type
+
+    @default_tx_type.setter
+    @check_connected
+    def default_tx_type(self, value: int) -> None:
+        if value not in {0, 1, 2}:
+            raise ValueError("Invalid transaction type")
+        self._default_tx_type = value
+
+    @property
+    @check_connected
+    def default_tx_confirmations(self) -> int:
+        return self._default_tx_confirmations
+
+    @default_tx_confirmations.setter
+    @check_connected
+    def default_tx_confirmations(self, value: int) -> None:
+        if value < 0:
+            raise ValueError("Invalid transaction confirmations value")
+        self._default_tx_confirmations = value
+
+    @contextmanager
+    def change_automine(self, automine: bool):
+        if not self._connected:
+            raise NotConnectedError("Not connected to a chain")
+        automine_was = self._get_automine()
+        self._set_automine(automine)
+        try:
+            yield
+        except Exception as e:
+            if not isinstance(e, BdbQuit):
+                exception_handler = get_exception_handler()
+                if exception_handler is not None:
+                    exception_handler(*sys.exc_info())
+                raise
+        finally:
+            self._set_automine(automine_was)
+
+    @contextmanager
+    def change_chain_id(self, chain_id: int):
+        """
+        Context manager temporarily changing the chain ID returned by the `CHAINID` opcode and `eth_chainId`,
+        e.g. to test cross-chain replay protection without reconnecting the chain.
+        Transactions and authorizations signed inside the context use the changed chain ID. Reverting to a snapshot
+        taken before the context restores the chain ID of the snapshot. Only supported with Anvil.
+        Use the `chain_id_override` keyword argument of contract functions and low-level methods to change the chain ID
+        for a single request.
+        """
+        if not self._connected:
+            raise NotConnectedError("Not connected to a chain")
+        if not isinstance(self._chain_interface, AnvilChainInterface):
+            raise NotImplementedError(
+                "Changing the chain ID is only supported with Anvil"
+            )
+        if chain_id <= 0:
+            raise ValueError("Chain ID must be positive")
+
+        chain_id_was = self._chain_id
+        self._chain_interface.set_chain_id(chain_id)
+        self._chain_id = chain_id
+        try:
+            yield
+        finally:
+            self._restore_chain_id(chain_id_was)
+
+    def _in_view_request(self) -> bool:
+        # view requests may run concurrently with other threads and must not touch the shared (journaled) state
+        return getattr(_view_requests, "chain", None) is self
+
+    def _chain_id_override(self, chain_id: Optional[int]):
+        # context of a request with the `chain_id_override` keyword argument
+        if chain_id is None:
+            return nullcontext()
+        return self.change_chain_id(chain_id)
+
+    def _check_chain_id_override(
+        self, chain_id: Optional[int], confirmations: Optional[int]
+    ) -> None:
+        # transactions are only signed and mined with the overridden chain ID, so they must be mined before it is restored
+        if chain_id is not None and (confirmations == 0 or not self._get_automine()):
+            raise ValueError(
+                "chain_id_override requires the transaction to be mined synchronously "
+                "(automine enabled and confirmations other than 0)"
+            )
+
+    def _restore_chain_id(self, chain_id: int) -> None:
+        # the chain ID is not part of the state reverted by the client
+        if chain_id != self._chain_id:
+            assert isinstance(self._chain_interface, AnvilChainInterface)
+            self._chain_interface.set_chain_id(chain_id)
+            self._chain_id = chain_id
+
+    @property
+    @check_connected
+    def execution_timeout(self) -> Optional[float]:
+        """
+        Wall-clock time limit in seconds for requests executing EVM code (transactions, calls and gas estimations).
+        `ExecutionTimeout` is raised when the limit is exceeded. `None` means the `general.json_rpc_timeout` config option applies.
+
+        The limit is enforced on the client side only, the node keeps executing the abandoned request and may still mine
+        the transaction. After a transaction times out, the nonce of the sender and the latest block are fetched from
+        the node again on the next use, so the chain stays usable. Use a lower `gas_limit` to bound the work done by the node itself.
+        """
+        return self._chain_interface.execution_timeout
+
+    @execution_timeout.setter
+    @check_connected
+    def execution_timeout(self, value: Optional[float]) -> None:
+        if value is not None and value <= 0:
+            raise ValueError("Execution timeout must be positive")
+        self._chain_interface.execution_timeout = value
+
+    @contextmanager
+    def change_execution_timeout(self, timeout: Optional[float]):
+        if not self._connected:
+            raise NotConnectedError("Not connected to a chain")
+        timeout_was = self.execution_timeout
+        self.execution_timeout = timeout
+        try:
+            yield
+        finally:
+            self._chain_interface.execution_timeout = timeout_was
+
+    @property
+    @check_connected
+    def signing_timeout(self) -> Optional[float]:
+        """
+        Wall-clock time limit in seconds for signing requests served by the client (`eth_sign`, `eth_signTypedData_v4`
+        and transactions sent from client-owned accounts), e.g. when the client forwards them to a hardware wallet.
+        `SigningTimeout` is raised when the limit is exceeded. `None` means the `general.json_rpc_timeout` config option applies.
+        """
+        return self._chain_interface.signing_timeout
+
+    @signing_timeout.setter
+    @check_connected
+    def signing_timeout(self, value: Optional[float]) -> None:
+        if value is not None and value <= 0:
+            raise ValueError("Signing timeout must be positive")
+        self._chain_interface.signing_timeout = value
+
+    @property
+    @check_connected
+    def automine(self) -> bool:
+        return self._get_automine()
+
+    @automine.setter
+    @check_connected
+    def automine(self, value: bool) -> None:
+        self._set_automine(value)
+
+    def _get_automine(self) -> bool:
+        if self._automine is None:
+            self._automine = self._chain_interface.get_automine()
+        return self._automine
+
+    def _set_automine(self, value: bool) -> None:
+        self._chain_interface.set_automine(value)
+        self._automine = value
+
+    @property
+    @check_connected
+    def exact_gas_estimates(self) -> bool:
+        """
+        Whether `estimate` requests refine the node estimate by binary search over `eth_call` executions
+        to the minimal gas limit at which the transaction succeeds, bounded by the block gas limit.
+        Costs roughly 25 additional requests per estimate. Can be overridden per call with `exact` in `Account.estimate`.
+        """
+        return self._exact_gas_estimates
+
+    @exact_gas_estimates.setter
+    @check_connected
+    def exact_gas_estimates(self, value: bool) -> None:
+        self._exact_gas_estimates = value
+
+    @check_connected
+    def set_next_block_base_fee_per_gas(self, value: Union[int, str]) -> None:
+        if isinstance(value, str):
+            value = Wei.from_str(value)
+        self._chain_interface.set_next_block_base_fee_per_gas(value)
+
+    @check_connected
+    def set_next_block_timestamp(self, timestamp: int) -> None:
+        self._chain_interface.set_next_block_timestamp(timestamp)
+
+    @check_connected
+    def set_min_gas_price(self, value: Union[int, str]) -> None:
+        if isinstance(value, str):
+            value = Wei.from_str(value)
+        self._chain_interface.set_min_gas_price(value)
+        self.gas_price = value
+
+    @check_connected
+    def set_default_accounts(self, account: Union[Account, Address, str, None]) -> None:
+        if isinstance(account, Account):
+            if account.chain != self:
+                raise ChainMismatchError(account, self)
+        elif account is not None:
+            account = Account(account, self)
+
+        self._default_call_account = account
+        self._default_tx_account = account
+        self._default_estimate_account = account
+        self._default_access_list_account = account
+
+    @check_connected
+    def reset(self) -> None:
+        self._chain_interface.reset()
+        self._revert_count += 1
+
+    @check_connected
+    def update_accounts(self):
+        # keep the order of already known accounts stable, append new accounts at the end
+        accounts = [Account(acc, self) for acc in self._chain_interface.get_accounts()]
+        accounts_set = set(accounts)
+        self._accounts = [
+            acc for acc in self._accounts if acc in accounts_set
+        ] + [acc for acc in accounts if acc not in self._accounts_set]
+        self._accounts_set = accounts_set
 
-                self._default_tx_type = 0
-            else:
-                raise NotImplementedError(
-                    f"Unknown chain interface type: {type(self._chain_interface)}"
-                )
+    @check_connected
+    def account(self, label_or_index: Union[str, int]) -> Account:
+        """
+        Args:
+            label_or_index: Label of an account or index into `chain.accounts`.
 
-            if block_base_fee_per_gas is not None and not isinstance(
-                self._chain_interface, GanacheChainInterface
-            ):
-                try:
-                    self._chain_interface.set_next_block_base_fee_per_gas(
-                        block_base_fee_per_gas
-                    )
-                except JsonRpcError:
-                    pass
+        Returns:
+            Account with the given label or at the given index.
+        """
+        if isinstance(label_or_index, int):
+            return self._accounts[label_or_index]
 
-            from .transactions import ChainTransactions
+        for address, label in self._labels.items():
+            if label == label_or_index:
+                return Account(address, self)
+        raise KeyError(f"No account labeled {label_or_index!r}")
 
-            self._txs = ChainTransactions(self)
+    @property
+    @check_connected
+    def fresh_addresses(self) -> List[Account]:
+        """
+        Accounts returned by [fresh_address][wake.development.core.Chain.fresh_address] in the order of creation.
+        """
+        return list(self._fresh_addresses)
 
-            self._accounts = [
-                Account(acc, self) for acc in self._chain_interface.get_accounts()
-            ]
+    @check_connected
+    def fresh_address(self, label: Optional[str] = None) -> Account:
+        """
+        Return a new account without a private key that is distinct from `chain.accounts`, accounts with known
+        private keys and previously returned fresh addresses. Addresses are derived deterministically
+        from the chain ID and the number of fresh addresses created so far, independently of the random seed.
+        Fresh addresses created after a snapshot are forgotten when reverting to it, so the same addresses
+        are returned again. On forked chains, addresses already holding code, balance or nonce are skipped.
 
-            if len(self._accounts) > _test_accounts_generated_count:
-                # generate private keys for accounts without private key
-                for i in range(_test_accounts_generated_count, len(self._accounts)):
-                    Address.from_mnemonic(
-                        "test test test test test test test test test test test junk",
-                        path=f"m/44'/60'/0'/0/{i}",
-                    )
-                _test_accounts_generated_count = len(self._accounts)
+        Args:
+            label: Label of the account; defaults to `fresh{index}`.
 
-            self._accounts_set = set(self._accounts)
-            self._nonces = KeyedDefaultDict(
-                lambda addr: self._chain_interface.get_transaction_count(  # pyright: ignore reportGeneralTypeIssues
-                    str(addr)
-                )
+        Returns:
+            Fresh account, e.g. to be used as a third party or recipient.
+        """
+        index = len(self._fresh_addresses)
+        used = {acc.address for acc in self._fresh_addresses}
+        counter = index
+        while True:
+            address = Address(
+                "0x"
+                + keccak.new(
+                    data=b"wake-fresh-address"
+                    + self._chain_id.to_bytes(32, "big")
+                    + counter.to_bytes(32, "big"),
+                    digest_bits=256,
+                ).digest()[12:].hex()
             )
-            self._snapshots = {}
-            self._deployed_libraries = defaultdict(list)
+            acc = Account(address, self)
+            if (
+                acc not in self._accounts_set
+                and address not in used
+                and not acc.has_signer
+                and (self._fork is None or not self._is_address_used(address))
+            ):
+                break
+            counter += 1
 
-            if len(self._accounts) > 0:
-                self.set_default_accounts(self._accounts[0])
-            else:
-                self.set_default_accounts(None)
-            self._default_tx_confirmations = 1
-            self._blocks = ChainBlocks(self)
-            self._labels = {}
-            self._fork = fork
+        acc.label = label if label is not None else f"fresh{index}"
+        self._fresh_addresses.append(acc)
+        return acc
 
-            self._single_source_errors = {
-                selector
-                for selector, sources in errors.items()
-                if len({source for fqn, source in sources.items()}) == 1
-            }
+    def _is_address_used(self, address: Address) -> bool:
+        return (
+            len(self._chain_interface.get_code(str(address), "pending")) > 0
+            or self._chain_interface.get_balance(str(address), "pending") > 0
+            or self._chain_interface.get_transaction_count(str(address), "pending") > 0
+        )
 
-            self.tx_callback = None
+    @check_connected
+    def label_all(self, prefix: str = "account") -> None:
+        """
+        Label all `chain.accounts` as `{prefix}{index}`, overwriting existing labels.
 
-            self._connect_setup(min_gas_price, block_base_fee_per_gas)
+        Args:
+            prefix: Prefix of the labels.
+        """
+        for i, acc in enumerate(self._accounts):
+            acc.label = f"{prefix}{i}"
 
-            yield self
-        except Exception as e:
-            if not isinstance(e, BdbQuit):
-                exception_handler = get_exception_handler()
-                if exception_handler is not None:
-                    exception_handler(*sys.exc_info())
-                raise
-        finally:
-            self._connect_finalize()
-            self._connected = False
+    @check_connected
+    def mine(self, timestamp_change: Optional[Callable[[int], int]] = None) -> None:
+        if timestamp_change is not None:
+            block_info = self._chain_interface.get_block("latest")
+            assert "timestamp" in block_info
+            last_timestamp = int(block_info["timestamp"], 16)
+            timestamp = timestamp_change(last_timestamp)
+        else:
+            timestamp = None
 
-    @property
-    def connected(self) -> bool:
-        return self._connected
+        self._chain_interface.mine(timestamp)
+        self._run_block_mined_hooks()
 
-    @property
-    def client_version(self) -> str:
-        return self._client_version
+    @check_connected
+    def mine_many(
+        self, num_blocks: int, timestamp_change: Optional[int] = None
+    ) -> None:
+        self._chain_interface.mine_many(num_blocks, timestamp_change)
+        self._run_block_mined_hooks()
 
-    @property
     @check_connected
-    def chain_interface(self) -> ChainInterfaceAbc:
-        return self._chain_interface
+    def set_batched_tx_callback(
+        self,
+        callback: Optional[Callable[[List[TransactionAbc]], None]],
+        *,
+        batch_size: int = 100,
+        interval: Optional[float] = None,
+    ) -> None:
+        """
+        Register a callback receiving mined transactions in batches instead of one by one like `tx_callback`.
+        The callback is called once `batch_size` transactions are collected or `interval` seconds passed since
+        the first transaction of the batch, and with the remaining transactions before the chain is reverted
+        (`revert` or leaving `with_rollback`) and when the chain is disconnected (also when disconnecting because of an exception). The interval is only checked when a new transaction
+        is added to the batch, there is no timer flushing an idle batch; use `flush_tx_callback` for that.
+        Pending transactions of a previously registered callback are flushed first.
+
+        Args:
+            callback: Function receiving a list of transaction objects; `None` to unregister.
+            batch_size: Maximum number of transactions in a batch.
+            interval: Maximum time in seconds a transaction waits in a batch; no limit if `None`.
+        """
+        if batch_size <= 0:
+            raise ValueError("batch_size must be positive")
+        if interval is not None and interval < 0:
+            raise ValueError("interval must not be negative")
+
+        self.flush_tx_callback()
+        self._batched_tx_callback = callback
+        self._tx_batch_size = batch_size
+        self._tx_batch_interval = interval
 
-    @property
     @check_connected
-    def chain_id(self) -> uint256:
-        return uint256(self._chain_id)
+    def flush_tx_callback(self) -> None:
+        """
+        Call the batched transaction callback with all transactions collected so far.
+        """
+        if len(self._tx_batch) == 0:
+            return
+
+        batch = self._tx_batch
+        self._tx_batch = []
+        if self._batched_tx_callback is not None:
+            self._batched_tx_callback(batch)
+
+    def _add_to_tx_batch(self, tx: TransactionAbc) -> None:
+        if len(self._tx_batch) == 0:
+            self._tx_batch_start = time.perf_counter()
+        self._tx_batch.append(tx)
+
+        if len(self._tx_batch) >= self._tx_batch_size or (
+            self._tx_batch_interval is not None
+            and time.perf_counter() - self._tx_batch_start >= self._tx_batch_interval
+        ):
+            self.flush_tx_callback()
 
-    @property
     @check_connected
-    def accounts(self) -> Tuple[Account, ...]:
-        return tuple(self._accounts)
+    def on_block_mined(
+        self, callback: Callable[[Block], None]
+    ) -> Callable[[Block], None]:
+        """
+        Register a callback called with every block mined since the registration. Blocks are reported in order
+        once Wake observes them, i.e. after a transaction sent through Wake is confirmed or after `mine`/`mine_many`.
+        Can be used as a decorator.
+
+        Args:
+            callback: Function receiving the mined `Block`.
+
+        Returns:
+            The registered callback.
+        """
+        if self._last_hooked_block is None:
+            self._last_hooked_block = self._chain_interface.get_block_number()
+        self._block_mined_hooks.append(callback)
+        return callback
 
-    @property
     @check_connected
-    def txs(self) -> ChainTransactions:
-        return self._txs
+    def on_snapshot(
+        self, callback: Callable[[Snapshot], None]
+    ) -> Callable[[Snapshot], None]:
+        """
+        Register a callback called with the snapshot ID every time a snapshot is taken. Can be used as a decorator.
+
+        Args:
+            callback: Function receiving the new `Snapshot`.
+
+        Returns:
+            The registered callback.
+        """
+        self._snapshot_hooks.append(callback)
+        return callback
 
-    @property
     @check_connected
-    def default_call_account(self) -> Optional[Account]:
-        return self._default_call_account
+    def on_revert(self, callback: Callable[[str], None]) -> Callable[[str], None]:
+        """
+        Register a callback called with the snapshot ID every time the chain is reverted to a snapshot.
+        The chain state is already reverted when the callback is called. Can be used as a decorator.
+
+        Args:
+            callback: Function receiving the ID of the snapshot the chain was reverted to.
+
+        Returns:
+            The registered callback.
+        """
+        self._revert_hooks.append(callback)
+        return callback
 
-    @default_call_account.setter
     @check_connected
-    def default_call_account(self, account: Union[Account, Address, str]) -> None:
-        if isinstance(account, Account):
-            if account.chain != self:
-                raise ValueError("Account is not from this chain")
-            self._default_call_account = account
-        else:
-            self._default_call_account = Account(account, self)
+    def remove_hook(self, callback: Callable) -> None:
+        """
+        Unregister a callback previously registered with `on_block_mined`, `on_snapshot` or `on_revert`.
+
+        Args:
+            callback: Callback to unregister.
+        """
+        found = False
+        for hooks in (
+            self._block_mined_hooks,
+            self._snapshot_hooks,
+            self._revert_hooks,
+        ):
+            while callback in hooks:
+                hooks.remove(callback)
+                found = True
+        if not found:
+            raise ValueError(f"{callback!r} is not a registered hook")
+
+    def _run_block_mined_hooks(self) -> None:
+        if len(self._block_mined_hooks) == 0 or self._last_hooked_block is None:
+            return
+
+        latest = self._chain_interface.get_block_number()
+        while self._last_hooked_block < latest:
+            self._last_hooked_block += 1
+            block = self.blocks[self._last_hooked_block]
+            for hook in list(self._block_mined_hooks):
+                hook(block)
+
+    def _run_snapshot_hooks(self, snapshot_id: Snapshot) -> None:
+        for hook in list(self._snapshot_hooks):
+            hook(snapshot_id)
+
+    def _run_revert_hooks(self, snapshot_id: str) -> None:
+        if self._last_hooked_block is not None:
+            self._last_hooked_block = self._chain_interface.get_block_number()
+        for hook in list(self._revert_hooks):
+            hook(snapshot_id)
+
+    @check_connected
+    def prefetch_storage(
+        self,
+        storage: Mapping[Union[Account, Address, str], Iterable[int]],
+        *,
+        batch_size: int = 64,
+    ) -> None:
+        """
+        Fetch code and storage slots of the given accounts in JSON-RPC batches of at most `batch_size` requests.
+        A forking client resolves batched requests against the forked chain concurrently, so a following transaction
+        touching the slots does not wait for each cold slot to be fetched one by one.
+        The argument has the same format as the access list returned by `Account.access_list`.
+        """
+        if batch_size <= 0:
+            raise ValueError("batch_size must be positive")
+
+        addresses = []
+        requests = []
+        for account, slots in storage.items():
+            if isinstance(account, Account):
+                account = account.address
+            address = str(Address(account))
+            addresses.append(address)
+            requests.extend((address, slot) for slot in slots)
+
+        for i in range(0, len(addresses), batch_size):
+            self._chain_interface.get_code_many(addresses[i : i + batch_size])
+        for i in range(0, len(requests), batch_size):
+            self._chain_interface.get_storage_at_many(requests[i : i + batch_size])
 
     @property
     @check_connected
-    def default_tx_account(self) -> Optional[Account]:
-        return self._default_tx_account
+    def deployed_libraries(self) -> Dict[bytes, Library]:
+        """
+        Libraries used for linking, keyed by library ID (`LibType._library_id`).
+        The most recently deployed or linked instance is returned for each library.
+        """
+        return {
+            lib_id: libs[-1]
+            for lib_id, libs in self._deployed_libraries.items()
+            if len(libs) > 0
+        }
 
-    @default_tx_account.setter
     @check_connected
-    def default_tx_account(self, account: Union[Account, Address, str]) -> None:
-        if isinstance(account, Account):
-            if account.chain != self:
-                raise ValueError("Account is not from this chain")
-            self._default_tx_account = account
+    def link_library(
+        self, lib_type: Type[Library], address: Union[Account, Address, str]
+    ) -> Library:
+        """
+        Link `lib_type` against an already deployed library (e.g. on a forked chain) so that contracts
+        deployed afterwards use it instead of requiring the library to be deployed first.
+        """
+        if isinstance(address, Account):
+            if address.chain != self:
+                raise ChainMismatchError(address, self)
+            address = address.address
+
+        lib = lib_type(address, self)
+        self._deployed_libraries[lib_type._library_id].append(lib)
+        return lib
+
+    @overload
+    def etch(
+        self,
+        address: Union[Account, Address, str],
+        code: Type[ContractType],
+        *,
+        balance: Optional[Union[int, str]] = None,
+        nonce: Optional[int] = None,
+        storage: Optional[Dict[int, Union[int, bytes]]] = None,
+    ) -> ContractType:
+        ...
+
+    @overload
+    def etch(
+        self,
+        address: Union[Account, Address, str],
+        code: Union[bytes, bytearray],
+        *,
+        balance: Optional[Union[int, str]] = None,
+        nonce: Optional[int] = None,
+        storage: Optional[Dict[int, Union[int, bytes]]] = None,
+    ) -> Account:
+        ...
+
+    @check_connected
+    def etch(
+        self,
+        address: Union[Account, Address, str],
+        code: Union[bytes, bytearray, Type[Contract]],
+        *,
+        balance: Optional[Union[int, str]] = None,
+        nonce: Optional[int] = None,
+        storage: Optional[Dict[int, Union[int, bytes]]] = None,
+    ) -> Account:
+        """
+        Place code (and optionally balance, nonce and storage) at an arbitrary address, e.g. to mock a system contract.
+        If a `pytypes` contract type is given, its creation code is executed (without constructor arguments)
+        to obtain the runtime code and the storage initialized by the constructor, and the address is bound
+        to the contract type for call traces and events. All arguments are validated before the account is modified.
+
+        Args:
+            address: Address to place the code at.
+            code: Runtime code or a `pytypes` contract type.
+            balance: Balance to set.
+            nonce: Nonce to set.
+            storage: Storage slots to set (overriding the slots initialized by the constructor);
+                integer values are converted to 32-byte words.
+
+        Returns:
+            Instance of the contract type if given, otherwise an `Account` at the address.
+        """
+        if isinstance(address, Account):
+            if address.chain != self:
+                raise ChainMismatchError(address, self)
+            address = address.address
+        elif isinstance(address, str):
+            address = Address(address)
+
+        if isinstance(balance, str):
+            balance = Wei.from_str(balance)
+        for name, value in (("balance", balance), ("nonce", nonce)):
+            if value is None:
+                continue
+            if not isinstance(value, int):
+                raise TypeError(f"{name} must be an integer")
+            if value < 0:
+                raise ValueError(f"{name} must be non-negative")
+
+        slots: Dict[int, bytes] = {}
+        if isinstance(code, type):
+            libraries = {
+                lib_id: (libs[-1], "")
+                for lib_id, libs in self._deployed_libraries.items()
+                if len(libs) > 0
+            }
+            for match in LIBRARY_PLACEHOLDER_REGEX.finditer(code._creation_code):
+                if bytes.fromhex(match.group(0)[3:-3]) not in libraries:
+                    raise ValueError(
+                        f"Libraries used by {code.__name__} must be deployed or linked first"
+                    )
+            creation_code = code._get_creation_code(libraries)
+
+            if not self._debug_trace_call_supported:
+                raise ValueError(
+                    f"Storage initialized by the constructor of {code.__name__} cannot be obtained without debug_traceCall, pass the runtime code and storage instead"
+                )
+            try:
+                trace = self._chain_interface.debug_trace_call(
+                    {"data": creation_code},
+                    "latest",
+                    {"disableMemory": True, "disableStorage": True},
+                )
+            except JsonRpcError:
+                trace = None
+            if trace is None or trace["failed"]:
+                raise ValueError(
+                    f"Failed to execute creation code of {code.__name__}, pass the runtime code instead"
+                )
+
+            return_value = trace["returnValue"]
+            if return_value.startswith("0x"):
+                return_value = return_value[2:]
+            runtime_code = bytes.fromhex(return_value)
+            slots = {
+                slot: value.to_bytes(32, "big")
+                for slot, value in _get_constructor_storage(trace).items()
+            }
+            fqn = get_fqn_from_creation_code(creation_code)[0]
+            account = code(address, self)
         else:
-            self._default_tx_account = Account(account, self)
+            runtime_code = bytes(_as_bytes(code))
+            fqn = None
+            account = Account(address, self)
+
+        if storage is not None:
+            for slot, value in storage.items():
+                if isinstance(value, int):
+                    value = value.to_bytes(32, "big")
+                elif len(value) > 32:
+                    raise ValueError(f"Value of storage slot {slot} exceeds 32 bytes")
+                slots[slot] = bytes(value).rjust(32, b"\x00")
+
+        self._chain_interface.set_code(str(address), runtime_code)
+        # undone by the journal together with the chain state on revert
+        if fqn is not None:
+            self._fqn_overrides[address] = (
+                keccak.new(data=runtime_code, digest_bits=256).digest(),
+                fqn,
+            )
+        elif address in self._fqn_overrides:
+            del self._fqn_overrides[address]
+
+        if balance is not None:
+            account.balance = balance
+        if nonce is not None:
+            account.nonce = nonce
+        for slot, value in slots.items():
+            self._chain_interface.set_storage_at(str(address), slot, value)
+        return account
+
+    def _get_patches(
+        self, address: Address
+    ) -> Tuple[bytes, Optional[Tuple[Address, Dict[bytes, Address]]]]:
+        code = self._chain_interface.get_code(str(address))
+        return code, self._patched_functions.get(
+            keccak.new(data=code, digest_bits=256).digest()
+        )
+
+    def _set_patches(
+        self, address: Address, original: Address, patches: Dict[bytes, Address]
+    ) -> None:
+        if len(patches) == 0:
+            self._chain_interface.set_code(
+                str(address), self._chain_interface.get_code(str(original))
+            )
+            if address in self._fqn_overrides:
+                del self._fqn_overrides[address]
+            return
+
+        shim = _build_patch_shim(original, patches)
+        shim_hash = keccak.new(data=shim, digest_bits=256).digest()
+        self._patched_functions[shim_hash] = (original, dict(patches))
+        fqn = get_fqn_from_address(original, "latest", self)
+        if fqn is not None:
+            self._fqn_overrides[address] = (shim_hash, fqn)
+        self._chain_interface.set_code(str(address), shim)
 
-    @property
     @check_connected
-    def default_estimate_account(self) -> Optional[Account]:
-        return self._default_estimate_account
+    def patch_function(
+        self,
+        contract: Union[Account, Address, str],
+        selector: Union[bytes, Callable],
+        new_impl: Union[Account, Address, str],
+    ) -> None:
+        """
+        Reroute calls of a single function of a deployed contract to another contract without redeploying it,
+        e.g. to stub out a subsystem during fuzzing. The contract's code is replaced with a dispatcher
+        delegatecalling `new_impl` for the patched selector and a copy of the original code otherwise.
+        As `new_impl` is executed with `DELEGATECALL`, it operates on the storage of the patched contract.
+
+        Args:
+            contract: Contract to patch.
+            selector: 4-byte function selector or a `pytypes` function (e.g. `Counter.increment`).
+            new_impl: Contract implementing the function.
+        """
+        address = self._resolve_patch_address(contract)
+        impl = self._resolve_patch_address(new_impl)
+        selector = self._resolve_patch_selector(selector)
+
+        code, patched = self._get_patches(address)
+        if patched is None:
+            if len(code) == 0:
+                raise ValueError(f"No code at address {address}")
+            # deterministic address holding a copy of the original code
+            code_hash = keccak.new(data=code, digest_bits=256).digest()
+            original = Address(
+                keccak.new(
+                    data=b"wake-patch" + bytes(address) + code_hash, digest_bits=256
+                ).digest()[12:].hex()
+            )
+            self._chain_interface.set_code(str(original), code)
+            patches = {}
+        else:
+            original, patches = patched[0], dict(patched[1])
+
+        patches[selector] = impl
+        self._set_patches(address, original, patches)
 
-    @default_estimate_account.setter
     @check_connected
-    def default_estimate_account(self, account: Union[Account, Address, str]) -> None:
+    def unpatch_function(
+        self,
+        contract: Union[Account, Address, str],
+        selector: Optional[Union[bytes, Callable]] = None,
+    ) -> None:
+        """
+        Revert a function patched by `patch_function` to the original implementation.
+        The original code of the contract is restored once no patched functions remain.
+
+        Args:
+            contract: Patched contract.
+            selector: 4-byte function selector or a `pytypes` function; all patched functions are reverted if `None`.
+        """
+        address = self._resolve_patch_address(contract)
+        _, patched = self._get_patches(address)
+        if patched is None:
+            raise ValueError(f"Contract at address {address} is not patched")
+
+        original, patches = patched[0], dict(patched[1])
+        if selector is None:
+            patches.clear()
+        else:
+            selector = self._resolve_patch_selector(selector)
+            if selector not in patches:
+                raise ValueError(
+                    f"Function with selector 0x{selector.hex()} is not patched"
+                )
+            del patches[selector]
+
+        self._set_patches(address, original, patches)
+
+    def _resolve_patch_address(self, account: Union[Account, Address, str]) -> Address:
         if isinstance(account, Account):
             if account.chain != self:
-                raise ValueError("Account is not from this chain")
-            self._default_estimate_account = account
-        else:
-            self._default_estimate_account = Account(account, self)
+                raise ChainMismatchError(account, self)
+            return account.address
+        elif isinstance(account, str):
+            return Address(account)
+        return account
+
+    @staticmethod
+    def _resolve_patch_selector(selector: Union[bytes, Callable]) -> bytes:
+        if not isinstance(selector, (bytes, bytearray)):
+            if not isinstance(getattr(selector, "selector", None), bytes):
+                raise TypeError("selector must be bytes or a pytypes function")
+            selector = selector.selector  # pyright: ignore reportGeneralTypeIssues
+        if len(selector) != 4:
+            raise ValueError("selector must be 4 bytes long")
+        return bytes(selector)
 
     @property
     @check_connected
-    def default_access_list_account(self) -> Optional[Account]:
-        return self._default_access_list_account
+    def ignored_events(self) -> List[Tuple[Optional[bytes], Optional[Address]]]:
+        """
+        Filters added with [ignore_event][wake.development.core.Chain.ignore_event] as `(selector, address)` tuples.
+        """
+        return list(self._ignored_events)
 
-    @default_access_list_account.setter
     @check_connected
-    def default_access_list_account(
-        self, account: Union[Account, Address, str]
+    def ignore_event(
+        self,
+        selector: Optional[Union[bytes, Type]] = None,
+        *,
+        address: Optional[Union[Account, Address, str]] = None,
     ) -> None:
-        if isinstance(account, Account):
-            if account.chain != self:
-                raise ValueError("Account is not from this chain")
-            self._default_access_list_account = account
-        else:
-            self._default_access_list_account = Account(account, self)
+        """
+        Drop matching events from `tx.events` and from call traces (with the `events` call trace option).
+        Ignored events are still counted in `tx.ignored_events_count` and included in `tx.raw_events`.
+
+        Args:
+            selector: 32-byte event selector (topic0) or a `pytypes` event; events with any selector match if `None`.
+            address: Address of the contract emitting the events; events emitted by any contract match if `None`.
+        """
+        entry = self._resolve_ignored_event(selector, address)
+        if entry not in self._ignored_events:
+            self._ignored_events.append(entry)
+
+    @check_connected
+    def unignore_event(
+        self,
+        selector: Optional[Union[bytes, Type]] = None,
+        *,
+        address: Optional[Union[Account, Address, str]] = None,
+    ) -> None:
+        """
+        Remove a filter previously added with [ignore_event][wake.development.core.Chain.ignore_event]
+        with the same arguments.
+        """
+        entry = self._resolve_ignored_event(selector, address)
+        if entry not in self._ignored_events:
+            raise ValueError("Event filter not found")
+        self._ignored_events.remove(entry)
+
+    def _resolve_ignored_event(
+        self,
+        selector: Optional[Union[bytes, Type]],
+        address: Optional[Union[Account, Address, str]],
+    ) -> Tuple[Optional[bytes], Optional[Address]]:
+        if selector is None and address is None:
+            raise ValueError("Either selector or address must be specified")
+
+        if selector is not None and not isinstance(selector, (bytes, bytearray)):
+            if not isinstance(getattr(selector, "selector", None), bytes):
+                raise TypeError("selector must be bytes or a pytypes event")
+            selector = selector.selector  # pyright: ignore reportGeneralTypeIssues
+        if selector is not None and len(selector) != 32:
+            raise ValueError("selector must be 32 bytes long")
+
+        return (
+            bytes(selector) if selector is not None else None,
+            self._resolve_patch_address(address) if address is not None else None,
+        )
+
+    def _is_log_ignored(self, log: Dict[str, Any]) -> bool:
+        if len(self._ignored_events) == 0:
+            return False
+
+        selector = None
+        if len(log["topics"]) > 0:
+            topic = log["topics"][0]
+            topic = topic[2:] if topic.startswith("0x") else topic
+            selector = bytes.fromhex(topic.zfill(64))
+        return self._is_event_ignored(selector, Address(log["address"]))
+
+    def _is_event_ignored(self, selector: Optional[bytes], address: Address) -> bool:
+        return any(
+            (s is None or s == selector) and (a is None or a == address)
+            for s, a in self._ignored_events
+        )
 
-    @property
     @check_connected
-    def coinbase(self) -> Account:
-        return Account(self._chain_interface.get_coinbase(), self)
+    def dump_state(self) -> Dict[str, Any]:
+        """
+        Return a JSON-serializable dump of the chain state together with the deployed libraries mapping.
+        Only supported with Anvil.
+        """
+        if not isinstance(self._chain_interface, AnvilChainInterface):
+            raise NotImplementedError("State dumps are only supported with Anvil")
+
+        return {
+            "state": self._chain_interface.dump_state(),
+            "libraries": {
+                lib_id.hex(): str(lib.address)
+                for lib_id, lib in self.deployed_libraries.items()
+            },
+        }
 
-    @coinbase.setter
     @check_connected
-    def coinbase(self, value: Union[Account, Address, str]) -> None:
-        if isinstance(value, Account):
-            if value.chain != self:
-                raise ValueError("Account is not from this chain")
-            self._chain_interface.set_coinbase(str(value.address))
-        else:
-            self._chain_interface.set_coinbase(str(value))
+    def load_state(self, state: Dict[str, Any]) -> None:
+        """
+        Load a state previously returned by [dump_state][wake.development.core.Chain.dump_state],
+        including the deployed libraries mapping. Only supported with Anvil.
+        Locally tracked nonces, written storage slots and cached transactions and blocks are dropped,
+        as they describe the replaced state. All active snapshots are invalidated and cannot be reverted anymore.
+        """
+        if not isinstance(self._chain_interface, AnvilChainInterface):
+            raise NotImplementedError("State loading is only supported with Anvil")
+
+        self._chain_interface.load_state(state["state"])
+        self._nonces.clear()
+        self._written_slots.clear()
+        self._verified_libraries.clear()
+        self._txs._clear()
+        self._blocks._clear()
+        # the journal and snapshots describe the replaced state, reverting them would mix both states
+        self._snapshots.clear()
+        self._journal.clear()
+        for lib_id, address in state.get("libraries", {}).items():
+            self._deployed_libraries[bytes.fromhex(lib_id)].append(
+                Library(Address(address), self)
+            )
 
     @property
     @check_connected
-    def blocks(self) -> ChainBlocks:
-        return self._blocks
+    def journal_stats(self) -> JournalStats:
+        """
+        Statistics of the journal recording changes of locally tracked nonces and written storage slots since
+        the oldest active snapshot. Snapshots store a journal checkpoint instead of copies of these collections.
+        The counters are numbers of undo entries, not numbers or sizes of memory allocations.
+        """
+        return self._journal.stats
 
     @property
     @check_connected
-    def require_signed_txs(self) -> bool:
-        return self._require_signed_txs
+    def journal_limit(self) -> Optional[int]:
+        """
+        Maximum number of entries held by the journal. When exceeded, the oldest snapshots are evicted and cannot
+        be reverted anymore; a `RuntimeWarning` is issued for every eviction. `None` means no limit.
+        Evicting snapshots changes the outcome of tests reverting them, so the limit should only be set as a safety net.
+        """
+        return self._journal.limit
 
-    @require_signed_txs.setter
+    @journal_limit.setter
     @check_connected
-    def require_signed_txs(self, value: bool) -> None:
-        self._require_signed_txs = value
+    def journal_limit(self, value: Optional[int]) -> None:
+        if value is not None and value < 0:
+            raise ValueError("Journal limit must be non-negative")
+        self._journal.limit = value
 
-    @property
     @check_connected
-    def default_tx_type(self) -> int:
-        return self._default_tx_type
+    def compact(self) -> None:
+        """
+        Drop journal entries recorded before the oldest remaining snapshot. Runs automatically when a snapshot is taken or reverted (journal entries are dropped once they make up
+        at least half of the journal), so calling it is only needed to free memory immediately.
+        Snapshots are never released implicitly; use `Chain.release` for snapshots that will not be reverted.
+        """
+        self._journal.compact()
 
-    @default_tx_type.setter
-    @check_connected
-    def default_tx_type(self, value: int) -> None:
-        if value not in {0, 1, 2}:
-            raise ValueError("Invalid transaction type")
-        self._default_tx_type = value
+    def _resolve_address(
+        self, value: Union[Account, Address, str, int], role: str = "Account"
+    ) -> Address:
+        # single place where accounts, addresses and ENS names passed by the user are converted to addresses
+        if isinstance(value, Account):
+            if value.chain != self:
+                raise ChainMismatchError(value, self, role)
+            return value.address
+        elif isinstance(value, Address):
+            return value
+        elif isinstance(value, str) and _is_ens_name(value):
+            return self.resolve_ens(value)
+        return Address(value)
+
+    def _resolve_ens_arguments(self, arg_abi: Dict[str, Any], value: Any) -> Any:
+        # resolve ENS names passed as address arguments (including arrays and tuples of addresses)
+        t = arg_abi["type"]
+        array_match = _ARRAY_SUFFIX_RE.search(t)
+        if array_match is not None:
+            if not isinstance(value, (list, tuple)):
+                return value
+            item_abi = dict(arg_abi)
+            item_abi["type"] = t[: array_match.start()]
+            return [self._resolve_ens_arguments(item_abi, item) for item in value]
+        elif t == "tuple":
+            if not isinstance(value, (list, tuple)):
+                return value
+            return tuple(
+                self._resolve_ens_arguments(component, item)
+                for component, item in zip(arg_abi["components"], value)
+            )
+        elif t == "address" and isinstance(value, str) and _is_ens_name(value):
+            return str(self.resolve_ens(value))
+        return value
 
-    @property
     @check_connected
-    def default_tx_confirmations(self) -> int:
-        return self._default_tx_confirmations
+    def resolve_ens(self, name: str) -> Address:
+        """
+        Resolve an ENS name (e.g. `vitalik.eth`) using the ENS registry deployed on the chain, typically on a chain forked from a network with ENS.
+        Resolved names are cached until the chain is reverted to a snapshot.
+        ENS names are accepted wherever an account is expected, e.g. `Account("vitalik.eth", chain=chain)`, `from_` and `to` parameters
+        and `address` arguments of contract functions.
+        """
+        name = name.lower()
+        if name in self._ens_cache:
+            return self._ens_cache[name]
+
+        if len(self._chain_interface.get_code(str(_ENS_REGISTRY))) == 0:
+            raise ValueError(f"ENS registry not deployed on chain {self._chain_id}")
+
+        node = ens_namehash(name)
+        resolver = Abi.decode(
+            ["address"],
+            self._chain_interface.call(
+                {
+                    "to": str(_ENS_REGISTRY),
+                    "data": Abi.encode_with_signature(
+                        "resolver(bytes32)", ["bytes32"], [node]
+                    ),
+                }
+            ),
+        )[0]
+        if resolver == Address.ZERO:
+            raise ValueError(f"ENS name {name} has no resolver")
+
+        address = Abi.decode(
+            ["address"],
+            self._chain_interface.call(
+                {
+                    "to": str(resolver),
+                    "data": Abi.encode_with_signature(
+                        "addr(bytes32)", ["bytes32"], [node]
+                    ),
+                }
+            ),
+        )[0]
+        if address == Address.ZERO:
+            raise ValueError(f"ENS name {name} does not resolve to an address")
+
+        self._ens_cache[name] = address
+        return address
 
-    @default_tx_confirmations.setter
     @check_connected
-    def default_tx_confirmations(self, value: int) -> None:
-        if value < 0:
-            raise ValueError("Invalid transaction confirmations value")
-        self._default_tx_confirmations = value
+    def events(
+        self,
+        since_block: Optional[int] = None,
+        types: Optional[Iterable[type]] = None,
+        *,
+        address: Optional[
+            Union[Account, Address, Iterable[Union[Account, Address]]]
+        ] = None,
+    ) -> EventSubscription:
+        """
+        Subscribe to events emitted on the chain, optionally filtered by event types and emitting addresses.
+        Events are decoded lazily while the returned subscription is iterated.
 
-    @contextmanager
-    def change_automine(self, automine: bool):
-        if not self._connected:
-            raise NotConnectedError("Not connected to a chain")
-        automine_was = self._chain_interface.get_automine()
-        self._chain_interface.set_automine(automine)
-        try:
-            yield
-        except Exception as e:
-            if not isinstance(e, BdbQuit):
-                exception_handler = get_exception_handler()
-                if exception_handler is not None:
-                    exception_handler(*sys.exc_info())
-                raise
-        finally:
-            self._chain_interface.set_automine(automine_was)
+        Args:
+            since_block: First block to yield events from; defaults to the next block to be mined.
+            types: Event types (e.g. `IERC20.Transfer`) to yield; all events are yielded if `None`.
+            address: Address(es) of the emitting contracts to yield events from.
 
-    @property
-    @check_connected
-    def automine(self) -> bool:
-        return self._chain_interface.get_automine()
+        Returns:
+            Subscription that yields events from blocks mined since its last iteration.
+        """
+        from .transactions import EventSubscription
 
-    @automine.setter
-    @check_connected
-    def automine(self, value: bool) -> None:
-        self._chain_interface.set_automine(value)
+        if since_block is None:
+            since_block = self._chain_interface.get_block_number() + 1
+        elif since_block < 0:
+            since_block = self._chain_interface.get_block_number() + since_block + 1
+        return EventSubscription(self, since_block, types, address)
 
     @check_connected
-    def set_next_block_base_fee_per_gas(self, value: Union[int, str]) -> None:
-        if isinstance(value, str):
-            value = Wei.from_str(value)
-        self._chain_interface.set_next_block_base_fee_per_gas(value)
+    def token_flows(
+        self,
+        token: Optional[Union[Account, Address, str]],
+        *,
+        since_block: Optional[int] = None,
+        to_block: Optional[int] = None,
+    ) -> TokenFlows:
+        """
+        Aggregate net balance changes of an ERC-20 token from `Transfer` events emitted in a range of blocks.
+        Ether flows are aggregated from call traces of all transactions in the range if `token` is `None`;
+        both `since_block` and `to_block` must be given in this case, as every transaction in the range is traced.
+
+        Args:
+            token: Address of the ERC-20 token or `None` for Ether.
+            since_block: First block of the range; defaults to the first block mined after the chain was connected.
+                Negative values are relative to the latest block.
+            to_block: Last block of the range (inclusive); defaults to the latest block.
+
+        Returns:
+            Net balance changes aggregated over all transactions in the range.
+        """
+        from .transactions import (
+            _ERC20_TRANSFER_TOPIC,
+            TokenFlows,
+            _add_transfer_logs,
+        )
 
-    @check_connected
-    def set_next_block_timestamp(self, timestamp: int) -> None:
-        self._chain_interface.set_next_block_timestamp(timestamp)
+        if token is None and (since_block is None or to_block is None):
+            raise ValueError(
+                "since_block and to_block must be given to aggregate Ether flows, all transactions in the range are traced"
+            )
 
-    @check_connected
-    def set_min_gas_price(self, value: Union[int, str]) -> None:
-        if isinstance(value, str):
-            value = Wei.from_str(value)
-        self._chain_interface.set_min_gas_price(value)
-        self.gas_price = value
+        latest = self._chain_interface.get_block_number()
+        if since_block is None:
+            since_block = self._first_local_block
+        elif since_block < 0:
+            since_block = latest + since_block + 1
+        if to_block is None:
+            to_block = latest
+
+        if token is None:
+            flows = TokenFlows(self, None)
+            for block_number in range(since_block, to_block + 1):
+                for tx in self.blocks[block_number].txs:
+                    flows += tx.eth_flows()
+            return flows
+
+        if isinstance(token, Account):
+            token = token.address
+        flows = TokenFlows(self, Address(token))
+        if since_block <= to_block:
+            logs = self._chain_interface.get_logs(
+                from_block=since_block,
+                to_block=to_block,
+                address=[str(flows.token)],
+                topics=[["0x" + _ERC20_TRANSFER_TOPIC.hex()]],
+            )
+            logs.sort(
+                key=lambda l: (int(l["blockNumber"], 16), int(l["logIndex"], 16))
+            )
+            _add_transfer_logs(flows, logs)
+        return flows
 
-    @check_connected
-    def set_default_accounts(self, account: Union[Account, Address, str, None]) -> None:
-        if isinstance(account, Account):
-            if account.chain != self:
-                raise ValueError("Account is not from this chain")
-        elif account is not None:
-            account = Account(account, self)
+    def register_abi(
+        self,
+        address_or_metadata: Union[Account, Address, str, bytes],
+        abi: Union[List[Dict[str, Any]], Dict[str, Any]],
+    ) -> None:
+        """
+        Register an ABI used to decode events and errors not known to `pytypes` (e.g. of closed-source contracts).
+        Such events are decoded into `ExternalEvent` and errors into `ExternalError` instances.
+        Errors are registered using `register_error_abi` for all contracts, as revert data does not identify the reverting contract.
+
+        Args:
+            address_or_metadata: Address of the contract or CBOR metadata (last 53 bytes of the runtime code)
+                matching all contracts compiled from the same source.
+            abi: JSON ABI; compilation artifacts with an `abi` key are accepted as well.
+        """
+        if isinstance(address_or_metadata, Account):
+            key = address_or_metadata.address
+        elif isinstance(address_or_metadata, str):
+            key = Address(address_or_metadata)
+        elif isinstance(address_or_metadata, (bytes, bytearray)):
+            key = bytes(address_or_metadata)
+        else:
+            key = address_or_metadata
 
-        self._default_call_account = account
-        self._default_tx_account = account
-        self._default_estimate_account = account
-        self._default_access_list_account = account
+        if isinstance(abi, dict):
+            abi = abi["abi"]
 
-    @check_connected
-    def reset(self) -> None:
-        self._chain_interface.reset()
+        event_abis = self._registered_event_abis.setdefault(key, {})
+        for item in abi:
+            if item.get("type") == "event" and not item.get("anonymous", False):
+                event_abis[eth_utils.abi.event_abi_to_log_topic(item)] = item
+        register_error_abi(abi)
 
-    @check_connected
-    def update_accounts(self):
-        self._accounts = [
-            Account(acc, self) for acc in self._chain_interface.get_accounts()
+    def _find_registered_event_abi(
+        self,
+        address: Address,
+        selector: bytes,
+        block_number: int,
+        metadata_cache: Dict[Address, bytes],
+    ) -> Optional[Dict]:
+        if selector in self._registered_event_abis.get(address, {}):
+            return self._registered_event_abis[address][selector]
+
+        if any(isinstance(k, bytes) for k in self._registered_event_abis):
+            # code of an address cannot change within the events of a single tx
+            if address not in metadata_cache:
+                metadata_cache[address] = self._chain_interface.get_code(
+                    str(address), block_number
+                )[-53:]
+            metadata = metadata_cache[address]
+            if selector in self._registered_event_abis.get(metadata, {}):
+                return self._registered_event_abis[metadata][selector]
+        return None
+
+    @staticmethod
+    def _decode_abi_values(inputs: List[Dict], data: bytes) -> List[Any]:
+        types = [
+            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
+            for arg in inputs
         ]
-        self._accounts_set = set(self._accounts)
+        if len(types) == 0:
+            return []
+        return list(Abi.decode(types, data))
+
+    def _decode_registered_event(
+        self, abi: Dict, unknown_event: UnknownEvent
+    ) -> ExternalEvent:
+        arguments = {}
+        non_indexed = []
+        topic_index = 1
+        for i, arg in enumerate(abi["inputs"]):
+            name = arg["name"] or f"arg{i}"
+            if arg["indexed"]:
+                if arg["type"] in {"string", "bytes"} or arg["type"].endswith(
+                    ("]", "tuple")
+                ):
+                    arguments[name] = unknown_event.topics[topic_index]
+                else:
+                    arguments[name] = Abi.decode(
+                        [arg["type"]], unknown_event.topics[topic_index]
+                    )[0]
+                topic_index += 1
+            else:
+                arguments[name] = None
+                non_indexed.append((name, arg))
+
+        decoded = self._decode_abi_values(
+            [arg for _, arg in non_indexed], unknown_event.data
+        )
+        for (name, _), value in zip(non_indexed, decoded):
+            arguments[name] = value
+
+        external_event = ExternalEvent(abi["name"], arguments)
+        external_event.origin = unknown_event.origin
+        return external_event
 
     @check_connected
-    def mine(self, timestamp_change: Optional[Callable[[int], int]] = None) -> None:
-        if timestamp_change is not None:
-            block_info = self._chain_interface.get_block("latest")
-            assert "timestamp" in block_info
-            last_timestamp = int(block_info["timestamp"], 16)
-            timestamp = timestamp_change(last_timestamp)
-        else:
-            timestamp = None
+    def view(self) -> ChainView:
+        """
+        Returns:
+            Read-only handle of the chain pinned to the latest block, usable for view calls from other threads.
+        """
+        return ChainView(self, self._chain_interface.get_block_number())
 
-        self._chain_interface.mine(timestamp)
+    @check_connected
+    def call_raw(
+        self, fn: Callable[..., T], *args, max_gas: int = 2**31, **kwargs
+    ) -> T:
+        """
+        Call a contract function (e.g. `registry.getAll`) as a view call with a gas limit of `max_gas`,
+        ignoring the block gas limit, like node providers do for `eth_call`. Gas prices are set to zero
+        unless specified, so the caller does not need any balance to cover `max_gas`.
+
+        Returns:
+            Return value of the called function.
+        """
+        if max_gas <= 0:
+            raise ValueError("max_gas must be positive")
+
+        tx_type = kwargs.get("type", self._default_tx_type)
+        if tx_type in {2, 4}:
+            kwargs.setdefault("max_fee_per_gas", 0)
+            kwargs.setdefault("max_priority_fee_per_gas", 0)
+        else:
+            kwargs.setdefault("gas_price", 0)
+        return fn(*args, request_type="call", gas_limit=max_gas, **kwargs)
 
     @check_connected
-    def mine_many(
-        self, num_blocks: int, timestamp_change: Optional[int] = None
-    ) -> None:
-        self._chain_interface.mine_many(num_blocks, timestamp_change)
+    def find_block(
+        self,
+        condition: Callable[[ChainView], bool],
+        *,
+        start: int = 0,
+        end: Optional[int] = None,
+    ) -> Optional[Block]:
+        """
+        Binary search for the first block in the range `start`..`end` (inclusive, defaults to the latest block)
+        at which `condition` holds. The condition receives a [ChainView][wake.development.core.ChainView]
+        pinned to the evaluated block and must be monotonic, i.e. once true, it stays true in all later blocks.
+        Blocks before the fork block of a forked chain are queried from the forked network, which must
+        serve historical state (an archive node).
+
+        Returns:
+            First block satisfying the condition or `None` if the condition does not hold at `end`.
+        """
+        if end is None:
+            end = self._chain_interface.get_block_number()
+        if start < 0 or start > end:
+            raise ValueError(f"Invalid block range {start}..{end}")
+
+        if not condition(ChainView(self, end)):
+            return None
+
+        low, high = start, end
+        while low < high:
+            mid = (low + high) // 2
+            if condition(ChainView(self, mid)):
+                high = mid
+            else:
+                low = mid + 1
+        return self.blocks[low]
+
+    def _drop_snapshots_after(self, snapshot_id: str) -> None:
+        # reverting to a snapshot invalidates all snapshots taken after it
+        snapshot_ids = list(self._snapshots.keys())
+        for i in snapshot_ids[snapshot_ids.index(snapshot_id) + 1 :]:
+            del self._snapshots[i]
 
     @contextmanager
     def snapshot_and_revert(self):
@@ -2019,7 +5937,8 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
-    ) -> bytearray:
+        chain_id_override: Optional[int] = None,
+    ) -> Union[bytearray, memoryview]:
         ...
 
     @overload
@@ -2043,6 +5962,7 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
+        chain_id_override: Optional[int] = None,
     ) -> Contract:
         ...
 
@@ -2067,6 +5987,7 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
+        chain_id_override: Optional[int] = None,
     ) -> int:
         ...
 
@@ -2091,6 +6012,7 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
+        chain_id_override: Optional[int] = None,
     ) -> Tuple[Dict[Address, List[int]], int]:
         ...
 
@@ -2115,6 +6037,7 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
+        chain_id_override: Optional[int] = None,
     ) -> TransactionAbc[Contract]:
         ...
 
@@ -2138,6 +6061,7 @@ class Chain(ABC):
             Union[int, Literal["latest", "pending", "earliest", "safe", "finalized"]]
         ] = None,
         confirmations: Optional[int] = None,
+        chain_id_override: Optional[int] = None,
     ) -> Union[
         bytearray,
         Contract,
@@ -2163,11 +6087,116 @@ class Chain(ABC):
             type,
             block,
             confirmations,
+            chain_id_override,
+        )
+
+    @check_connected
+    def estimate_deploy(self, contract_type: Type[Contract], *args, **kwargs) -> int:
+        """
+        Estimate gas needed to deploy `contract_type` with constructor arguments `args`.
+        Library placeholders are linked and constructor arguments encoded exactly as in `contract_type.deploy`,
+        so the estimate reflects the final creation code.
+
+        Args:
+            contract_type: `pytypes` contract type to deploy.
+            *args: Constructor arguments.
+            **kwargs: Keyword arguments accepted by `contract_type.deploy`, e.g. `from_`, `value` or library addresses.
+
+        Returns:
+            Gas estimate returned by the connected node.
+        """
+        for kwarg in ("request_type", "return_tx", "confirmations", "chain"):
+            if kwarg in kwargs:
+                raise TypeError(
+                    f"estimate_deploy() got an unexpected keyword argument {kwarg!r}"
+                )
+
+        return contract_type.deploy(  # pyright: ignore reportGeneralTypeIssues
+            *args, request_type="estimate", chain=self, **kwargs
         )
 
+    def _check_fee_caps(self, params: TxParams) -> None:
+        if "maxFeePerGas" not in params:
+            return
+
+        max_fee = params["maxFeePerGas"]
+        priority_fee = params.get("maxPriorityFeePerGas")
+        if priority_fee is not None and priority_fee > max_fee:
+            raise FeeTooLow(max_fee, priority_fee, None)
+
+        base_fee = self._blocks["pending"].base_fee_per_gas
+        if base_fee is not None and max_fee < base_fee:
+            raise FeeTooLow(max_fee, priority_fee, base_fee)
+
     def _update_nonce(self, address: Address, nonce: int) -> None:
         self._nonces[address] = nonce
 
+    def _validate_authorization_list(
+        self, sender: Address, authorization_list: List[Authorization]
+    ) -> Tuple[List[Authorization], Dict[Address, int]]:
+        """
+        Check that all EIP-7702 authorizations would be applied by the chain.
+        Authorizations signed by the sender with its current nonce are signed again with the current nonce + 1
+        if the private key of the sender is known.
+        Returns the (adjusted) authorization list and the nonces of the authorities after the transaction is executed.
+        """
+        # sender nonce is incremented before the authorization list is processed
+        nonces: Dict[Address, int] = {sender: self._nonces[sender] + 1}
+        ret: List[Authorization] = []
+
+        for authorization in authorization_list:
+            if authorization.chain_id not in {0, self._chain_id}:
+                raise InvalidAuthorizationError(
+                    authorization,
+                    f"Authorization chain ID {authorization.chain_id} does not match chain ID {self._chain_id}",
+                )
+
+            authority = authorization.authority
+            expected_nonce = nonces.get(authority, self._nonces[authority])
+            if (
+                authorization.nonce == expected_nonce - 1
+                and authority == sender
+                and Account(sender, self).has_signer
+            ):
+                # self-sponsored authorization signed with the current nonce
+                authorization = Account(sender, self).sign_authorization(
+                    authorization.address,
+                    chain_id=authorization.chain_id,
+                    nonce=expected_nonce,
+                )
+            elif authorization.nonce != expected_nonce:
+                if authority == sender and authorization.nonce == expected_nonce - 1:
+                    raise InvalidAuthorizationError(
+                        authorization,
+                        f"Authorization signed by transaction sender {sender} must use nonce {expected_nonce} (current nonce + 1), "
+                        "the private key of the sender is not known to sign it again",
+                    )
+                raise InvalidAuthorizationError(
+                    authorization,
+                    f"Authorization nonce {authorization.nonce} does not match nonce {expected_nonce} of authority {authority}",
+                )
+            nonces[authority] = expected_nonce + 1
+            ret.append(authorization)
+
+        return ret, nonces
+
+    def _encode_arguments(self, abi: Dict, arguments: Iterable) -> bytes:
+        inputs, types = _get_abi_types(abi["inputs"])
+        arguments = [self._convert_to_web3_type(arg) for arg in arguments]
+        for i, arg_abi in enumerate(inputs[: len(arguments)]):
+            arguments[i] = self._resolve_ens_arguments(arg_abi, arguments[i])
+
+        if self._validate_arguments:
+            for i, (arg_abi, arg) in enumerate(zip(inputs, arguments)):
+                _validate_abi_value(arg_abi, arg, arg_abi.get("name") or f"arg{i}")
+        else:
+            types = [
+                eth_utils.abi.collapse_if_tuple(_widen_enum_abi(arg_abi))
+                for arg_abi in inputs
+            ]
+
+        return Abi.encode(types, arguments)
+
     def _convert_to_web3_type(self, value: Any) -> Any:
         if dataclasses.is_dataclass(value):
             return tuple(
@@ -2180,7 +6209,7 @@ class Chain(ABC):
             return tuple(self._convert_to_web3_type(v) for v in value)
         elif isinstance(value, Account):
             if value.chain != self:
-                raise ValueError("Account must belong to this chain")
+                raise ChainMismatchError(value, self)
             return str(value.address)
         elif isinstance(value, Address):
             return str(value)
@@ -2197,10 +6226,7 @@ class Chain(ABC):
             raise ValueError(f"Unknown selector: {selector.hex()}")
         abi = hardhat_console.abis[selector]
 
-        output_types = [
-            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-            for arg in fix_library_abi(abi)
-        ]
+        _, output_types = _get_abi_types(abi)
         decoded_data = list(Abi.decode(output_types, data[4:]))
         for i in range(len(decoded_data)):
             if abi[i]["type"] == "address":
@@ -2262,7 +6288,7 @@ class Chain(ABC):
             )
         elif dataclasses.is_dataclass(expected_type):
             assert isinstance(value, tuple)
-            resolved_types = get_type_hints(
+            resolved_types = _get_type_hints(
                 expected_type  # pyright: ignore reportGeneralTypeIssues
             )
             field_types = [
@@ -2294,6 +6320,32 @@ class Chain(ABC):
 
         selector = revert_data[0:4]
         if selector not in errors:
+            for path in get_config().general.error_abis - _loaded_error_abis:
+                register_error_abi(path)
+                _loaded_error_abis.add(path)
+
+            if selector in external_errors:
+                return self._process_external_error(tx, revert_data)
+
+            registered_abi = external_error_abis.get(selector)
+            if registered_abi is not None:
+                from .transactions import ExternalError
+
+                decoded = self._decode_abi_values(
+                    registered_abi["inputs"], revert_data[4:]
+                )
+                e = ExternalError(
+                    registered_abi["name"],
+                    {
+                        arg["name"] or f"arg{i}": value
+                        for i, (arg, value) in enumerate(
+                            zip(registered_abi["inputs"], decoded)
+                        )
+                    },
+                )
+                e.tx = tx
+                return e
+
             e = UnknownTransactionRevertedError(revert_data)
             e.tx = tx
             raise e from None
@@ -2333,18 +6385,26 @@ class Chain(ABC):
             obj = getattr(obj, attr)
         abi = obj._abi
 
-        types = [
-            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-            for arg in fix_library_abi(abi["inputs"])
-        ]
+        _, types = _get_abi_types(abi["inputs"])
         decoded = Abi.decode(types, revert_data[4:])
         generated_error = self._convert_from_web3_type(tx, decoded, obj)
         generated_error.tx = tx
         return generated_error
 
+    def _process_external_error(
+        self, tx: Optional[TransactionAbc], revert_data: bytes
+    ) -> TransactionRevertedError:
+        error_type = external_errors[revert_data[0:4]]
+        _, types = _get_abi_types(error_type._abi["inputs"])
+        decoded = Abi.decode(types, revert_data[4:])
+        generated_error = self._convert_from_web3_type(tx, decoded, error_type)
+        generated_error.tx = tx
+        return generated_error
+
     def _process_events(self, tx: TransactionAbc) -> list:
         fqn_overrides: ChainMap[Address, Optional[str]] = ChainMap()
         generated_events = []
+        metadata_cache: Dict[Address, bytes] = {}
 
         # process fqn_overrides for all txs before this one in the same block
         for i in range(tx.tx_index):
@@ -2390,7 +6450,15 @@ class Chain(ABC):
             selector = topics[0]
 
             if selector not in events:
-                generated_events.append(unknown_event)
+                registered_abi = self._find_registered_event_abi(
+                    address, selector, tx.block.number, metadata_cache
+                )
+                if registered_abi is not None:
+                    generated_events.append(
+                        self._decode_registered_event(registered_abi, unknown_event)
+                    )
+                else:
+                    generated_events.append(unknown_event)
                 continue
 
             if len({source for source in events[selector].values()}) > 1:
@@ -2452,7 +6520,8 @@ class Chain(ABC):
 
             decoded_indexed = []
 
-            for input in fix_library_abi(abi["inputs"]):
+            inputs, input_types = _get_abi_types(abi["inputs"])
+            for input, input_type in zip(inputs, input_types):
                 if input["indexed"]:
                     if (
                         input["type"] in {"string", "bytes"}
@@ -2470,7 +6539,7 @@ class Chain(ABC):
                     )
                     topic_index += 1
                 else:
-                    types.append(eth_utils.abi.collapse_if_tuple(input))
+                    types.append(input_type)
             decoded = list(
                 Abi.decode(
                     types,
@@ -2492,15 +6561,32 @@ class Chain(ABC):
             generated_event.origin = Account(address, tx.chain)
             generated_events.append(generated_event)
 
-        return generated_events
+        frames: List[Optional[Tuple[int, ...]]] = [None] * len(generated_events)
+        if self._event_frames:
+            tx._fetch_debug_trace_transaction()
+            log_frames = _get_log_frames(
+                tx._debug_trace_transaction  # pyright: ignore reportGeneralTypeIssues
+            )
+            # leave frames unset if logs cannot be matched (e.g. unsupported trace format)
+            if len(log_frames) == len(generated_events):
+                frames = log_frames
+        for event, frame in zip(generated_events, frames):
+            event.frame = frame
+
+        return [
+            event
+            for event, log in zip(generated_events, logs)
+            if not self._is_log_ignored(log)
+        ]
 
     def _process_return_data(
         self, tx: Optional[TransactionAbc], output: bytes, abi: Dict, return_type: Type
     ):
-        output_types = [
-            eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-            for arg in fix_library_abi(abi["outputs"])
-        ]
+        _, output_types = _get_abi_types(abi["outputs"])
+        if self._zero_copy_returns and output_types == ["bytes"]:
+            view = _decode_bytes_view(output)
+            if view is not None:
+                return view
         decoded_data = Abi.decode(output_types, output)
         if isinstance(decoded_data, (list, tuple)) and len(decoded_data) == 1:
             decoded_data = decoded_data[0]
@@ -2567,6 +6653,9 @@ class Chain(ABC):
             else:
                 raise e from None
         except Exception:
+            halt = self._process_call_halt(e)
+            if halt is not None:
+                return halt
             raise e from None
 
         if revert_data.startswith("0x"):
@@ -2574,6 +6663,22 @@ class Chain(ABC):
 
         return self._process_revert_data(None, bytes.fromhex(revert_data))
 
+    def _process_call_halt(self, e: JsonRpcError) -> Optional[TransactionRevertedError]:
+        from .transactions import Halt, HaltReasonEnum, parse_halt_reason
+
+        try:
+            message = e.data["message"]
+        except Exception:
+            return None
+        if not isinstance(message, str):
+            return None
+
+        # unrecognized messages are usually node errors unrelated to the execution (e.g. insufficient funds for gas)
+        reason = parse_halt_reason(message)
+        if reason is None or reason == HaltReasonEnum.UNKNOWN:
+            return None
+        return Halt(reason=reason, message=message)
+
     def _send_transaction(
         self, tx_params: TxParams, from_: Optional[Union[Account, Address, str]]
     ) -> str:
@@ -2585,15 +6690,72 @@ class Chain(ABC):
             tx_params.pop("type", None)
             tx_params.pop("accessList", None)
 
+        if self._strict_nonces:
+            sender = Address(tx_params["from"])
+            expected_nonce = self._chain_interface.get_transaction_count(
+                str(sender), "pending"
+            )
+            if tx_params["nonce"] > expected_nonce:
+                raise NonceGap(sender, tx_params["nonce"], expected_nonce, tx_params)
+            elif tx_params["nonce"] < expected_nonce:
+                raise NonceReuse(sender, tx_params["nonce"], expected_nonce, tx_params)
+
+        if self._fork is not None and tx_params.get("to") is None:
+            self._check_create_collision(tx_params)
+
         self._confirm_transaction(tx_params)
 
+        try:
+            tx_hash = self._submit_transaction(tx_params, from_)
+        except (ExecutionTimeout, SigningTimeout):
+            # the node may or may not have accepted (and mined) the transaction,
+            # fetch the nonce and the latest block again on the next use
+            sender = Address(tx_params["from"])
+            if sender in self._nonces:
+                del self._nonces[sender]
+            self._blocks._invalidate_head()
+            raise
+        self._txs.register_tx(tx_hash)
+
+        return tx_hash
+
+    def _check_create_collision(self, tx_params: TxParams) -> None:
+        from .utils import get_create_address
+
+        deployer = Address(tx_params["from"])
+        address = get_create_address(deployer, tx_params["nonce"])
+        if (
+            len(self._chain_interface.get_code(str(address))) == 0
+            and self._chain_interface.get_transaction_count(str(address)) == 0
+        ):
+            return
+
+        try:
+            contract = get_fqn_from_creation_code(tx_params.get("data", b""))[0]
+        except ValueError:
+            contract = None
+
+        raise CreateCollision(
+            address,
+            deployer,
+            tx_params["nonce"],
+            self._chain_interface.get_transaction_count(str(deployer), "pending"),
+            contract,
+            get_fqn_from_address(address, "latest", self),
+        )
+
+    def _submit_transaction(
+        self, tx_params: TxParams, from_: Optional[Union[Account, Address, str]]
+    ) -> str:
         if self.require_signed_txs:
-            if isinstance(from_, (Account, Address)):
-                key = from_.private_key
+            if isinstance(from_, Account):
+                signer = from_.address
+            elif isinstance(from_, Address):
+                signer = from_
             elif from_ is None and self._default_tx_account is not None:
-                key = self._default_tx_account.private_key
+                signer = self._default_tx_account.address
             else:
-                key = None
+                signer = None
 
             tx_params["from"] = eth_utils.address.to_checksum_address(tx_params["from"])
 
@@ -2608,10 +6770,24 @@ class Chain(ABC):
                         tx_hash = e.args[0]["data"]["txHash"]
                     except Exception:
                         raise e from None
-            elif key is not None:
-                signed_tx = bytes(
-                    eth_account.Account.sign_transaction(tx_params, key).rawTransaction
-                )
+            elif signer is not None and Account(signer, self).has_signer:
+                sign_params = tx_params
+                if "chainId" not in tx_params and not self._allow_unprotected_txs:
+                    # sign legacy transactions with EIP-155 replay protection, tx params are kept as built
+                    sign_params = cast(
+                        TxParams, {**tx_params, "chainId": self._chain_id}
+                    )
+                self._check_replay_protection(sign_params)
+
+                with Account(signer, self)._private_keys().use(signer) as key:
+                    if sign_params.get("type") == 4:
+                        signed_tx = _sign_eip7702_transaction(sign_params, key)
+                    else:
+                        signed_tx = bytes(
+                            eth_account.Account.sign_transaction(
+                                sign_params, key
+                            ).rawTransaction
+                        )
                 try:
                     tx_hash = self._chain_interface.send_raw_transaction(signed_tx)
                 except (ValueError, JsonRpcError) as e:
@@ -2647,8 +6823,6 @@ class Chain(ABC):
                             raise e
                     self._update_nonce(sender.address, tx_params["nonce"] + 1)
 
-        self._txs.register_tx(tx_hash)
-
         return tx_hash
 
     @check_connected
@@ -2680,11 +6854,48 @@ class Chain(ABC):
 
         # deploy
         if "to" not in params:
+            if self._zero_copy_returns:
+                return memoryview(output)
             return bytearray(output)
 
         assert abi is not None
         return self._process_return_data(None, output, abi, return_type)
 
+    @check_connected
+    def _call_result(
+        self,
+        abi: Optional[Dict],
+        arguments: Iterable,
+        params: TxParams,
+        return_type: Type,
+        block: Union[int, str],
+    ) -> CallResult:
+        tx_params = self._build_transaction(RequestType.CALL, params, arguments, abi)
+        try:
+            ret = self._chain_interface.debug_trace_call(
+                tx_params,
+                block,
+                {"tracer": "callTracer", "tracerConfig": {"onlyTopCall": True}},
+            )
+        except JsonRpcError as e:
+            raise self._process_call_revert(e) from None
+
+        output = bytes.fromhex(ret.get("output", "0x")[2:])
+        if "error" in ret:
+            raise self._process_revert_data(None, output) from None
+        gas_used = int(ret["gasUsed"], 16)
+
+        # deploy
+        if "to" not in params:
+            return CallResult(bytearray(output), output, gas_used)
+
+        assert abi is not None
+        return CallResult(
+            self._process_return_data(None, output, abi, return_type),
+            output,
+            gas_used,
+        )
+
     @check_connected
     def _estimate(
         self,
@@ -2697,10 +6908,46 @@ class Chain(ABC):
             RequestType.ESTIMATE, params, arguments, abi
         )
         try:
-            return self._chain_interface.estimate_gas(tx_params, block)
+            return self._estimate_gas(tx_params, block)
         except JsonRpcError as e:
             raise self._process_call_revert(e) from None
 
+    def _estimate_gas(
+        self,
+        tx_params: TxParams,
+        block: Union[int, str],
+        exact: Optional[bool] = None,
+    ) -> int:
+        estimate = self._chain_interface.estimate_gas(tx_params, block)
+        if exact is None:
+            exact = self._exact_gas_estimates
+        if not exact:
+            return estimate
+
+        def succeeds(gas_limit: int) -> bool:
+            try:
+                self._chain_interface.call({**tx_params, "gas": gas_limit}, block)
+                return True
+            except JsonRpcError:
+                return False
+
+        # invariant: `low` fails (or is below the intrinsic cost), `high` succeeds
+        low = 21_000 - 1
+        high = estimate
+        if not succeeds(high):
+            low = high
+            high = self.block_gas_limit
+            # re-raise the original revert if the transaction fails even with the block gas limit
+            self._chain_interface.call({**tx_params, "gas": high}, block)
+
+        while high - low > 1:
+            mid = (low + high) // 2
+            if succeeds(mid):
+                high = mid
+            else:
+                low = mid
+        return high
+
     @check_connected
     def _access_list(
         self,
@@ -2714,10 +6961,7 @@ class Chain(ABC):
         )
         try:
             response = self._chain_interface.create_access_list(tx_params, block)
-            return {
-                Address(e["address"]): [int(s, 16) for s in e["storageKeys"]]
-                for e in response["accessList"]
-            }, int(response["gasUsed"], 16)
+            return AccessList(response["accessList"]), int(response["gasUsed"], 16)
         except JsonRpcError as e:
             raise self._process_call_revert(e) from None
 
@@ -2731,37 +6975,79 @@ class Chain(ABC):
         return_type: Type,
         confirmations: Optional[int],
         from_: Optional[Union[Account, Address, str]],
+        chain_id_override: Optional[int] = None,
     ) -> Any:
+        self._check_chain_id_override(chain_id_override, confirmations)
         tx_params = self._build_transaction(RequestType.TX, params, arguments, abi)
+        if "to" not in tx_params:
+            self._check_initcode_size(return_type, tx_params["data"])
+        if chain_id_override is not None and "chainId" in tx_params:
+            tx_params["chainId"] = chain_id_override
 
-        tx_hash = self._send_transaction(tx_params, from_)
+        # with automine disabled, a failed deployment would still be included in the pending block,
+        # occupying the nonce and gas, so simulate it first and do not send it at all if it reverts
+        if (
+            "to" not in tx_params
+            and isinstance(
+                self._chain_interface, (AnvilChainInterface, HardhatChainInterface)
+            )
+            and not self._get_automine()
+        ):
+            error = None
+            try:
+                self._chain_interface.call(tx_params, "pending")
+            except JsonRpcError as e:
+                try:
+                    error = self._process_call_revert(e)
+                except JsonRpcError:
+                    # not a revert (e.g. insufficient funds), sending the transaction reports the error as usual
+                    pass
 
-        if "type" not in tx_params:
-            from wake.development.transactions import LegacyTransaction
+            if error is not None:
+                try:
+                    contract = get_fqn_from_creation_code(tx_params["data"])[0]
+                except ValueError:
+                    contract = None
+                raise DeploymentFailed(
+                    contract, Address(tx_params["from"]), tx_params["nonce"], error
+                ) from None
 
-            tx_type = LegacyTransaction[return_type]
-        elif tx_params["type"] == 1:
-            from wake.development.transactions import Eip2930Transaction
+        # only signing and mining use the overridden chain ID
+        with self._chain_id_override(chain_id_override):
+            tx_hash = self._send_transaction(tx_params, from_)
 
-            tx_type = Eip2930Transaction[return_type]
-        elif tx_params["type"] == 2:
-            from wake.development.transactions import Eip1559Transaction
+            if "type" not in tx_params:
+                from wake.development.transactions import LegacyTransaction
 
-            tx_type = Eip1559Transaction[return_type]
-        else:
-            raise ValueError(f"Unknown transaction type {tx_params['type']}")
+                tx_type = LegacyTransaction[return_type]
+            elif tx_params["type"] == 1:
+                from wake.development.transactions import Eip2930Transaction
 
-        tx = tx_type(
-            tx_hash,
-            tx_params,
-            abi,
-            return_type,
-            self,
-        )
+                tx_type = Eip2930Transaction[return_type]
+            elif tx_params["type"] == 2:
+                from wake.development.transactions import Eip1559Transaction
 
-        if confirmations != 0:
-            tx.wait(confirmations)
+                tx_type = Eip1559Transaction[return_type]
+            elif tx_params["type"] == 4:
+                from wake.development.transactions import Eip7702Transaction
+
+                tx_type = Eip7702Transaction[return_type]
+            else:
+                raise ValueError(f"Unknown transaction type {tx_params['type']}")
+
+            tx = tx_type(
+                tx_hash,
+                tx_params,
+                abi,
+                return_type,
+                self,
+            )
+            tx._gas_estimate = self._last_gas_estimate
+
+            if confirmations != 0:
+                tx.wait(confirmations)
 
+        if confirmations != 0:
             coverage_handler = get_coverage_handler()
             if coverage_handler is not None:
                 tx._fetch_debug_trace_transaction()
@@ -2771,8 +7057,15 @@ class Chain(ABC):
                     tx._debug_trace_transaction,  # pyright: ignore reportGeneralTypeIssues
                 )
 
+            if self._detect_uninitialized_reads:
+                self._process_uninitialized_reads(tx)
+
             if self.tx_callback is not None:
                 self.tx_callback(tx)
+            if self._batched_tx_callback is not None:
+                self._add_to_tx_batch(tx)
+
+            self._run_block_mined_hooks()
 
             if tx.error is not None:
                 raise tx.error
@@ -2783,6 +7076,110 @@ class Chain(ABC):
         return tx.return_value
 
 
+class AddressBook:
+    """
+    Named addresses of a chain loaded from the `addresses` config namespace for the chain ID of the chain
+    (or of the forked chain in case of a fork). Entries are returned as `Account` instances bound to the chain
+    and can be accessed both as attributes (`chain.addresses.USDC`) and items (`chain.addresses["USDC"]`).
+    """
+
+    _chain: Chain
+    _entries: Dict[str, Address]
+
+    def __init__(self, chain: Chain, entries: Mapping[str, Union[Address, str]]):
+        self._chain = chain
+        self._entries = {name: Address(address) for name, address in entries.items()}
+
+    def __getattr__(self, name: str) -> Account:
+        if name.startswith("_"):
+            raise AttributeError(name)
+        try:
+            return self[name]
+        except KeyError:
+            raise AttributeError(
+                f"Address {name} not found in the address book of chain {self._chain.chain_id}"
+            ) from None
+
+    def __getitem__(self, name: str) -> Account:
+        return Account(self._entries[name], self._chain)
+
+    def __setitem__(self, name: str, address: Union[Account, Address, str]) -> None:
+        if isinstance(address, Account):
+            if address.chain != self._chain:
+                raise ChainMismatchError(address, self._chain)
+            address = address.address
+        self._entries[name] = Address(address)
+
+    def __contains__(self, name: str) -> bool:
+        return name in self._entries
+
+    def __iter__(self) -> Iterator[str]:
+        return iter(self._entries)
+
+    def __len__(self) -> int:
+        return len(self._entries)
+
+    def __repr__(self) -> str:
+        return f"AddressBook({', '.join(self._entries)})"
+
+
+class ChainView:
+    """
+    Read-only handle of a chain pinned to a block number. All requests are performed against the pinned block,
+    so that the results are not affected by transactions sent from other threads in the meantime.
+    Reverting the chain past the pinned block invalidates the view.
+    """
+
+    _chain: Chain
+    _block_number: int
+
+    def __init__(self, chain: Chain, block_number: int):
+        self._chain = chain
+        self._block_number = block_number
+
+    @property
+    def chain(self) -> Chain:
+        return self._chain
+
+    @property
+    def block_number(self) -> int:
+        return self._block_number
+
+    def call(self, fn: Callable[..., T], *args, **kwargs) -> T:
+        """
+        Call a contract function (e.g. `token.balanceOf`) as a view call at the pinned block.
+        The call does not modify any state of the chain, so `chain_id_override` is not supported.
+        """
+        if kwargs.get("chain_id_override") is not None:
+            raise ValueError("chain_id_override cannot be specified for view calls")
+
+        chain_was = getattr(_view_requests, "chain", None)
+        _view_requests.chain = self._chain
+        try:
+            return fn(*args, request_type="call", block=self._block_number, **kwargs)
+        finally:
+            _view_requests.chain = chain_was
+
+    def balance(self, account: Union[Account, Address, str]) -> Wei:
+        if isinstance(account, Account):
+            account = account.address
+        return Wei(
+            self._chain.chain_interface.get_balance(str(account), self._block_number)
+        )
+
+    def code(self, account: Union[Account, Address, str]) -> bytes:
+        if isinstance(account, Account):
+            account = account.address
+        return self._chain.chain_interface.get_code(str(account), self._block_number)
+
+    def storage_at(self, account: Union[Account, Address, str], slot: int) -> bytes:
+        if isinstance(account, Account):
+            account = account.address
+        return self._chain.chain_interface.get_storage_at(
+            str(account), slot, self._block_number
+        )
+
+
 @contextmanager
 def _signer_account(sender: Account):
     chain = sender.chain
@@ -2845,11 +7242,14 @@ def get_fqn_from_address(
     addr: Address, block_number: Union[int, str], chain: Chain
 ) -> Optional[str]:
     code = chain.chain_interface.get_code(str(addr), block_number)
+    if addr in chain._fqn_overrides:
+        code_hash, fqn = chain._fqn_overrides[addr]
+        if keccak.new(data=code, digest_bits=256).digest() == code_hash:
+            return fqn
     metadata = code[-53:]
     if metadata in contracts_by_metadata:
         return contracts_by_metadata[metadata]
-    else:
-        return None
+    return None
 
 
 def get_contract_from_fqn(fqn: str):
@@ -3027,6 +7427,369 @@ def process_debug_trace_for_revert(
     return last_revert_origin
 
 
+@dataclasses.dataclass(frozen=True)
+class SourceLocation:
+    path: Path
+    line: int
+
+    def __str__(self) -> str:
+        return f"{self.path}:{self.line}"
+
+
+@dataclasses.dataclass(frozen=True)
+class _RevertOrigin:
+    fqn: Optional[str]
+    is_create: bool
+    # first 4 bytes of the calldata of the reverted call, `None` for contract creations and shorter calldata
+    selector: Optional[bytes]
+    location: Optional[SourceLocation]
+
+
+def _process_debug_trace_for_revert_origin(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+) -> Optional[_RevertOrigin]:
+    # reverts bubbled up unchanged through the call stack are attributed to the innermost call
+    if tx.to is None:
+        origin = get_fqn_from_creation_code(tx.data)[0]
+    else:
+        origin = get_fqn_from_address(tx.to.address, tx.block.number - 1, tx.chain)
+
+    fqns: List[Optional[str]] = [origin]
+    trace_is_create: List[bool] = [tx.to is None]
+    selectors: List[Optional[bytes]] = [
+        bytes(tx.data[:4]) if tx.to is not None and len(tx.data) >= 4 else None
+    ]
+    # origin and revert data of the last reverted call returning into the current frame
+    child_revert: Optional[Tuple[_RevertOrigin, bytes]] = None
+
+    for i, trace in enumerate(debug_trace["structLogs"]):
+        if i > 0:
+            prev_trace = debug_trace["structLogs"][i - 1]
+            if (
+                prev_trace["op"] in {"CALL", "CALLCODE", "DELEGATECALL", "STATICCALL"}
+                and prev_trace["depth"] == trace["depth"]
+            ):
+                # precompiled contract was called in the previous trace
+                fqns.pop()
+                trace_is_create.pop()
+                selectors.pop()
+
+        if trace["op"] in {"CALL", "CALLCODE", "DELEGATECALL", "STATICCALL"}:
+            addr = Address(int(trace["stack"][-2], 16))
+            fqns.append(get_fqn_from_address(addr, tx.block.number - 1, tx.chain))
+            trace_is_create.append(False)
+            if trace["op"] in {"CALL", "CALLCODE"}:
+                args_offset = int(trace["stack"][-4], 16)
+                args_size = int(trace["stack"][-5], 16)
+            else:
+                args_offset = int(trace["stack"][-3], 16)
+                args_size = int(trace["stack"][-4], 16)
+            selectors.append(
+                bytes(read_from_memory(args_offset, 4, trace["memory"]))
+                if args_size >= 4
+                else None
+            )
+            child_revert = None
+        elif trace["op"] in {"CREATE", "CREATE2"}:
+            offset = int(trace["stack"][-2], 16)
+            length = int(trace["stack"][-3], 16)
+            creation_code = read_from_memory(offset, length, trace["memory"])
+            fqns.append(get_fqn_from_creation_code(creation_code)[0])
+            trace_is_create.append(True)
+            selectors.append(None)
+            child_revert = None
+        elif trace["op"] in {"INVALID", "REVERT"}:
+            pc = trace["pc"]
+            fqn = fqns.pop()
+            is_create = trace_is_create.pop()
+            selector = selectors.pop()
+
+            if trace["op"] == "REVERT":
+                offset = int(trace["stack"][-1], 16)
+                length = int(trace["stack"][-2], 16)
+                revert_data = bytes(read_from_memory(offset, length, trace["memory"]))
+            else:
+                revert_data = b""
+
+            index = (
+                contracts_revert_constructor_locations
+                if is_create
+                else contracts_revert_locations
+            )
+            if fqn in index and pc in index[fqn]:
+                path, line = index[fqn][pc]
+                location = SourceLocation(Path(path), line)
+            else:
+                location = None
+
+            if (
+                child_revert is not None
+                and child_revert[1] == revert_data
+                and trace["op"] == "REVERT"
+            ):
+                # revert data bubbled up from the reverted call
+                revert_origin = child_revert[0]
+            else:
+                revert_origin = _RevertOrigin(fqn, is_create, selector, location)
+            child_revert = (revert_origin, revert_data)
+        elif trace["op"] in {"RETURN", "STOP", "SELFDESTRUCT"}:
+            fqns.pop()
+            trace_is_create.pop()
+            selectors.pop()
+            child_revert = None
+
+    if (
+        len(debug_trace["structLogs"]) == 0
+        or debug_trace["structLogs"][-1]["op"] not in {"INVALID", "REVERT"}
+        or child_revert is None
+    ):
+        # e.g. out of gas in the top-level call
+        return None
+    return child_revert[0]
+
+
+def process_debug_trace_for_revert_location(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+) -> Optional[SourceLocation]:
+    """
+    Find the source location of the REVERT/INVALID opcode the transaction revert originates from.
+    Reverts bubbled up unchanged through the call stack are attributed to the innermost call.
+    """
+    revert_origin = _process_debug_trace_for_revert_origin(tx, debug_trace)
+    return revert_origin.location if revert_origin is not None else None
+
+
+def process_debug_trace_for_panic_location(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+) -> Optional[str]:
+    """
+    Find the contract and function name (`Contract.function`) of the call the transaction revert originates from,
+    in the format of `Panic.location`.
+    """
+    revert_origin = _process_debug_trace_for_revert_origin(tx, debug_trace)
+    if revert_origin is None or revert_origin.fqn is None:
+        return None
+
+    contract_name = revert_origin.fqn.split(":")[-1]
+    fn_name = None
+    if revert_origin.is_create:
+        fn_name = "constructor"
+    elif revert_origin.fqn in contracts_by_fqn:
+        module_name, attrs = contracts_by_fqn[revert_origin.fqn]
+        obj = getattr(importlib.import_module(module_name), attrs[0])
+        for attr in attrs[1:]:
+            obj = getattr(obj, attr)
+        if revert_origin.selector is not None and revert_origin.selector in obj._abi:
+            fn_name = obj._abi[revert_origin.selector]["name"]
+        elif "fallback" in obj._abi:
+            fn_name = "fallback"
+    return f"{contract_name}.{fn_name or '???'}"
+
+
+def process_debug_trace_for_uninitialized_reads(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+    written_slots: Set[Tuple[Address, int]],
+) -> List[Tuple[Account, int]]:
+    """
+    Find SLOADs returning zero from storage slots not written before (i.e. not in `written_slots` and not
+    written earlier in the transaction). Slots written by the transaction and not reverted are added to `written_slots`.
+    """
+    # storage contexts are single-item lists shared by delegatecalls,
+    # address of a contract being created is known only after the CREATE returns
+    if tx.to is None:
+        root: List[Optional[Address]] = [
+            Address(
+                tx._tx_receipt["contractAddress"]  # pyright: ignore reportOptionalSubscript
+            )
+            if tx._tx_receipt is not None
+            and tx._tx_receipt.get("contractAddress") is not None
+            else None
+        ]
+    else:
+        root = [tx.to.address]
+    contexts: List[List[Optional[Address]]] = [root]
+    writes: List[List[Tuple[List[Optional[Address]], int]]] = [[]]
+    pending_creates: List[Tuple[List[Optional[Address]], int]] = []
+    written_in_tx: Set[Tuple[int, int]] = set()
+    reads: List[Tuple[List[Optional[Address]], int]] = []
+    pending_read: Optional[Tuple[List[Optional[Address]], int]] = None
+    prev_op: Optional[str] = None
+
+    for trace in debug_trace["structLogs"]:
+        if pending_read is not None:
+            if int(trace["stack"][-1], 16) == 0:
+                reads.append(pending_read)
+            pending_read = None
+
+        if len(pending_creates) > 0 and pending_creates[-1][1] == trace["depth"]:
+            context, _ = pending_creates.pop()
+            created = int(trace["stack"][-1], 16)
+            context[0] = Address(created) if created != 0 else None
+
+        while len(contexts) > trace["depth"]:
+            contexts.pop()
+            frame_writes = writes.pop()
+            if prev_op in {"RETURN", "STOP", "SELFDESTRUCT"}:
+                writes[-1].extend(frame_writes)
+
+        op = trace["op"]
+        if op in {"CALL", "STATICCALL"}:
+            contexts.append([Address(int(trace["stack"][-2], 16))])
+            writes.append([])
+        elif op in {"DELEGATECALL", "CALLCODE"}:
+            contexts.append(contexts[-1])
+            writes.append([])
+        elif op in {"CREATE", "CREATE2"}:
+            context = [None]
+            contexts.append(context)
+            writes.append([])
+            pending_creates.append((context, trace["depth"]))
+        elif op == "SLOAD":
+            slot = int(trace["stack"][-1], 16)
+            context = contexts[-1]
+            if (id(context), slot) not in written_in_tx and (
+                context[0] is None or (context[0], slot) not in written_slots
+            ):
+                pending_read = (context, slot)
+        elif op == "SSTORE":
+            slot = int(trace["stack"][-1], 16)
+            writes[-1].append((contexts[-1], slot))
+            written_in_tx.add((id(contexts[-1]), slot))
+
+        prev_op = op
+
+    if tx.status == 1:
+        for context, slot in writes[0]:
+            if context[0] is not None:
+                written_slots.add((context[0], slot))
+
+    ret: List[Tuple[Account, int]] = []
+    seen = set()
+    for context, slot in reads:
+        if context[0] is None or (context[0], slot) in seen:
+            continue
+        seen.add((context[0], slot))
+        ret.append((Account(context[0], tx.chain), slot))
+    return ret
+
+
+def process_debug_trace_for_precompile_calls(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+    addresses: Iterable[Address],
+) -> List[Tuple[Address, bytes]]:
+    """
+    Collect successful calls of the given precompiled contracts made by the transaction together with their input data.
+    Calls in reverted frames are included, as the gas spent by them is charged even though the frame reverts.
+    Failed calls are excluded, as they consume all forwarded gas instead of the precompile price.
+    """
+    addresses = set(addresses)
+    calls = []
+    if tx.to is not None and tx.to.address in addresses and tx.status == 1:
+        calls.append((tx.to.address, bytes(tx.data)))
+
+    struct_logs = debug_trace["structLogs"]
+    for i, trace in enumerate(struct_logs):
+        op = trace["op"]
+        if op not in {"CALL", "CALLCODE", "DELEGATECALL", "STATICCALL"}:
+            continue
+        address = Address(int(trace["stack"][-2], 16))
+        if address not in addresses:
+            continue
+        # precompiles do not execute any steps, the next step pushes the success flag in the calling frame
+        if i + 1 < len(struct_logs):
+            next_trace = struct_logs[i + 1]
+            if (
+                next_trace["depth"] == trace["depth"]
+                and int(next_trace["stack"][-1], 16) == 0
+            ):
+                continue
+
+        if op in {"CALL", "CALLCODE"}:
+            args_offset = int(trace["stack"][-4], 16)
+            args_size = int(trace["stack"][-5], 16)
+        else:
+            args_offset = int(trace["stack"][-3], 16)
+            args_size = int(trace["stack"][-4], 16)
+        calls.append(
+            (
+                address,
+                bytes(read_from_memory(args_offset, args_size, trace["memory"])),
+            )
+        )
+    return calls
+
+
+def process_debug_trace_for_storage_writes(
+    tx: TransactionAbc,
+    debug_trace: Dict,
+) -> Dict[Address, Dict[int, int]]:
+    """
+    Collect final values of storage slots written by the transaction, excluding writes in reverted calls.
+    Writes in `DELEGATECALL` and `CALLCODE` frames are attributed to the account owning the storage (the caller),
+    not to the account whose code is executed.
+    """
+    if tx.status != 1:
+        return {}
+
+    # storage contexts are single-item lists shared by delegatecalls,
+    # address of a contract being created is known only after the CREATE returns
+    if tx.to is None:
+        assert tx._tx_receipt is not None
+        root: List[Optional[Address]] = [Address(tx._tx_receipt["contractAddress"])]
+    else:
+        root = [tx.to.address]
+    contexts: List[List[Optional[Address]]] = [root]
+    writes: List[List[Tuple[List[Optional[Address]], int, int]]] = [[]]
+    pending_creates: List[Tuple[List[Optional[Address]], int]] = []
+    prev_op: Optional[str] = None
+
+    for trace in debug_trace["structLogs"]:
+        if len(pending_creates) > 0 and pending_creates[-1][1] == trace["depth"]:
+            context, _ = pending_creates.pop()
+            created = int(trace["stack"][-1], 16)
+            context[0] = Address(created) if created != 0 else None
+
+        while len(contexts) > trace["depth"]:
+            contexts.pop()
+            frame_writes = writes.pop()
+            if prev_op in {"RETURN", "STOP", "SELFDESTRUCT"}:
+                writes[-1].extend(frame_writes)
+
+        op = trace["op"]
+        if op in {"CALL", "STATICCALL"}:
+            contexts.append([Address(int(trace["stack"][-2], 16))])
+            writes.append([])
+        elif op in {"DELEGATECALL", "CALLCODE"}:
+            contexts.append(contexts[-1])
+            writes.append([])
+        elif op in {"CREATE", "CREATE2"}:
+            context = [None]
+            contexts.append(context)
+            writes.append([])
+            pending_creates.append((context, trace["depth"]))
+        elif op == "SSTORE":
+            writes[-1].append(
+                (
+                    contexts[-1],
+                    int(trace["stack"][-1], 16),
+                    int(trace["stack"][-2], 16),
+                )
+            )
+
+        prev_op = op
+
+    ret: Dict[Address, Dict[int, int]] = {}
+    for context, slot, value in writes[0]:
+        if context[0] is not None:
+            ret.setdefault(context[0], {})[slot] = value
+    return ret
+
+
 def process_debug_trace_for_events(
     tx: TransactionAbc,
     debug_trace: Dict,
@@ -3105,6 +7868,38 @@ def process_debug_trace_for_events(
 
 LIBRARY_PLACEHOLDER_REGEX = re.compile(r"__\$[0-9a-fA-F]{34}\$__")
 
+# EIP-3860 limit, twice the EIP-170 contract code size limit
+MAX_INITCODE_SIZE = 49152
+# command line arguments of development chains lifting the code and initcode size limits
+_CODE_SIZE_LIMIT_ARGS = (
+    "--code-size-limit",
+    "--disable-code-size-limit",
+    "--chain.allowUnlimitedContractSize",
+    "--chain.allowUnlimitedInitCodeSize",
+)
+
+_HEX_CHUNK_SIZE = 1 << 16
+
+
+def _decode_creation_code(
+    creation_code: str, library_address: Callable[[bytes], Address]
+) -> bytearray:
+    # decode hex chunks directly into a single buffer instead of substituting library placeholders
+    # in the (possibly multi-megabyte) hex string, which would copy the whole string for every placeholder
+    code = bytearray()
+
+    def decode(start: int, end: int) -> None:
+        for i in range(start, end, _HEX_CHUNK_SIZE):
+            code.extend(bytes.fromhex(creation_code[i : min(i + _HEX_CHUNK_SIZE, end)]))
+
+    pos = 0
+    for match in LIBRARY_PLACEHOLDER_REGEX.finditer(creation_code):
+        decode(pos, match.start())
+        code.extend(bytes(library_address(bytes.fromhex(match.group(0)[3:-3]))))
+        pos = match.end()
+    decode(pos, len(creation_code))
+    return code
+
 
 class Contract(Account):
     _abi: Dict[
@@ -3112,6 +7907,7 @@ class Contract(Account):
         Any,
     ]
     _creation_code: str
+    _evm_version: Optional[str] = None
 
     def __init__(
         self, addr: Union[Account, Address, str], chain: Optional[Chain] = None
@@ -3120,7 +7916,7 @@ class Contract(Account):
             if chain is None:
                 chain = addr.chain
             elif addr.chain != chain:
-                raise ValueError("Account and chain must be from the same chain")
+                raise ChainMismatchError(addr, chain)
             addr = addr.address
         super().__init__(addr, chain)
 
@@ -3131,29 +7927,54 @@ class Contract(Account):
 
     __repr__ = __str__
 
+    @classmethod
+    def predicted_address(
+        cls,
+        from_: Optional[Union[Account, Address, str]] = None,
+        *,
+        offset: int = 0,
+        chain: Optional[Chain] = None,
+    ) -> Address:
+        """
+        Compute the address the contract will be deployed at when deployed by `from_` (defaults to `chain.default_tx_account`)
+        in its next transaction, or `offset` transactions later.
+        """
+        if chain is None:
+            chain = detect_default_chain()
+
+        if from_ is None:
+            from_ = chain.default_tx_account
+            if from_ is None:
+                raise ValueError(
+                    "No from_ account specified and no default account set"
+                )
+        elif isinstance(from_, Account):
+            if from_.chain != chain:
+                raise ChainMismatchError(from_, chain, "`from_` account")
+        else:
+            from_ = Account(from_, chain)
+
+        return from_.next_create_address(offset)
+
     @classmethod
     def _get_creation_code(
         cls, libraries: Dict[bytes, Tuple[Union[Account, Address], str]]
     ) -> bytes:
-        creation_code = cls._creation_code
-        for match in LIBRARY_PLACEHOLDER_REGEX.finditer(creation_code):
-            lib_id = bytes.fromhex(match.group(0)[3:-3])
+
+        def library_address(lib_id: bytes) -> Address:
             assert (
                 lib_id in libraries
             ), f"Address of library {libraries[lib_id][1]} required to generate creation code"
 
             lib = libraries[lib_id][0]
             if isinstance(lib, Account):
-                lib_addr = str(lib.address)[2:]
+                return lib.address
             elif isinstance(lib, Address):
-                lib_addr = str(lib)[2:]
+                return lib
             else:
                 raise TypeError()
 
-            creation_code = (
-                creation_code[: match.start()] + lib_addr + creation_code[match.end() :]
-            )
-        return bytes.fromhex(creation_code)
+        return bytes(_decode_creation_code(cls._creation_code, library_address))
 
     @classmethod
     def _deploy(
@@ -3176,34 +7997,51 @@ class Contract(Account):
         type: Optional[int],
         block: Optional[Union[int, str]],
         confirmations: Optional[int],
+        chain_id_override: Optional[int] = None,
     ) -> Any:
         if chain is None:
             chain = detect_default_chain()
 
-        creation_code = cls._creation_code
-        for match in LIBRARY_PLACEHOLDER_REGEX.finditer(creation_code):
-            lib_id = bytes.fromhex(match.group(0)[3:-3])
+        verify_code = get_config().general.verify_library_code
+        # verified libraries are shared across deployments unless verifying against a historical block,
+        # the set is journaled so that libraries deployed after a snapshot are verified again after reverting
+        verified: Set[Address] = (
+            chain._verified_libraries if block is None else set()
+        )
+
+        def library_address(lib_id: bytes) -> Address:
             assert lib_id in libraries
 
             lib = libraries[lib_id][0]
             if lib is not None:
                 if isinstance(lib, Account):
-                    lib_addr = str(lib.address)[2:]
+                    lib_address = lib.address
+                elif isinstance(lib, Address):
+                    lib_address = lib
                 else:
-                    lib_addr = str(lib)[2:]
+                    lib_address = Address(lib)
             elif lib_id in chain._deployed_libraries:
-                lib_addr = str(chain._deployed_libraries[lib_id][-1].address)[2:]
+                lib_address = chain._deployed_libraries[lib_id][-1].address
             else:
                 raise ValueError(f"Library {libraries[lib_id][1]} not deployed")
 
-            creation_code = (
-                creation_code[: match.start()] + lib_addr + creation_code[match.end() :]
-            )
+            if verify_code and lib_address not in verified:
+                code = chain.chain_interface.get_code(
+                    str(lib_address), block if block is not None else "pending"
+                )
+                if len(code) == 0:
+                    raise LibraryNotDeployed(libraries[lib_id][1], lib_address)
+                verified.add(lib_address)
+
+            return lib_address
+
+        creation_code = _decode_creation_code(cls._creation_code, library_address)
+        chain._check_evm_version(cls, creation_code)
 
         return cls._execute(
             chain,
             request_type,
-            creation_code,
+            bytes(creation_code),
             arguments,
             return_tx,
             return_type,
@@ -3218,6 +8056,7 @@ class Contract(Account):
             type,
             block,
             confirmations,
+            chain_id_override,
         )
 
     @classmethod
@@ -3225,7 +8064,7 @@ class Contract(Account):
         cls,
         chain: Chain,
         request_type: RequestType,
-        data: str,
+        data: Union[str, bytes],
         arguments: Iterable,
         return_tx: bool,
         return_type: Type,
@@ -3242,6 +8081,7 @@ class Contract(Account):
         type: Optional[int],
         block: Optional[Union[int, str]],
         confirmations: Optional[int],
+        chain_id_override: Optional[int] = None,
     ):
         if request_type == RequestType.TX and block is not None:
             raise ValueError("block cannot be specified for contract transactions")
@@ -3256,12 +8096,7 @@ class Contract(Account):
 
         params: TxParams = {}
         if from_ is not None:
-            if isinstance(from_, Account):
-                if from_.chain != chain:
-                    raise ValueError("`from_` account must belong to this chain")
-                params["from"] = str(from_.address)
-            else:
-                params["from"] = str(from_)
+            params["from"] = str(chain._resolve_address(from_, "`from_` account"))
 
         if isinstance(value, str):
             value = Wei.from_str(value)
@@ -3279,12 +8114,7 @@ class Contract(Account):
             raise TypeError("`gas_limit` must be an int, 'max', 'auto', or None")
 
         if to is not None:
-            if isinstance(to, Account):
-                if to.chain != chain:
-                    raise ValueError("`to` account must belong to this chain")
-                params["to"] = str(to.address)
-            else:
-                params["to"] = str(to)
+            params["to"] = str(chain._resolve_address(to, "`to` account"))
 
         if gas_price is not None:
             if isinstance(gas_price, str):
@@ -3304,26 +8134,12 @@ class Contract(Account):
         if access_list == "auto":
             params["accessList"] = "auto"
         elif access_list is not None:
-            # normalize access_list, all keys should be Address
-            tmp_access_list = defaultdict(list)
-            for k, v in access_list.items():
-                if isinstance(k, Account):
-                    k = k.address
-                elif isinstance(k, str):
-                    k = Address(k)
-                elif not isinstance(k, Address):
-                    raise TypeError("access_list keys must be Account, Address or str")
-                tmp_access_list[k].extend(v)
-            access_list = tmp_access_list
-            params["accessList"] = [
-                {"address": str(k), "storageKeys": [hex(i) for i in v]}
-                for k, v in access_list.items()
-            ]
+            params["accessList"] = AccessList(access_list).to_json()
 
         if type is not None:
             params["type"] = type
 
-        params["data"] = bytes.fromhex(data)
+        params["data"] = bytes.fromhex(data) if isinstance(data, str) else data
 
         if to is None:
             abi = (
@@ -3343,23 +8159,30 @@ class Contract(Account):
                 return_type,
                 confirmations,
                 from_,
+                chain_id_override,
             )
-        elif request_type == RequestType.CALL:
-            if block is None:
-                block = "latest"
-            return chain._call(abi, arguments, params, return_type, block)
-        elif request_type == RequestType.ESTIMATE:
-            if block is None:
-                block = "pending"
 
-            return chain._estimate(abi, arguments, params, block)
-        elif request_type == RequestType.ACCESS_LIST:
-            if block is None:
-                block = "pending"
+        with chain._chain_id_override(chain_id_override):
+            if request_type == RequestType.CALL:
+                if block is None:
+                    block = "latest"
+                return chain._call(abi, arguments, params, return_type, block)
+            elif request_type == RequestType.CALL_RESULT:
+                if block is None:
+                    block = "latest"
+                return chain._call_result(abi, arguments, params, return_type, block)
+            elif request_type == RequestType.ESTIMATE:
+                if block is None:
+                    block = "pending"
+
+                return chain._estimate(abi, arguments, params, block)
+            elif request_type == RequestType.ACCESS_LIST:
+                if block is None:
+                    block = "pending"
 
-            return chain._access_list(abi, arguments, params, block)
-        else:
-            raise ValueError("invalid request type")
+                return chain._access_list(abi, arguments, params, block)
+            else:
+                raise ValueError("invalid request type")
 
 
 class Library(Contract):
@@ -3384,6 +8207,7 @@ class Library(Contract):
         type: Optional[int],
         block: Optional[Union[int, str]],
         confirmations: Optional[int],
+        chain_id_override: Optional[int] = None,
     ) -> Any:
         if chain is None:
             chain = detect_default_chain()
@@ -3405,6 +8229,7 @@ class Library(Contract):
             type,
             block,
             confirmations,
+            chain_id_override,
         )
         if confirmations != 0:
             if return_tx:
@@ -23,18 +23,35 @@ from ipdb.__main__ import _init_pdb
 
 from wake.config import WakeConfig
 from wake.core import get_logger
-from wake.development.chain_interfaces import ChainInterfaceAbc
+from wake.development.chain_interfaces import (
+    ChainInterfaceAbc,
+    get_deterministic_genesis_timestamp,
+)
 from wake.development.json_rpc import JsonRpcError
 from wake.utils.file_utils import is_relative_to
 
 if TYPE_CHECKING:
-    from wake.testing.coverage import CoverageHandler
+    from wake.testing.coverage import CoverageDelta, CoverageHandler
 
 
 logger = get_logger(__name__)
 
 
 random = Random()
+_random_seed: Optional[bytes] = None
+
+
+def set_random_seed(seed: bytes) -> None:
+    """
+    Seed the global random number generator and remember the seed for seed-derived chain parameters.
+    """
+    global _random_seed
+    _random_seed = seed
+    random.seed(seed)
+
+
+def get_random_seed() -> Optional[bytes]:
+    return _random_seed
 
 
 # must be declared before functions that use it because of a bug in Python (https://bugs.python.org/issue34939)
@@ -159,6 +176,18 @@ def get_coverage_handler() -> Optional[CoverageHandler]:
     return _coverage_handler
 
 
+def sync_coverage() -> Optional[CoverageDelta]:
+    """
+    Immediately deliver the coverage collected by the current process (e.g. to the `wake test` coordinator process).
+
+    Returns:
+        Statement, function and modifier hits recorded since the previous sync, or `None` if coverage is not collected.
+    """
+    if _coverage_handler is None:
+        return None
+    return _coverage_handler.sync()
+
+
 def set_verbosity(verbosity: int):
     global _verbosity
     _verbosity = verbosity
@@ -184,24 +213,42 @@ class ChainInterfaceManager:
         chain_id: Optional[int],
         fork: Optional[str],
         hardfork: Optional[str],
+        genesis_timestamp: Optional[int] = None,
     ) -> ChainInterfaceAbc:
-        params = (uri, accounts, chain_id, fork, hardfork)
+        config = get_config()
+        if (
+            uri is None
+            and genesis_timestamp is None
+            and fork is None
+            and config.testing.cmd == "anvil"
+            and config.testing.deterministic_timestamps
+            and not any(
+                arg in {"-f", "--fork-url", "--rpc-url", "--timestamp"}
+                for arg in config.testing.anvil.cmd_args.split()
+            )
+        ):
+            # resolved before looking up free chains, so that chains launched for a different seed are not reused
+            genesis_timestamp = get_deterministic_genesis_timestamp(_random_seed)
+
+        params = (uri, accounts, chain_id, fork, hardfork, genesis_timestamp)
 
         if len(self._free_chain_interfaces[params]) == 0:
             if uri is None:
                 logger.debug(
-                    "Launching chain with accounts=%s, chain_id=%s, fork=%s, hardfork=%s",
+                    "Launching chain with accounts=%s, chain_id=%s, fork=%s, hardfork=%s, genesis_timestamp=%s",
                     accounts,
                     chain_id,
                     fork,
                     hardfork,
+                    genesis_timestamp,
                 )
                 chain_interface = ChainInterfaceAbc.launch(
-                    get_config(),
+                    config,
                     accounts=accounts,
                     chain_id=chain_id,
                     fork=fork,
                     hardfork=hardfork,
+                    genesis_timestamp=genesis_timestamp,
                 )
             else:
                 if (
@@ -209,19 +256,21 @@ class ChainInterfaceManager:
                     or chain_id is not None
                     or fork is not None
                     or hardfork is not None
+                    or genesis_timestamp is not None
                 ):
                     raise ValueError(
-                        "Cannot specify accounts, chain_id, fork or hardfork when connecting to a running chain"
+                        "Cannot specify accounts, chain_id, fork, hardfork or genesis_timestamp when connecting to a running chain"
                     )
                 logger.debug("Connecting to chain at %s", uri)
-                chain_interface = ChainInterfaceAbc.connect(get_config(), uri)
+                chain_interface = ChainInterfaceAbc.connect(config, uri)
         else:
             logger.debug(
-                "Reusing chain with accounts=%s, chain_id=%s, fork=%s, hardfork=%s",
+                "Reusing chain with accounts=%s, chain_id=%s, fork=%s, hardfork=%s, genesis_timestamp=%s",
                 accounts,
                 chain_id,
                 fork,
                 hardfork,
+                genesis_timestamp,
             )
             chain_interface = self._free_chain_interfaces[params].pop()
 
@@ -251,7 +300,7 @@ class ChainInterfaceManager:
 
         if snapshot_reverted and params is not None:
             logger.debug(
-                "Freeing chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s",
+                "Freeing chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s, genesis_timestamp=%s",
                 *params,
             )
             self._free_chain_interfaces[params].add(chain_interface)
@@ -260,7 +309,7 @@ class ChainInterfaceManager:
                 logger.debug("Unable to revert snapshot, closing chain")
             else:
                 logger.debug(
-                    "Unable to revert snapshot, closing chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s",
+                    "Unable to revert snapshot, closing chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s, genesis_timestamp=%s",
                     *params,
                 )
             chain_interface.close()
@@ -273,7 +322,7 @@ class ChainInterfaceManager:
                 if c == chain_interface:
                     chain_interfaces.pop(i)
                     logger.debug(
-                        "Closed chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s",
+                        "Closed chain with uri=%s, accounts=%s, chain_id=%s, fork=%s, hardfork=%s, genesis_timestamp=%s",
                         *chain_params,
                     )
                     return
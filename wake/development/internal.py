@@ -1,7 +1,7 @@
 from __future__ import annotations
 
 from dataclasses import dataclass, field
-from typing import TYPE_CHECKING, List
+from typing import TYPE_CHECKING, Any, Dict, List, Optional, Tuple
 
 if TYPE_CHECKING:
     from .core import Account
@@ -10,10 +10,24 @@ if TYPE_CHECKING:
 @dataclass
 class UnknownEvent:
     origin: Account = field(init=False, compare=False, repr=False)
+    frame: Optional[Tuple[int, ...]] = field(init=False, compare=False, repr=False)
     topics: List[bytes]
     data: bytes
 
 
+@dataclass
+class ExternalEvent:
+    """
+    Event decoded using an ABI registered with `Chain.register_abi`.
+    Indexed arguments of dynamic types hold the 32-byte topic (hash of the value).
+    """
+
+    origin: Account = field(init=False, compare=False, repr=False)
+    frame: Optional[Tuple[int, ...]] = field(init=False, compare=False, repr=False)
+    name: str
+    arguments: Dict[str, Any]
+
+
 def read_from_memory(offset: int, length: int, memory: List) -> bytearray:
     start_block = offset // 32
     start_offset = offset % 32
@@ -1 +1,7 @@
-from .communicator import JsonRpcError
+from .communicator import (
+    ConnectionLost,
+    ExecutionTimeout,
+    ForkTimeout,
+    JsonRpcError,
+    SigningTimeout,
+)
@@ -3,6 +3,16 @@ from typing import Any
 
 
 class ProtocolAbc(ABC):
+    _timeout: float
+
+    @property
+    def timeout(self) -> float:
+        return self._timeout
+
+    @timeout.setter
+    def timeout(self, value: float) -> None:
+        self._timeout = value
+
     @abstractmethod
     def __enter__(self):
         ...
@@ -14,3 +24,13 @@ class ProtocolAbc(ABC):
     @abstractmethod
     def send_recv(self, data: str) -> Any:
         ...
+
+    def recv(self) -> Any:
+        """
+        Receive the next message without sending a request. Supported only by persistent connections.
+        """
+        raise NotImplementedError
+
+    def reconnect(self) -> None:
+        self.__exit__(None, None, None)
+        self.__enter__()
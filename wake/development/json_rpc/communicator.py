@@ -1,8 +1,16 @@
+from __future__ import annotations
+
 import json
 import logging
 import platform
+import socket
+import threading
+from collections import OrderedDict
 from pathlib import Path
-from typing import Any, Dict, List, Optional
+from typing import Any, Dict, List, Optional, Tuple
+from urllib.error import URLError
+
+from websocket import WebSocketTimeoutException
 
 from wake.config import WakeConfig
 from wake.core import get_logger
@@ -20,10 +28,127 @@ class JsonRpcError(Exception):
         self.data = data
 
 
+class ExecutionTimeout(Exception):
+    method: str
+    timeout: float
+
+    def __init__(self, method: str, timeout: float):
+        self.method = method
+        self.timeout = timeout
+        super().__init__(f"{method} did not finish within {timeout} seconds")
+
+
+class SigningTimeout(Exception):
+    method: str
+    timeout: float
+
+    def __init__(self, method: str, timeout: float):
+        self.method = method
+        self.timeout = timeout
+        super().__init__(
+            f"{method} was not signed within {timeout} seconds, is the signer connected?"
+        )
+
+
+class ForkTimeout(JsonRpcError):
+    """
+    Request failed because the development chain did not receive a response from the forked chain RPC endpoint in time.
+    """
+
+    def __str__(self) -> str:
+        return f"Forked chain RPC endpoint timed out: {self.data.get('message', self.data)}"
+
+
+# state reads served from the fallback cache when the forked chain RPC endpoint times out
+# => index of the block parameter
+_FORK_READ_METHODS = {
+    "eth_getBalance": 1,
+    "eth_getCode": 1,
+    "eth_getStorageAt": 2,
+    "eth_getTransactionCount": 1,
+    "eth_getBlockByNumber": 0,
+    "eth_getBlockByHash": 0,
+}
+_FALLBACK_CACHE_SIZE = 100_000
+
+
+def _is_fallback_cacheable(method_name: str, params: List) -> bool:
+    # results for block tags (`latest`, `pending`, ...) change over time and must not be served when stale
+    index = _FORK_READ_METHODS[method_name]
+    if index >= len(params):
+        return False
+    block = params[index]
+    if isinstance(block, dict):
+        # EIP-1898 block parameter
+        return "blockHash" in block or "blockNumber" in block
+    return isinstance(block, str) and block.startswith("0x")
+
+
+def _is_fork_timeout(error: Dict) -> bool:
+    # Anvil reports failed requests to the forked chain as internal errors, e.g.
+    # "Fork Error: ... operation timed out" or "failed to get account for 0x...: ... timed out";
+    # reverts use different codes, so revert reasons mentioning a timeout are not matched
+    if error.get("code") != -32603:
+        return False
+    message = str(error.get("message", "")).lower()
+    return ("fork" in message or "failed to get" in message) and (
+        "timed out" in message or "timeout" in message
+    )
+
+
+# requests executing EVM code, subject to the execution timeout
+_EXECUTION_METHODS = {
+    "eth_call",
+    "eth_estimateGas",
+    "eth_sendTransaction",
+    "eth_sendRawTransaction",
+    "eth_sendUnsignedTransaction",
+    "debug_traceCall",
+}
+
+
+# requests signed by the client, possibly waiting for an external (hardware) signer
+_SIGNING_METHODS = {
+    "eth_sign",
+    "eth_signTypedData_v4",
+    "eth_signTransaction",
+    "eth_sendTransaction",
+}
+
+
+def _is_timeout(e: BaseException) -> bool:
+    if isinstance(e, URLError):
+        return isinstance(e.reason, (TimeoutError, socket.timeout))
+    return isinstance(e, (TimeoutError, socket.timeout, WebSocketTimeoutException))
+
+
+def _is_stale(response: Any, request_id: int) -> bool:
+    # responses to requests abandoned after a timeout carry lower request IDs
+    if isinstance(response, dict):
+        response_id = response.get("id")
+        return isinstance(response_id, int) and response_id < request_id
+    return len(response) > 0 and all(
+        isinstance(r, dict) and isinstance(r.get("id"), int) and r["id"] < request_id
+        for r in response
+    )
+
+
+class ConnectionLost(ConnectionError):
+    """
+    Raised when a persistent (WebSocket or IPC) connection could not be re-established after a request timed out.
+    Reconnecting is attempted again by every further request.
+    """
+
+
 class JsonRpcCommunicator:
     _protocol: ProtocolAbc
     _request_id: int
     _connected: bool
+    _connection_lost: bool
+    _lock: threading.Lock
+    _execution_timeout: Optional[float]
+    _signing_timeout: Optional[float]
+    _fallback_cache: Optional[OrderedDict[str, Any]]
 
     def __init__(self, config: WakeConfig, uri: str):
         if uri.startswith(("http://", "https://")):
@@ -37,6 +162,14 @@ class JsonRpcCommunicator:
 
         self._request_id = 0
         self._connected = False
+        self._connection_lost = False
+        # requests may be sent from multiple threads (e.g. through chain views)
+        self._lock = threading.Lock()
+        self._execution_timeout = None
+        self._signing_timeout = None
+        self._fallback_cache = (
+            OrderedDict() if config.testing.fork_timeout_fallback else None
+        )
 
     def __enter__(self):
         self._protocol.__enter__()
@@ -51,18 +184,149 @@ class JsonRpcCommunicator:
     def connected(self) -> bool:
         return self._connected
 
+    @property
+    def execution_timeout(self) -> Optional[float]:
+        return self._execution_timeout
+
+    @execution_timeout.setter
+    def execution_timeout(self, value: Optional[float]) -> None:
+        self._execution_timeout = value
+
+    @property
+    def signing_timeout(self) -> Optional[float]:
+        return self._signing_timeout
+
+    @signing_timeout.setter
+    def signing_timeout(self, value: Optional[float]) -> None:
+        self._signing_timeout = value
+
+    def _send_recv(self, post_data: Any, request_id: int) -> Any:
+        if self._connection_lost:
+            try:
+                self._protocol.reconnect()
+            except Exception:
+                raise ConnectionLost(
+                    "Connection to the node was lost after a request timed out"
+                ) from None
+            self._connection_lost = False
+
+        try:
+            response = self._protocol.send_recv(json.dumps(post_data))
+            while _is_stale(response, request_id):
+                logger.info(f"Discarding stale response:\n{json.dumps(response)}")
+                response = self._protocol.recv()
+            return response
+        except Exception as e:
+            if _is_timeout(e):
+                # the node keeps processing the abandoned request, its late response
+                # would be received by the next request on a persistent connection
+                try:
+                    self._protocol.reconnect()
+                except Exception:
+                    self._connection_lost = True
+                    logger.warning("Failed to reconnect to the node after a timeout")
+            raise
+
     def send_request(self, method_name: str, params: Optional[List] = None) -> Any:
-        post_data = {
-            "jsonrpc": "2.0",
-            "method": method_name,
-            "params": params if params is not None else [],
-            "id": self._request_id,
-        }
-        logger.info(f"Sending request:\n{post_data}")
-        self._request_id += 1
-
-        response = self._protocol.send_recv(json.dumps(post_data))
+        with self._lock:
+            request_id = self._request_id
+            post_data = {
+                "jsonrpc": "2.0",
+                "method": method_name,
+                "params": params if params is not None else [],
+                "id": self._request_id,
+            }
+            logger.info(f"Sending request:\n{post_data}")
+            self._request_id += 1
+
+            limits = []
+            if (
+                self._execution_timeout is not None
+                and method_name in _EXECUTION_METHODS
+            ):
+                limits.append((self._execution_timeout, ExecutionTimeout))
+            if self._signing_timeout is not None and method_name in _SIGNING_METHODS:
+                limits.append((self._signing_timeout, SigningTimeout))
+
+            if len(limits) > 0:
+                # the longer limit applies if a request both executes code and is signed
+                timeout, error_type = max(limits, key=lambda limit: limit[0])
+                default_timeout = self._protocol.timeout
+                self._protocol.timeout = timeout
+                try:
+                    response = self._send_recv(post_data, request_id)
+                except Exception as e:
+                    if _is_timeout(e):
+                        raise error_type(method_name, timeout) from None
+                    raise
+                finally:
+                    self._protocol.timeout = default_timeout
+            else:
+                response = self._send_recv(post_data, request_id)
         logger.info(f"Received response:\n{json.dumps(response)}")
+
+        if (
+            self._fallback_cache is not None
+            and method_name in _FORK_READ_METHODS
+            and _is_fallback_cacheable(method_name, post_data["params"])
+        ):
+            key = json.dumps([method_name, post_data["params"]])
+            if "error" not in response:
+                self._fallback_cache[key] = response["result"]
+                self._fallback_cache.move_to_end(key)
+                if len(self._fallback_cache) > _FALLBACK_CACHE_SIZE:
+                    self._fallback_cache.popitem(last=False)
+            elif _is_fork_timeout(response["error"]) and key in self._fallback_cache:
+                logger.warning(
+                    f"{method_name} timed out, using previously fetched result"
+                )
+                return self._fallback_cache[key]
+
         if "error" in response:
+            if _is_fork_timeout(response["error"]):
+                raise ForkTimeout(response["error"])
             raise JsonRpcError(response["error"])
         return response["result"]
+
+    def send_batch_request(
+        self, requests: List[Tuple[str, Optional[List]]]
+    ) -> List[Any]:
+        """
+        Send multiple requests in a single JSON-RPC batch. The client may process batched requests concurrently,
+        e.g. Anvil resolves storage of a forked chain in parallel. Results are returned in the order of the requests.
+        """
+        if len(requests) == 0:
+            return []
+
+        with self._lock:
+            request_id = self._request_id
+            post_data = []
+            for method_name, params in requests:
+                post_data.append(
+                    {
+                        "jsonrpc": "2.0",
+                        "method": method_name,
+                        "params": params if params is not None else [],
+                        "id": self._request_id,
+                    }
+                )
+                self._request_id += 1
+            logger.info(f"Sending batch request:\n{post_data}")
+
+            response = self._send_recv(post_data, request_id)
+        logger.info(f"Received batch response:\n{json.dumps(response)}")
+
+        if isinstance(response, dict):
+            # the whole batch was rejected (e.g. batching not supported)
+            raise JsonRpcError(response["error"])
+
+        responses = {r["id"]: r for r in response}
+        results = []
+        for request in post_data:
+            r = responses[request["id"]]
+            if "error" in r:
+                if _is_fork_timeout(r["error"]):
+                    raise ForkTimeout(r["error"])
+                raise JsonRpcError(r["error"])
+            results.append(r["result"])
+        return results
@@ -34,6 +34,9 @@ if platform.system() == "Windows":
                 self._handle,  # pyright: ignore reportGeneralTypeIssues
                 data.encode("utf-8"),
             )
+            return self.recv()
+
+        def recv(self):
             received = bytearray()
             start = time.perf_counter()
 
@@ -72,6 +75,9 @@ else:
 
         def send_recv(self, data: str):
             self._socket.sendall(data.encode("utf-8"))
+            return self.recv()
+
+        def recv(self):
             received = bytearray()
             start = time.perf_counter()
 
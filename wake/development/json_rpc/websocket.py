@@ -22,6 +22,15 @@ class WebsocketProtocol(ProtocolAbc):
     def __exit__(self, exc_type, exc_val, exc_tb):
         self._ws.close()
 
+    @ProtocolAbc.timeout.setter
+    def timeout(self, value: float) -> None:
+        self._timeout = value
+        if hasattr(self, "_ws"):
+            self._ws.settimeout(value)
+
     def send_recv(self, data: str):
         self._ws.send(data)  # pyright: ignore reportGeneralTypeIssues
+        return self.recv()
+
+    def recv(self):
         return json.loads(self._ws.recv())
@@ -1,8 +1,10 @@
 import abc
-from typing import TYPE_CHECKING, Iterable, List, TypeVar
+from typing import TYPE_CHECKING, Iterable, List, Optional, Type, TypeVar, Union
 
 NoneType = type(None)
 
+IntT = TypeVar("IntT", bound=int)
+
 
 if TYPE_CHECKING:
     uint8 = int
@@ -39,6 +41,85 @@ if TYPE_CHECKING:
     uint256 = int
     uint = uint256
 
+    class U256(int):
+        min: U256
+        max: U256
+
+        def __new__(cls, value: Union[int, str] = 0) -> U256:
+            ...
+
+        def __add__(self, other: int) -> U256:
+            ...
+
+        def __radd__(self, other: int) -> U256:
+            ...
+
+        def __sub__(self, other: int) -> U256:
+            ...
+
+        def __rsub__(self, other: int) -> U256:
+            ...
+
+        def __mul__(self, other: int) -> U256:
+            ...
+
+        def __rmul__(self, other: int) -> U256:
+            ...
+
+        def __floordiv__(self, other: int) -> U256:
+            ...
+
+        def __rfloordiv__(self, other: int) -> U256:
+            ...
+
+        def __mod__(self, other: int) -> U256:
+            ...
+
+        def __rmod__(self, other: int) -> U256:
+            ...
+
+        def __pow__(self, other: int, mod: Optional[int] = None) -> U256:
+            ...
+
+        def __rpow__(self, other: int, mod: Optional[int] = None) -> U256:
+            ...
+
+        def __lshift__(self, other: int) -> U256:
+            ...
+
+        def __rshift__(self, other: int) -> U256:
+            ...
+
+        def __and__(self, other: int) -> U256:
+            ...
+
+        def __rand__(self, other: int) -> U256:
+            ...
+
+        def __or__(self, other: int) -> U256:
+            ...
+
+        def __ror__(self, other: int) -> U256:
+            ...
+
+        def __xor__(self, other: int) -> U256:
+            ...
+
+        def __rxor__(self, other: int) -> U256:
+            ...
+
+        def __invert__(self) -> U256:
+            ...
+
+        def wrapping_add(self, other: int) -> U256:
+            ...
+
+        def wrapping_sub(self, other: int) -> U256:
+            ...
+
+        def wrapping_mul(self, other: int) -> U256:
+            ...
+
     int8 = int
     int16 = int
     int24 = int
@@ -347,6 +428,125 @@ else:
 
     uint = uint256
 
+    _UINT256_MAX = 2**256 - 1
+
+    def _checked_u256(value):
+        if value is NotImplemented:
+            return value
+        if not isinstance(value, int):
+            # e.g. a float result of a negative exponent
+            raise TypeError(f"Result {value!r} is not an integer")
+        if value < 0 or value > _UINT256_MAX:
+            raise OverflowError(f"Result {value} out of uint256 range")
+        return int.__new__(U256, value)
+
+    class U256(uint256):
+        """
+        `uint256` value with checked arithmetic. Results of arithmetic operations with other integers are `U256` values
+        again (instead of plain `int` values), `OverflowError` is raised if a result is out of the `uint256` range,
+        like in checked arithmetic in Solidity. `TypeError` is raised if a result is not an integer (e.g. for negative exponents).
+        Shifts to the left are truncated to 256 bits.
+
+        As `U256` values are always within the `uint256` range, ABI encoding skips range checks for them.
+        Arguments consisting only of `uint256` values passed as `U256` are encoded directly without `eth_abi`.
+        Arithmetic operations are not faster than with plain `int` values.
+        """
+
+        def __new__(cls, value=0):
+            ret = int.__new__(cls, value)
+            if ret < 0 or ret > _UINT256_MAX:
+                raise ValueError(
+                    f"Expected value within range [0, {_UINT256_MAX}], got {ret}"
+                )
+            return ret
+
+        def __add__(self, other):
+            return _checked_u256(int.__add__(self, other))
+
+        def __radd__(self, other):
+            return _checked_u256(int.__radd__(self, other))
+
+        def __sub__(self, other):
+            return _checked_u256(int.__sub__(self, other))
+
+        def __rsub__(self, other):
+            return _checked_u256(int.__rsub__(self, other))
+
+        def __mul__(self, other):
+            return _checked_u256(int.__mul__(self, other))
+
+        def __rmul__(self, other):
+            return _checked_u256(int.__rmul__(self, other))
+
+        def __floordiv__(self, other):
+            return _checked_u256(int.__floordiv__(self, other))
+
+        def __rfloordiv__(self, other):
+            return _checked_u256(int.__rfloordiv__(self, other))
+
+        def __mod__(self, other):
+            return _checked_u256(int.__mod__(self, other))
+
+        def __rmod__(self, other):
+            return _checked_u256(int.__rmod__(self, other))
+
+        def __pow__(self, other, mod=None):
+            return _checked_u256(int.__pow__(self, other, mod))
+
+        def __rpow__(self, other, mod=None):
+            return _checked_u256(int.__rpow__(self, other, mod))
+
+        def __lshift__(self, other):
+            ret = int.__lshift__(self, other)
+            if ret is NotImplemented:
+                return ret
+            return int.__new__(U256, ret & _UINT256_MAX)
+
+        def __rshift__(self, other):
+            return _checked_u256(int.__rshift__(self, other))
+
+        def __and__(self, other):
+            return _checked_u256(int.__and__(self, other))
+
+        def __rand__(self, other):
+            return _checked_u256(int.__rand__(self, other))
+
+        def __or__(self, other):
+            return _checked_u256(int.__or__(self, other))
+
+        def __ror__(self, other):
+            return _checked_u256(int.__ror__(self, other))
+
+        def __xor__(self, other):
+            return _checked_u256(int.__xor__(self, other))
+
+        def __rxor__(self, other):
+            return _checked_u256(int.__rxor__(self, other))
+
+        def __invert__(self):
+            return int.__new__(U256, _UINT256_MAX - self)
+
+        def wrapping_add(self, other):
+            """
+            Add `other` with wrap-around on overflow, like in `unchecked` blocks in Solidity.
+            """
+            return int.__new__(U256, int.__add__(self, other) & _UINT256_MAX)
+
+        def wrapping_sub(self, other):
+            """
+            Subtract `other` with wrap-around on underflow, like in `unchecked` blocks in Solidity.
+            """
+            return int.__new__(U256, int.__sub__(self, other) & _UINT256_MAX)
+
+        def wrapping_mul(self, other):
+            """
+            Multiply by `other` with wrap-around on overflow, like in `unchecked` blocks in Solidity.
+            """
+            return int.__new__(U256, int.__mul__(self, other) & _UINT256_MAX)
+
+    U256.min = U256(0)
+    U256.max = U256(_UINT256_MAX)
+
     uint_map = {
         8: uint8,
         16: uint16,
@@ -896,3 +1096,24 @@ else:
         31: List31,
         32: List32,
     }
+
+
+def wrapping_cast(t: Type[IntT], value: int) -> IntT:
+    """
+    Convert `value` to the integer type `t` with wrap-around on overflow, like unchecked arithmetic in Solidity.
+    For example, `wrapping_cast(uint256, 0 - 1)` returns `uint256.max`.
+    """
+    # plain ints so that checked types (U256) do not overflow in the intermediate results
+    lo = int(t.min)  # pyright: ignore reportGeneralTypeIssues
+    size = int(t.max) - lo + 1  # pyright: ignore reportGeneralTypeIssues
+    return t((int(value) - lo) % size + lo)
+
+
+def saturating_cast(t: Type[IntT], value: int) -> IntT:
+    """
+    Convert `value` to the integer type `t`, clamping it to the `[t.min, t.max]` range.
+    For example, `saturating_cast(uint8, 300)` returns `uint8.max`.
+    """
+    return t(
+        min(max(value, t.min), t.max)  # pyright: ignore reportGeneralTypeIssues
+    )
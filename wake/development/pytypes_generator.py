@@ -1,6 +1,7 @@
 from __future__ import annotations
 
 import heapq
+import json
 import keyword
 import logging
 import os
@@ -94,6 +95,11 @@ def _path_to_uri(path: Path) -> str:
         return "file://" + pathname2url(str(path.resolve()))
 
 
+def _return_type_hint(return_type: str) -> str:
+    # single `bytes` return values are returned as `memoryview` with `chain.zero_copy_returns` enabled
+    return "Union[bytearray, memoryview]" if return_type == "bytearray" else return_type
+
+
 def _parse_opcodes(opcodes: str) -> List[Tuple[int, str, int, Optional[int]]]:
     pc_op_map = []
     opcodes_spl = opcodes.split(" ")
@@ -169,10 +175,14 @@ class TypeGenerator:
     __errors_index: Dict[bytes, Dict[str, Any]]
     __events_index: Dict[bytes, Dict[str, Any]]
     __user_defined_value_types_index: Dict[str, str]
+    __enums_index: Dict[str, List[Tuple[str, Tuple[str, ...], int]]]
     __contracts_by_metadata_index: Dict[bytes, str]
     __contracts_inheritance_index: Dict[str, Tuple[str, ...]]
     __contracts_revert_constructor_index: Dict[str, Set[int]]
     __contracts_revert_index: Dict[str, Set[int]]
+    # contract fqn -> REVERT/INVALID pc -> (source file path, line)
+    __contracts_revert_constructor_locations: Dict[str, Dict[int, Tuple[str, int]]]
+    __contracts_revert_locations: Dict[str, Dict[int, Tuple[str, int]]]
     __creation_code_index: List[Tuple[Tuple[Tuple[int, bytes], ...], str]]
     __line_indexes: Dict[Path, List[Tuple[bytes, int]]]
     # source unit name -> other source unit names in the cycle
@@ -199,10 +209,13 @@ class TypeGenerator:
         self.__errors_index = {}
         self.__events_index = {}
         self.__user_defined_value_types_index = {}
+        self.__enums_index = {}
         self.__contracts_by_metadata_index = {}
         self.__contracts_inheritance_index = {}
         self.__contracts_revert_constructor_index = {}
         self.__contracts_revert_index = {}
+        self.__contracts_revert_constructor_locations = {}
+        self.__contracts_revert_locations = {}
         self.__creation_code_index = []
         self.__line_indexes = {}
         self.__cyclic_source_units = defaultdict(set)
@@ -348,7 +361,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["call"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> bytearray:""",
+            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["call"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> Union[bytearray, memoryview]:""",
             1,
         )
         generate_docstring()
@@ -358,7 +371,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["tx"] = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> {contract_name}:""",
+            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["tx"] = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> {contract_name}:""",
             1,
         )
         generate_docstring()
@@ -368,7 +381,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["estimate"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> int:""",
+            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["estimate"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> int:""",
             1,
         )
         generate_docstring()
@@ -378,7 +391,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["access_list"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> Tuple[Dict[Address, List[int]], int]:""",
+            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[False]{'' if self.__return_tx_obj else ' = False'}{libraries_str}, request_type: Literal["access_list"], chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> Tuple[Dict[Address, List[int]], int]:""",
             1,
         )
         generate_docstring()
@@ -388,7 +401,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[True]{' = True' if self.__return_tx_obj else ''}{libraries_str}, request_type: Literal["tx"] = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> TransactionAbc[{contract_name}]:""",
+            f"""def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: Literal[True]{' = True' if self.__return_tx_obj else ''}{libraries_str}, request_type: Literal["tx"] = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> TransactionAbc[{contract_name}]:""",
             1,
         )
         generate_docstring()
@@ -397,7 +410,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@classmethod", 1)
         self.add_str_to_types(
             1,
-            f'def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: bool = {self.__return_tx_obj}{libraries_str}, request_type: RequestType = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> Union[bytearray, {contract_name}, int, Tuple[Dict[Address, List[int]], int], TransactionAbc[{contract_name}]]:',
+            f'def deploy(cls, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, return_tx: bool = {self.__return_tx_obj}{libraries_str}, request_type: RequestType = "tx", chain: Optional[Chain] = None, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> Union[bytearray, memoryview, {contract_name}, int, Tuple[Dict[Address, List[int]], int], TransactionAbc[{contract_name}]]:',
             1,
         )
 
@@ -415,7 +428,7 @@ class TypeGenerator:
                 )
                 self.add_str_to_types(
                     2,
-                    f"return cls._deploy(request_type, [{', '.join(map(itemgetter(0), param_names))}], return_tx, {contract_name}, from_, value, gas_limit, {libs_arg}, chain, gas_price, max_fee_per_gas, max_priority_fee_per_gas, access_list, type, block, confirmations)",
+                    f"return cls._deploy(request_type, [{', '.join(map(itemgetter(0), param_names))}], return_tx, {contract_name}, from_, value, gas_limit, {libs_arg}, chain, gas_price, max_fee_per_gas, max_priority_fee_per_gas, access_list, type, block, confirmations, chain_id_override)",
                     1,
                 )
             else:
@@ -472,9 +485,10 @@ class TypeGenerator:
         parsed_opcodes,
         pc_map,
         index: Dict[str, Set[int]],
+        locations: Dict[str, Dict[int, Tuple[str, int]]],
     ) -> None:
         for pc, op, size, argument in parsed_opcodes:
-            if op == "REVERT" and pc in pc_map:
+            if op in {"REVERT", "INVALID"} and pc in pc_map:
                 start, end, file_id, _ = pc_map[pc]
                 if file_id == -1:
                     continue
@@ -485,6 +499,17 @@ class TypeGenerator:
                 except KeyError:
                     continue
 
+                line, _ = self.__get_line_pos_from_byte_offset(path, start)
+                try:
+                    # paths relative to the project root keep pytypes portable
+                    source_path = path.relative_to(self.__pytypes_dir.parent)
+                except ValueError:
+                    source_path = path
+                locations.setdefault(fqn, {})[pc] = (str(source_path), line + 1)
+
+                if op != "REVERT":
+                    continue
+
                 intervals = self.__interval_trees[path].envelop(start, end)
                 nodes: List = sorted(
                     [interval.data for interval in intervals],
@@ -538,9 +563,17 @@ class TypeGenerator:
 
         fqn = f"{contract.parent.source_unit_name}:{contract.name}"
 
-        for bytecode, index in [
-            (compilation_info.evm.bytecode, self.__contracts_revert_constructor_index),
-            (compilation_info.evm.deployed_bytecode, self.__contracts_revert_index),
+        for bytecode, index, locations in [
+            (
+                compilation_info.evm.bytecode,
+                self.__contracts_revert_constructor_index,
+                self.__contracts_revert_constructor_locations,
+            ),
+            (
+                compilation_info.evm.deployed_bytecode,
+                self.__contracts_revert_index,
+                self.__contracts_revert_locations,
+            ),
         ]:
             parsed_opcodes = _parse_opcodes(bytecode.opcodes)
             pc_map = _parse_source_map(bytecode.source_map, parsed_opcodes)
@@ -550,6 +583,7 @@ class TypeGenerator:
                 parsed_opcodes,
                 pc_map,
                 index,
+                locations,
             )
 
         if len(compilation_info.evm.deployed_bytecode.object) > 0:
@@ -682,6 +716,15 @@ class TypeGenerator:
                 1,
             )
 
+        if compilation_info.metadata is not None:
+            evm_version = (
+                json.loads(compilation_info.metadata)
+                .get("settings", {})
+                .get("evmVersion")
+            )
+            if evm_version is not None:
+                self.add_str_to_types(1, f'_evm_version = "{evm_version}"', 1)
+
         self.add_str_to_types(
             1, f'_creation_code = "{compilation_info.evm.bytecode.object}"', 2
         )
@@ -797,6 +840,17 @@ class TypeGenerator:
         self.__imports.add_python_import("from enum import IntEnum")
         for enum in enums:
             self.add_str_to_types(indent, f"class {self.get_name(enum)}(IntEnum):", 1)
+            module_name = "pytypes." + _make_path_alphanum(
+                enum.source_unit.source_unit_name[:-3]
+            ).replace("/", ".")
+            if isinstance(enum.parent, ContractDefinition):
+                attrs = (self.get_name(enum.parent), self.get_name(enum))
+            else:
+                attrs = (self.get_name(enum),)
+            # enums declared in different source units may share the same canonical name
+            self.__enums_index.setdefault(enum.canonical_name, []).append(
+                (module_name, attrs, len(enum.values))
+            )
             line, _ = self.__get_line_pos_from_byte_offset(
                 enum.source_unit.file, enum.byte_location[0]
             )
@@ -980,6 +1034,11 @@ class TypeGenerator:
                 "origin: Account = dataclasses.field(init=False, compare=False, repr=False)",
                 1,
             )
+            self.add_str_to_types(
+                indent + 1,
+                "frame: Optional[Tuple[int, ...]] = dataclasses.field(init=False, compare=False, repr=False)",
+                1,
+            )
             self.add_str_to_types(indent + 1, f"original_name = '{event.name}'", 1)
             self.add_str_to_types(indent + 1, f"selector = {event.event_selector}", 2)
             for param_name, param_type, _, original_name in parameters:
@@ -1261,13 +1320,22 @@ class TypeGenerator:
         if len(returns) == 0:
             returns_str = "None"
         elif len(returns) == 1:
-            returns_str = returns[0][0]
+            returns_str = _return_type_hint(returns[0][0])
         else:
             returns_str = f"Tuple[{', '.join(ret[0] for ret in returns)}]"
 
         self.generate_type_hint_stub_func(
             decl, generated_params, returns_str, "call", True, param_names, returns
         )
+        self.generate_type_hint_stub_func(
+            decl,
+            generated_params,
+            f"CallResult[{returns_str}]",
+            "call_result",
+            False,
+            param_names,
+            returns,
+        )
         self.generate_type_hint_stub_func(
             decl, generated_params, "int", "estimate", False, param_names, returns
         )
@@ -1329,12 +1397,12 @@ class TypeGenerator:
         if len(returns) == 0:
             returns_str = None
         elif len(returns) == 1:
-            returns_str = returns[0][0]
+            returns_str = _return_type_hint(returns[0][0])
         else:
             returns_str = f"Tuple[{', '.join(ret[0] for ret in returns)}]"
         self.add_str_to_types(
             1,
-            f"""def {self.get_name(declaration)}(self, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, to: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, request_type: RequestType = '{'call' if is_view_or_pure else 'tx'}', gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> Union[{returns_str}, TransactionAbc[{returns_str}], int, Tuple[Dict[Address, List[int]], int]]:""",
+            f"""def {self.get_name(declaration)}(self, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, to: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, request_type: RequestType = '{'call' if is_view_or_pure else 'tx'}', gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> Union[{returns_str}, CallResult[{returns_str}], TransactionAbc[{returns_str}], int, Tuple[Dict[Address, List[int]], int]]:""",
             1,
         )
 
@@ -1370,7 +1438,7 @@ class TypeGenerator:
         fn_selector = declaration.function_selector.hex()
         self.add_str_to_types(
             2,
-            f'return self._execute(self.chain, request_type, "{fn_selector}", [{", ".join(map(itemgetter(0), param_names))}], True if request_type == "tx" else False, {return_types}, from_, to if to is not None else str(self.address), value, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, access_list, type, block, confirmations)',
+            f'return self._execute(self.chain, request_type, "{fn_selector}", [{", ".join(map(itemgetter(0), param_names))}], True if request_type == "tx" else False, {return_types}, from_, to if to is not None else str(self.address), value, gas_limit, gas_price, max_fee_per_gas, max_priority_fee_per_gas, access_list, type, block, confirmations, chain_id_override)',
             2,
         )
 
@@ -1389,7 +1457,7 @@ class TypeGenerator:
         self.add_str_to_types(1, "@overload", 1)
         self.add_str_to_types(
             1,
-            f"""def {self.get_name(declaration)}(self, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, to: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, request_type: Literal["{request_type}"]{' = "' + request_type + '"' if request_type_is_default else ''}, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None) -> {returns_str}:""",
+            f"""def {self.get_name(declaration)}(self, {params_str}*, from_: Optional[Union[Account, Address, str]] = None, to: Optional[Union[Account, Address, str]] = None, value: Union[int, str] = 0, gas_limit: Optional[Union[int, Literal["max"], Literal["auto"]]] = None, request_type: Literal["{request_type}"]{' = "' + request_type + '"' if request_type_is_default else ''}, gas_price: Optional[Union[int, str]] = None, max_fee_per_gas: Optional[Union[int, str]] = None, max_priority_fee_per_gas: Optional[Union[int, str]] = None, access_list: Optional[Union[Dict[Union[Account, Address, str], List[int]], Literal["auto"]]] = None, type: Optional[int] = None, block: Optional[Union[int, Literal["latest"], Literal["pending"], Literal["earliest"], Literal["safe"], Literal["finalized"]]] = None, confirmations: Optional[int] = None, chain_id_override: Optional[int] = None) -> {returns_str}:""",
             1,
         )
         line, _ = self.__get_line_pos_from_byte_offset(
@@ -1421,7 +1489,7 @@ class TypeGenerator:
         if len(returns) == 0:
             returns_str = "None"
         elif len(returns) == 1:
-            returns_str = returns[0][0]
+            returns_str = _return_type_hint(returns[0][0])
         else:
             returns_str = f"Tuple[{', '.join(ret[0] for ret in returns)}]"
 
@@ -1439,6 +1507,15 @@ class TypeGenerator:
             params_names,
             returns,
         )
+        self.generate_type_hint_stub_func(
+            fn,
+            params,
+            f"CallResult[{returns_str}]",
+            "call_result",
+            False,
+            params_names,
+            returns,
+        )
         self.generate_type_hint_stub_func(
             fn,
             params,
@@ -1813,8 +1890,11 @@ class TypeGenerator:
                 contracts_inheritance=self.__contracts_inheritance_index,
                 contracts_revert_constructor_index=self.__contracts_revert_constructor_index,
                 contracts_revert_index=self.__contracts_revert_index,
+                contracts_revert_constructor_locations=self.__contracts_revert_constructor_locations,
+                contracts_revert_locations=self.__contracts_revert_locations,
                 creation_code_index=self.__creation_code_index,
                 user_defined_value_types_index=self.__user_defined_value_types_index,
+                enums_index=self.__enums_index,
             )
         )
 
@@ -2076,6 +2156,7 @@ class NameSanitizer:
             "_label",
             "_get_creation_code",
             "_deploy",
+            "_evm_version",
             "_execute",
             "_library_id",
             "_prepare_tx_params",
@@ -2108,9 +2189,16 @@ class NameSanitizer:
             "access_list",
             "block",
             "confirmations",
+            "chain_id_override",
         }
         self.__struct_reserved = {"original_name"}
-        self.__event_reserved = {"_abi", "selector", "original_name", "origin"}
+        self.__event_reserved = {
+            "_abi",
+            "selector",
+            "original_name",
+            "origin",
+            "frame",
+        }
         self.__error_reserved = {"_abi", "selector", "original_name"}
         self.__enum_reserved = set()
 
@@ -1,34 +1,47 @@
 from __future__ import annotations
 
+import dataclasses
 import functools
+import hashlib
 import importlib
 import inspect
+import re
+import time
 from abc import ABC, abstractmethod
+from collections import Counter, OrderedDict, deque
 from contextlib import contextmanager
 from dataclasses import dataclass, field, fields
-from enum import IntEnum
+from enum import Enum, IntEnum
 from typing import (
     TYPE_CHECKING,
     Any,
     Callable,
+    Deque,
     Dict,
+    FrozenSet,
     Generic,
+    Iterable,
     Iterator,
     List,
     Optional,
+    Set,
     Tuple,
     Type,
     TypeVar,
     Union,
+    cast,
 )
 from urllib.error import HTTPError
 
+import eth_utils
 from typing_extensions import get_args, get_origin, get_type_hints
 
+from wake.utils import StrEnum, get_class_that_defined_method
+
 if TYPE_CHECKING:
     from .blocks import Block
 
-from .call_trace import CallTrace
+from .call_trace import CallTrace, CallTraceKind, get_precompiled_gas_cost
 from .chain_interfaces import (
     AnvilChainInterface,
     GanacheChainInterface,
@@ -37,14 +50,25 @@ from .chain_interfaces import (
     TxParams,
 )
 from .core import (
+    Abi,
     Account,
     Address,
+    Authorization,
     Chain,
+    DataTruncatedError,
+    SourceLocation,
     Wei,
+    abi,
+    fix_library_abi,
     get_contract_from_fqn,
     get_fqn_from_address,
     get_fqn_from_creation_code,
+    process_debug_trace_for_panic_location,
+    process_debug_trace_for_precompile_calls,
+    process_debug_trace_for_revert_location,
+    process_debug_trace_for_storage_writes,
 )
+from .globals import get_config
 from .internal import UnknownEvent, read_from_memory
 from .json_rpc import JsonRpcError
 
@@ -61,6 +85,363 @@ class TransactionTypeEnum(IntEnum):
     LEGACY = 0
     EIP2930 = 1
     EIP1559 = 2
+    EIP7702 = 4
+
+
+@dataclass(frozen=True)
+class GasForwardingInfo:
+    """
+    Gas forwarded by a single external call (`CALL`, `CALLCODE`, `DELEGATECALL` or `STATICCALL`) made during a transaction.
+    """
+
+    depth: int
+    "Call depth of the calling frame (1 for the transaction target)"
+    opcode: str
+    caller: Address
+    callee: Address
+    gas_requested: int
+    "Gas requested by the caller (the gas operand of the call instruction)"
+    gas_available: int
+    "Gas available in the calling frame right before the call instruction"
+    gas_forwarded: Optional[int]
+    "Gas received by the callee excluding the value transfer stipend, `None` if the callee did not execute any code"
+    status: bool
+    out_of_gas: bool
+    "True if the callee halted exceptionally, consuming all forwarded gas"
+
+    @property
+    def max_forwardable(self) -> int:
+        """
+        Upper bound of gas that can be forwarded to the callee with respect to the 63/64 rule (EIP-150).
+        """
+        return self.gas_available - self.gas_available // 64
+
+    @property
+    def shortfall(self) -> bool:
+        """
+        True if the callee received less gas than requested because of the 63/64 rule.
+        """
+        if self.gas_forwarded is not None:
+            return self.gas_forwarded < self.gas_requested
+        return self.gas_requested > self.max_forwardable
+
+    @property
+    def griefable(self) -> bool:
+        """
+        True if the call may fail when the transaction is sent with a lower gas limit
+        while the caller continues execution, i.e. the forwarded gas was capped by the 63/64 rule
+        and the callee either succeeded or ran out of gas.
+        """
+        return self.shortfall and (self.status or self.out_of_gas)
+
+
+@dataclass(frozen=True)
+class EventGasInfo:
+    """
+    Gas spent by `LOG` opcodes emitting a single event type during a transaction.
+    """
+
+    topic: Optional[bytes]
+    "First topic (event selector), `None` for anonymous events without topics (`LOG0`)"
+    name: Optional[str]
+    "Event name if known from `pytypes`"
+    count: int
+    "Number of emitted events, including events emitted in reverted calls"
+    data_size: int
+    "Total size of non-indexed event data in bytes"
+    gas: int
+    "Total gas cost of the `LOG` opcodes, including memory expansion"
+
+
+# keccak256("Transfer(address,address,uint256)")
+_ERC20_TRANSFER_TOPIC = bytes.fromhex(
+    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+)
+
+
+class TokenFlows:
+    """
+    Net balance changes of an ERC-20 token (or Ether if `token` is `None`) aggregated over one or more transactions.
+    Token flows are collected from ERC-20 `Transfer` events emitted by the token, Ether flows from value transfers
+    in successful calls of call traces.
+    """
+
+    _chain: Chain
+    _token: Optional[Address]
+    _deltas: Dict[Address, int]
+    _minted: int
+    _burned: int
+    _transfers: int
+
+    def __init__(self, chain: Chain, token: Optional[Address]):
+        self._chain = chain
+        self._token = token
+        self._deltas = {}
+        self._minted = 0
+        self._burned = 0
+        self._transfers = 0
+
+    def __repr__(self) -> str:
+        token = "Ether" if self._token is None else str(self._token)
+        return f"TokenFlows({token}, transfers={self._transfers}, minted={self._minted}, burned={self._burned})"
+
+    def __getitem__(self, account: Union[Account, Address, str]) -> int:
+        if isinstance(account, Account):
+            account = account.address
+        return self._deltas.get(Address(account), 0)
+
+    def __add__(self, other: TokenFlows) -> TokenFlows:
+        ret = TokenFlows(self._chain, self._token)
+        ret += self
+        ret += other
+        return ret
+
+    def __iadd__(self, other: TokenFlows) -> TokenFlows:
+        if not isinstance(other, TokenFlows):
+            return NotImplemented
+        if other._token != self._token:
+            raise ValueError("Cannot add flows of different tokens")
+
+        for account, delta in other._deltas.items():
+            self._add_delta(account, delta)
+        self._minted += other._minted
+        self._burned += other._burned
+        self._transfers += other._transfers
+        return self
+
+    def _add_delta(self, account: Address, delta: int) -> None:
+        delta += self._deltas.get(account, 0)
+        if delta == 0:
+            self._deltas.pop(account, None)
+        else:
+            self._deltas[account] = delta
+
+    def _add_transfer(self, from_: Address, to: Address, amount: int) -> None:
+        self._transfers += 1
+        if self._token is not None and from_ == Address.ZERO:
+            self._minted += amount
+        else:
+            self._add_delta(from_, -amount)
+        if self._token is not None and to == Address.ZERO:
+            self._burned += amount
+        else:
+            self._add_delta(to, amount)
+
+    @property
+    def token(self) -> Optional[Address]:
+        """
+        Address of the token or `None` for Ether.
+        """
+        return self._token
+
+    @property
+    def deltas(self) -> Dict[Address, int]:
+        """
+        Non-zero net balance changes of all involved accounts.
+        """
+        return dict(self._deltas)
+
+    @property
+    def minted(self) -> int:
+        """
+        Amount transferred from the zero address. Always zero for Ether.
+        """
+        return self._minted
+
+    @property
+    def burned(self) -> int:
+        """
+        Amount transferred to the zero address. Always zero for Ether.
+        """
+        return self._burned
+
+    @property
+    def supply_delta(self) -> int:
+        """
+        Expected change of the total supply, i.e. `minted - burned`. Equal to the sum of all `deltas`.
+        """
+        return self._minted - self._burned
+
+    @property
+    def transfers(self) -> int:
+        """
+        Number of aggregated transfers.
+        """
+        return self._transfers
+
+    def _balance_of(self, account: Address, block: Union[int, str]) -> int:
+        if self._token is None:
+            return self._chain.chain_interface.get_balance(str(account), block)
+
+        output = self._chain.chain_interface.call(
+            {
+                "to": str(self._token),
+                # balanceOf(address)
+                "data": bytes.fromhex("70a08231") + Abi.encode(["address"], [account]),
+            },
+            block,
+        )
+        return int.from_bytes(output[:32], "big")
+
+    def mismatches(
+        self,
+        before_block: int,
+        after_block: Union[int, str] = "latest",
+        accounts: Iterable[Union[Account, Address, str]] = (),
+    ) -> Dict[Address, Tuple[int, int]]:
+        """
+        Compare the net balance changes with the actual balance changes between two blocks.
+        Ether balance changes include transaction fees that are not part of the flows.
+
+        Args:
+            before_block: Block with balances before the aggregated transactions.
+            after_block: Block with balances after the aggregated transactions.
+            accounts: Additional accounts to check, e.g. accounts that should not be affected at all.
+
+        Returns:
+            Mapping of accounts with a mismatch to a tuple of the tracked and the actual balance change.
+        """
+        checked = dict.fromkeys(self._deltas)
+        for account in accounts:
+            if isinstance(account, Account):
+                account = account.address
+            checked[Address(account)] = None
+
+        ret = {}
+        for account in checked:
+            actual = self._balance_of(account, after_block) - self._balance_of(
+                account, before_block
+            )
+            if actual != self[account]:
+                ret[account] = (self[account], actual)
+        return ret
+
+
+# keccak256("Approval(address,address,uint256)")
+_ERC20_APPROVAL_TOPIC = bytes.fromhex(
+    "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+)
+
+
+class TokenGuardFindingKind(StrEnum):
+    UNBOUNDED_APPROVAL = "UNBOUNDED_APPROVAL"
+    UNEXPECTED_RECIPIENT = "UNEXPECTED_RECIPIENT"
+
+
+@dataclass(frozen=True)
+class TokenGuardFinding:
+    """
+    ERC-20 approval or transfer made by a watched contract that may leak funds.
+    """
+
+    kind: TokenGuardFindingKind
+    token: Address
+    owner: Address
+    "Watched contract granting the approval or sending the tokens"
+    counterparty: Address
+    "Approved spender or recipient of the tokens"
+    amount: int
+
+
+class TokenGuard:
+    """
+    Configuration of ERC-20 safety checks performed on all transactions of a chain, see `Chain.token_guard`.
+    Approvals granted by the watched contracts with an amount of at least `unbounded_threshold` and transfers
+    from the watched contracts to accounts other than the watched contracts and `allowed_recipients` are reported.
+    """
+
+    _watched: FrozenSet[Address]
+    _allowed_recipients: FrozenSet[Address]
+    _unbounded_threshold: int
+
+    def __init__(
+        self,
+        watched: Iterable[Union[Account, Address, str]],
+        allowed_recipients: Iterable[Union[Account, Address, str]] = (),
+        *,
+        unbounded_threshold: int = 2**255,
+    ):
+        self._watched = frozenset(_to_address(a) for a in watched)
+        self._allowed_recipients = frozenset(_to_address(a) for a in allowed_recipients)
+        self._unbounded_threshold = unbounded_threshold
+
+    @property
+    def watched(self) -> FrozenSet[Address]:
+        return self._watched
+
+    @property
+    def allowed_recipients(self) -> FrozenSet[Address]:
+        return self._allowed_recipients
+
+    @property
+    def unbounded_threshold(self) -> int:
+        return self._unbounded_threshold
+
+    def check(self, logs: Iterable[Dict]) -> List[TokenGuardFinding]:
+        """
+        Returns:
+            Findings for ERC-20 `Approval` and `Transfer` events in the given JSON-RPC logs.
+        """
+        findings = []
+        for log in logs:
+            topics = log["topics"]
+            # ERC-721 events share the selectors but have 4 topics
+            if len(topics) != 3:
+                continue
+            data = bytes.fromhex(log["data"][2:])
+            if len(data) != 32:
+                continue
+
+            topic = bytes.fromhex(topics[0][2:])
+            owner = Address(topics[1][-40:])
+            counterparty = Address(topics[2][-40:])
+            amount = int.from_bytes(data, "big")
+            if owner not in self._watched:
+                continue
+
+            if topic == _ERC20_APPROVAL_TOPIC and amount >= self._unbounded_threshold:
+                kind = TokenGuardFindingKind.UNBOUNDED_APPROVAL
+            elif (
+                topic == _ERC20_TRANSFER_TOPIC
+                and counterparty not in self._watched
+                and counterparty not in self._allowed_recipients
+            ):
+                kind = TokenGuardFindingKind.UNEXPECTED_RECIPIENT
+            else:
+                continue
+            findings.append(
+                TokenGuardFinding(
+                    kind, Address(log["address"]), owner, counterparty, amount
+                )
+            )
+        return findings
+
+
+def _to_address(account: Union[Account, Address, str]) -> Address:
+    if isinstance(account, Account):
+        return account.address
+    return Address(account)
+
+
+def _add_transfer_logs(flows: TokenFlows, logs: Iterable[Dict]) -> None:
+    assert flows.token is not None
+    for log in logs:
+        topics = log["topics"]
+        # ERC-721 Transfer events share the selector but have 4 topics
+        if (
+            len(topics) != 3
+            or bytes.fromhex(topics[0][2:]) != _ERC20_TRANSFER_TOPIC
+            or Address(log["address"]) != flows.token
+        ):
+            continue
+        data = bytes.fromhex(log["data"][2:])
+        if len(data) != 32:
+            continue
+        flows._add_transfer(
+            Address(topics[1][-40:]),
+            Address(topics[2][-40:]),
+            int.from_bytes(data, "big"),
+        )
 
 
 def _fetch_tx_data(f):
@@ -75,27 +456,152 @@ def _fetch_tx_data(f):
 
 def _fetch_tx_receipt(f):
     @functools.wraps(f)
-    def wrapper(self: TransactionAbc):
+    def wrapper(self: TransactionAbc, *args, **kwargs):
         if self._tx_receipt is None:
             self.wait()
         assert self._tx_receipt is not None
-        return f(self)
+        return f(self, *args, **kwargs)
 
     return wrapper
 
 
+def _format_summary_value(value: Any, chain: Chain) -> str:
+    if isinstance(value, (bytes, bytearray, memoryview)):
+        return "0x" + bytes(value).hex()
+    elif isinstance(value, Enum):
+        return f"{value.__class__.__name__}.{value.name}"
+    elif isinstance(value, (list, tuple)):
+        return "[" + ", ".join(_format_summary_value(v, chain) for v in value) + "]"
+    elif dataclasses.is_dataclass(value) and not isinstance(value, type):
+        return (
+            value.__class__.__name__
+            + "("
+            + ", ".join(
+                f"{f.name}={_format_summary_value(getattr(value, f.name), chain)}"
+                for f in fields(value)
+                if f.repr
+            )
+            + ")"
+        )
+    elif isinstance(value, Address):
+        return chain._labels.get(value, str(value))
+    elif isinstance(value, Account):
+        return str(value)
+    return repr(value)
+
+
 class ChainTransactions:
     _chain: Chain
-    _transactions: Dict[str, TransactionAbc]
-    _tx_hashes: List[str]
+    _transactions: OrderedDict[str, TransactionAbc]
+    _tx_hashes: Deque[str]
+    _tx_hashes_offset: int
+    _history_limit: Optional[int]
+    _pinned: Set[str]
 
     def __init__(self, chain: Chain):
         self._chain = chain
-        self._transactions = {}
-        self._tx_hashes = []
+        self._transactions = OrderedDict()
+        self._tx_hashes = deque()
+        self._tx_hashes_offset = 0
+        self._history_limit = None
+        self._pinned = set()
+
+    def __len__(self) -> int:
+        return self._tx_hashes_offset + len(self._tx_hashes)
+
+    @property
+    def history_limit(self) -> Optional[int]:
+        """
+        Maximum number of most recent transactions kept in memory, not counting pinned transactions.
+        Older transactions are pruned. `None` means no limit.
+        """
+        return self._history_limit
+
+    @history_limit.setter
+    def history_limit(self, value: Optional[int]) -> None:
+        if value is not None and value <= 0:
+            raise ValueError("history_limit must be positive or None")
+        self._history_limit = value
+        self._prune()
+
+    def pin(self, tx: Union[TransactionAbc, str]) -> None:
+        """
+        Keep the transaction in memory regardless of `history_limit`.
+        """
+        if isinstance(tx, TransactionAbc):
+            key = tx.tx_hash.lower()
+            self._transactions.setdefault(key, tx)
+        else:
+            key = tx.lower() if tx.startswith("0x") else "0x" + tx.lower()
+            # pinned transactions are always cached
+            self[key]
+        self._pinned.add(key)
+
+    def unpin(self, tx: Union[TransactionAbc, str]) -> None:
+        if isinstance(tx, TransactionAbc):
+            key = tx.tx_hash.lower()
+        else:
+            key = tx.lower() if tx.startswith("0x") else "0x" + tx.lower()
+        if key in self._pinned:
+            self._pinned.discard(key)
+            # the transaction becomes the first one to be evicted
+            self._transactions.move_to_end(key, last=False)
+            self._prune()
+
+    def _prune(self) -> None:
+        if self._history_limit is None:
+            return
+
+        pruned = []
+        while len(self._tx_hashes) > self._history_limit:
+            pruned.append(self._tx_hashes.popleft())
+        if len(pruned) > 0:
+            self._tx_hashes_offset += len(pruned)
+            # only hashes are kept for snapshots, pruned transactions are fetched again when accessed
+            self._chain._journal.record(self, self._tx_hashes_offset, pruned)
+
+        # the cache is insertion-ordered, evict the oldest transactions first and move pinned ones to the end
+        while len(self._transactions) - len(self._pinned) > self._history_limit:
+            key = next(iter(self._transactions))
+            if key in self._pinned:
+                self._transactions.move_to_end(key)
+            else:
+                del self._transactions[key]
+
+    def _undo(self, offset: int, pruned: List[str]) -> None:
+        self._tx_hashes.extendleft(reversed(pruned))
+        self._tx_hashes_offset -= len(pruned)
+
+    def _truncate(self, count: int) -> None:
+        # drop transactions registered after the first `count` ones, e.g. when reverting the chain
+        removed_count = len(self) - count
+        if removed_count <= 0:
+            return
+        removed = [
+            self._tx_hashes.pop()
+            for _ in range(min(removed_count, len(self._tx_hashes)))
+        ]
+        # transactions pruned meanwhile cannot be restored if the journal was cleared
+        self._tx_hashes_offset -= removed_count - len(removed)
+        for tx_hash in removed:
+            self._transactions.pop(tx_hash.lower(), None)
+            self._pinned.discard(tx_hash.lower())
+
+    def _clear(self) -> None:
+        # drop all transactions, e.g. when the chain state was replaced
+        self._transactions = OrderedDict()
+        self._tx_hashes = deque()
+        self._tx_hashes_offset = 0
+        self._pinned = set()
 
     def __getitem__(self, key: Union[str, int]) -> TransactionAbc:
         if isinstance(key, int):
+            if key >= 0:
+                if key < self._tx_hashes_offset:
+                    raise IndexError(
+                        f"Transaction with index {key} was pruned from history"
+                    )
+                key -= self._tx_hashes_offset
             key = self._tx_hashes[key]
 
         if not key.startswith("0x"):
@@ -178,14 +684,132 @@ class ChainTransactions:
             tx_params["accessList"] = tx_data["accessList"]
             tx_params["chainId"] = 2
             tx = Eip1559Transaction(key, tx_params, abi, return_type, self._chain)
+        elif type == 4:
+            tx_params["type"] = 4
+            tx_params["maxFeePerGas"] = int(tx_data["maxFeePerGas"], 16)
+            tx_params["maxPriorityFeePerGas"] = int(tx_data["maxPriorityFeePerGas"], 16)
+            tx_params["accessList"] = tx_data["accessList"]
+            tx_params["authorizationList"] = tx_data.get("authorizationList", [])
+            tx_params["chainId"] = int(tx_data["chainId"], 16)
+            tx = Eip7702Transaction(key, tx_params, abi, return_type, self._chain)
         else:
             raise ValueError(f"Unknown transaction type {type}")
 
         self._transactions[key] = tx
+        self._prune()
         return tx
 
     def register_tx(self, tx_hash: str):
         self._tx_hashes.append(tx_hash)
+        self._prune()
+
+
+class EventSubscription:
+    """
+    Cursor over events emitted on a chain. Iterating yields decoded events from all blocks mined since the last iteration
+    (starting at `since_block`) and stops once the latest block is reached; the subscription can be iterated again later
+    to consume events from newly mined blocks.
+    """
+
+    _chain: Chain
+    _next_block: int
+    _types: Optional[Tuple[type, ...]]
+    _selectors: Optional[List[str]]
+    _address: Optional[List[str]]
+
+    def __init__(
+        self,
+        chain: Chain,
+        since_block: int,
+        types: Optional[Iterable[type]],
+        address: Optional[Union[Account, Address, Iterable[Union[Account, Address]]]],
+    ):
+        self._chain = chain
+        self._next_block = since_block
+
+        if types is None:
+            self._types = None
+            self._selectors = None
+        else:
+            self._types = tuple(types)
+            if all(hasattr(t, "selector") for t in self._types):
+                self._selectors = [
+                    "0x" + t.selector.hex()  # pyright: ignore reportGeneralTypeIssues
+                    for t in self._types
+                ]
+            else:
+                # unknown/external events cannot be filtered by topic
+                self._selectors = None
+
+        if address is None:
+            self._address = None
+        elif isinstance(address, (Account, Address)):
+            self._address = [
+                str(address.address if isinstance(address, Account) else address)
+            ]
+        else:
+            self._address = [
+                str(a.address if isinstance(a, Account) else a) for a in address
+            ]
+
+    @property
+    def next_block(self) -> int:
+        """
+        Number of the first block not processed yet.
+        """
+        return self._next_block
+
+    def __iter__(self) -> Iterator:
+        latest = self._chain.chain_interface.get_block_number()
+        if self._next_block > latest:
+            return
+
+        logs = self._chain.chain_interface.get_logs(
+            from_block=self._next_block,
+            to_block=latest,
+            address=self._address,
+            topics=[self._selectors] if self._selectors is not None else None,
+        )
+        logs.sort(
+            key=lambda l: (int(l["blockNumber"], 16), int(l["logIndex"], 16))
+        )
+
+        addresses = (
+            {Address(a) for a in self._address} if self._address is not None else None
+        )
+        # block number => transaction hashes in the order of their logs
+        block_txs: Dict[int, Dict[str, None]] = {}
+        for l in logs:
+            block = block_txs.setdefault(int(l["blockNumber"], 16), {})
+            block[l["transactionHash"]] = None
+
+        for block_number, tx_hashes in block_txs.items():
+            for tx_hash in tx_hashes:
+                for event in self._chain.txs[tx_hash].events:
+                    if addresses is not None and event.origin.address not in addresses:
+                        continue
+                    if self._types is not None and not isinstance(event, self._types):
+                        continue
+                    yield event
+
+            # an iteration stopped in the middle of a block resumes at its start
+            self._next_block = block_number + 1
+
+        self._next_block = latest + 1
+
+    def poll(self) -> list:
+        """
+        Return events from all blocks mined since the last poll or iteration.
+        """
+        return list(self)
+
+    def follow(self, poll_interval: float = 1.0) -> Iterator:
+        """
+        Yield events indefinitely, waiting `poll_interval` seconds for new blocks when the latest block is reached.
+        """
+        while True:
+            yield from self
+            time.sleep(poll_interval)
 
 
 class TransactionAbc(ABC, Generic[T]):
@@ -201,6 +825,17 @@ class TransactionAbc(ABC, Generic[T]):
     _error: Optional[TransactionRevertedError]
     _raw_error: Optional[UnknownTransactionRevertedError]
     _events: Optional[List]
+    _call_trace: Optional[CallTrace]
+    _call_path_hash: Optional[bytes]
+    _revert_count: int
+    _gas_estimate: Optional[int]
+    _revert_data_truncated: bool
+    _log_data_truncated: bool
+    _return_data: Optional[bytes]
+    _return_data_truncated: bool
+    _uninitialized_reads: Optional[List[Tuple[Account, int]]]
+    _opcode_histogram: Optional[Dict[str, int]]
+    _storage_changes: Optional[Dict[Address, Dict[int, Tuple[int, int]]]]
 
     def __init__(
         self,
@@ -223,6 +858,56 @@ class TransactionAbc(ABC, Generic[T]):
         self._error = None
         self._raw_error = None
         self._events = None
+        self._call_trace = None
+        self._call_path_hash = None
+        self._revert_count = chain._revert_count
+        self._gas_estimate = None
+        self._revert_data_truncated = False
+        self._log_data_truncated = False
+        self._return_data = None
+        self._return_data_truncated = False
+        self._uninitialized_reads = None
+        self._opcode_histogram = None
+        self._storage_changes = None
+
+    def refresh(self) -> None:
+        """
+        Drop all cached data of the transaction so that they are fetched from the chain again on next access.
+        """
+        self._tx_data = None
+        self._tx_receipt = None
+        self._trace_transaction = None
+        self._debug_trace_transaction = None
+        self._error = None
+        self._raw_error = None
+        self._events = None
+        self._call_trace = None
+        self._call_path_hash = None
+        self._revert_count = self._chain._revert_count
+        self._revert_data_truncated = False
+        self._log_data_truncated = False
+        self._return_data = None
+        self._return_data_truncated = False
+        self._opcode_histogram = None
+        self._storage_changes = None
+
+    def _check_reverted(self) -> None:
+        # the chain may have been reverted past this transaction since the cached data were fetched
+        if self._revert_count == self._chain._revert_count:
+            return
+
+        receipt = self._chain.chain_interface.get_transaction_receipt(self._tx_hash)
+        if (
+            receipt is None
+            or self._tx_receipt is None
+            or receipt["blockHash"] != self._tx_receipt["blockHash"]
+        ):
+            self.refresh()
+            if receipt is None:
+                raise RuntimeError(
+                    f"Transaction {self._tx_hash} was removed from the chain by a revert"
+                )
+        self._revert_count = self._chain._revert_count
 
     @property
     def tx_hash(self) -> str:
@@ -268,6 +953,45 @@ class TransactionAbc(ABC, Generic[T]):
             "gas"
         ]  # pyright: ignore reportTypedDictNotRequiredAccess
 
+    @property
+    def gas_estimate(self) -> Optional[int]:
+        """
+        Gas estimate the gas limit was computed from when `gas_limit="auto"` was used, `None` otherwise.
+        """
+        return self._gas_estimate
+
+    @property
+    @_fetch_tx_receipt
+    def return_data_truncated(self) -> bool:
+        """
+        Whether `raw_return_value` is truncated to `general.max_return_data_size` bytes.
+        """
+        if self.status != TransactionStatusEnum.SUCCESS:
+            return False
+
+        assert self._tx_receipt is not None
+        if self._tx_receipt.get("contractAddress") is not None:
+            return False
+        self._fetch_return_data()
+        return self._return_data_truncated
+
+    @property
+    @_fetch_tx_receipt
+    def revert_data_truncated(self) -> bool:
+        """
+        Whether the data of `raw_error` are truncated to `general.max_revert_data_size` bytes.
+        """
+        self.raw_error
+        return self._revert_data_truncated
+
+    @property
+    @_fetch_tx_receipt
+    def log_data_truncated(self) -> bool:
+        """
+        Whether the data of at least one event in `raw_events` are truncated to `general.max_log_data_size` bytes.
+        """
+        return self._log_data_truncated
+
     @property
     def nonce(self) -> int:
         return self._tx_params[
@@ -336,13 +1060,26 @@ class TransactionAbc(ABC, Generic[T]):
             if receipt is None:
                 return TransactionStatusEnum.PENDING
             else:
-                self._tx_receipt = receipt
+                self._set_tx_receipt(receipt)
 
         if int(self._tx_receipt["status"], 16) == 0:
             return TransactionStatusEnum.FAILURE
         else:
             return TransactionStatusEnum.SUCCESS
 
+    def _set_tx_receipt(self, receipt: Dict[str, Any]) -> None:
+        # only the truncated log data are kept in memory
+        max_size = get_config().general.max_log_data_size
+        if max_size is not None:
+            for log in receipt["logs"]:
+                prefix = "0x" if log["data"].startswith("0x") else ""
+                data = log["data"][len(prefix) :]
+                # two hex characters per byte
+                if len(data) > 2 * max_size:
+                    log["data"] = prefix + data[: 2 * max_size]
+                    self._log_data_truncated = True
+        self._tx_receipt = receipt
+
     def wait(self, confirmations: Optional[int] = None) -> None:
         self._chain._wait_for_transaction(self, confirmations)
 
@@ -413,6 +1150,18 @@ class TransactionAbc(ABC, Generic[T]):
         self._events = self._chain._process_events(self)
         return self._events
 
+    @property
+    @_fetch_tx_receipt
+    def ignored_events_count(self) -> int:
+        """
+        Number of events emitted by the transaction that were dropped from `events` by
+        [Chain.ignore_event][wake.development.core.Chain.ignore_event] filters.
+        """
+        assert self._tx_receipt is not None
+        return sum(
+            1 for log in self._tx_receipt["logs"] if self._chain._is_log_ignored(log)
+        )
+
     @property
     @_fetch_tx_receipt
     def raw_events(self) -> List[UnknownEvent]:
@@ -432,6 +1181,19 @@ class TransactionAbc(ABC, Generic[T]):
             ret.append(UnknownEvent(topics, data))
         return ret
 
+    @property
+    @_fetch_tx_receipt
+    def token_guard_findings(self) -> List[TokenGuardFinding]:
+        """
+        Risky ERC-20 approvals and transfers of the contracts watched by `chain.token_guard`.
+        Empty if no token guard is set.
+        """
+        guard = self._chain._token_guard
+        if guard is None:
+            return []
+        assert self._tx_receipt is not None
+        return guard.check(self._tx_receipt["logs"])
+
     @property
     @_fetch_tx_receipt
     def error(self) -> Optional[TransactionRevertedError]:
@@ -444,9 +1206,160 @@ class TransactionAbc(ABC, Generic[T]):
         raw_error = self.raw_error
         assert raw_error is not None
 
+        if isinstance(raw_error, Halt):
+            self._error = raw_error
+            return self._error
+
+        if self._revert_data_truncated:
+            raise DataTruncatedError(
+                f"Revert data of transaction {self.tx_hash} were truncated to {len(raw_error.data)} bytes "
+                "(general.max_revert_data_size) and cannot be decoded, use tx.raw_error instead"
+            )
+
         self._error = self._chain._process_revert_data(self, raw_error.data)
+        self._set_panic_location()
         return self._error
 
+    def _set_panic_location(self) -> None:
+        # computed when the error is created, so that printing the error never fetches a trace
+        if not isinstance(self._error, Panic):
+            return
+
+        if self._call_trace is not None:
+            origin = self._call_trace._panic_origin()
+            if origin is not None:
+                self._error.location = origin._location
+            return
+
+        try:
+            self._fetch_debug_trace_transaction()
+            assert self._debug_trace_transaction is not None
+            self._error.location = process_debug_trace_for_panic_location(
+                self, self._debug_trace_transaction  # pyright: ignore reportGeneralTypeIssues
+            )
+        except (JsonRpcError, HTTPError, ValueError):
+            # location is optional, the error is raised regardless
+            pass
+
+    @property
+    @_fetch_tx_receipt
+    def revert_source_location(self) -> Optional[SourceLocation]:
+        """
+        Source file and line of the revert the transaction failed with, `None` if the transaction succeeded
+        or the location cannot be determined (e.g. missing `pytypes` of the reverting contract).
+        """
+        if self.status == TransactionStatusEnum.SUCCESS:
+            return None
+
+        try:
+            self._fetch_debug_trace_transaction()
+        except (JsonRpcError, HTTPError):
+            return None
+        assert self._debug_trace_transaction is not None
+        return process_debug_trace_for_revert_location(
+            self, self._debug_trace_transaction  # pyright: ignore reportGeneralTypeIssues
+        )
+
+    @property
+    def precompile_gas_adjustment(self) -> int:
+        """
+        Difference between the gas costs of precompiled contract calls made by the transaction priced by
+        `chain.precompile_gas_overrides` and their native gas costs in the active hardfork. Zero if no overrides are set.
+        Calls of precompiles with a native cost unknown to Wake (see `get_precompiled_gas_cost`) and failed calls are not re-priced.
+        """
+        overrides = self._chain._precompile_gas_overrides
+        if len(overrides) == 0:
+            return 0
+
+        if self._tx_receipt is None:
+            self.wait()
+        self._fetch_debug_trace_transaction()
+        assert self._debug_trace_transaction is not None
+
+        adjustment = 0
+        for address, data in process_debug_trace_for_precompile_calls(
+            self, self._debug_trace_transaction, overrides.keys()  # pyright: ignore reportGeneralTypeIssues
+        ):
+            native_cost = get_precompiled_gas_cost(
+                address, data, self._chain._hardfork
+            )
+            if native_cost is None:
+                continue
+            cost = overrides[address]
+            if callable(cost):
+                cost = cost(data)
+            adjustment += cost - native_cost
+        return adjustment
+
+    @property
+    def adjusted_gas_used(self) -> int:
+        """
+        Gas used by the transaction with precompiled contract calls priced by `chain.precompile_gas_overrides`.
+        """
+        return self.gas_used + self.precompile_gas_adjustment
+
+    def storage_changes(self) -> Dict[Account, Dict[int, Tuple[int, int]]]:
+        """
+        Storage slots changed by the transaction. Writes performed in `DELEGATECALL` (and `CALLCODE`) frames
+        are attributed to the account owning the storage (e.g. a proxy), not to the account whose code was executed.
+        Writes in reverted calls and writes restoring the original value are omitted.
+        Values before the transaction are read with the `prestateTracer`, which must be supported by the client.
+
+        Returns:
+            Mapping of accounts to changed storage slots and their values before and after the transaction.
+        """
+        self._check_reverted()
+        if self._tx_receipt is None:
+            self.wait()
+
+        if self._storage_changes is None:
+            self._fetch_debug_trace_transaction()
+            assert self._debug_trace_transaction is not None
+            writes = process_debug_trace_for_storage_writes(
+                self, self._debug_trace_transaction  # pyright: ignore reportGeneralTypeIssues
+            )
+
+            prestate: Dict[Address, Dict] = {}
+            if len(writes) > 0:
+                response = self._chain.chain_interface.debug_trace_transaction(
+                    self._tx_hash, {"tracer": "prestateTracer"}
+                )
+                prestate = {Address(a): state for a, state in response.items()}
+
+            self._storage_changes = {}
+            for address, slots in writes.items():
+                storage = {
+                    int(slot, 16): int(value, 16)
+                    for slot, value in prestate.get(address, {})
+                    .get("storage", {})
+                    .items()
+                }
+                changes = {
+                    slot: (storage.get(slot, 0), value)
+                    for slot, value in slots.items()
+                    if storage.get(slot, 0) != value
+                }
+                if len(changes) > 0:
+                    self._storage_changes[address] = changes
+
+        return {
+            Account(address, self._chain): dict(changes)
+            for address, changes in self._storage_changes.items()
+        }
+
+    @property
+    def uninitialized_reads(self) -> List[Tuple[Account, int]]:
+        """
+        Pairs of account and storage slot read by the transaction (SLOAD returning zero) that were never written
+        in the chain session before. Available only if `chain.detect_uninitialized_reads` was enabled when the transaction was mined.
+        """
+        if self._uninitialized_reads is None:
+            raise ValueError(
+                "Uninitialized reads were not recorded for this transaction, "
+                "set chain.detect_uninitialized_reads = True before sending it"
+            )
+        return self._uninitialized_reads
+
     @property
     @_fetch_tx_receipt
     def raw_error(self) -> Optional[UnknownTransactionRevertedError]:
@@ -505,10 +1418,37 @@ class TransactionAbc(ABC, Generic[T]):
         else:
             raise NotImplementedError
 
+        if len(revert_data) == 0:
+            message = self._get_halt_message()
+            reason = parse_halt_reason(message) if message is not None else None
+            if reason is not None:
+                assert message is not None
+                self._raw_error = Halt(reason=reason, message=message)
+                self._raw_error.tx = self
+                return self._raw_error
+
+        max_size = get_config().general.max_revert_data_size
+        if max_size is not None and len(revert_data) > max_size:
+            revert_data = revert_data[:max_size]
+            self._revert_data_truncated = True
+            self._truncate_trace_output(revert_data)
+
         self._raw_error = UnknownTransactionRevertedError(revert_data)
         self._raw_error.tx = self
         return self._raw_error
 
+    def _get_halt_message(self) -> Optional[str]:
+        # only traces already fetched to obtain the revert data are used, no trace is requested just for the halt reason
+        if self._trace_transaction is not None:
+            return self._trace_transaction[0].get("error")
+        if self._debug_trace_transaction is None:
+            return None
+
+        struct_logs = self._debug_trace_transaction.get("structLogs", [])
+        if len(struct_logs) == 0 or struct_logs[-1].get("op") == "REVERT":
+            return None
+        return struct_logs[-1].get("error")
+
     @property
     @_fetch_tx_receipt
     def return_value(self) -> T:
@@ -517,6 +1457,8 @@ class TransactionAbc(ABC, Generic[T]):
         if self._return_type is type(None):
             return None  # pyright: ignore reportGeneralTypeIssues
 
+        self._check_return_data_truncated()
+
         if isinstance(raw_value, Account):
             return self._return_type(raw_value.address, self._chain)
         elif isinstance(raw_value, bytearray):
@@ -531,6 +1473,56 @@ class TransactionAbc(ABC, Generic[T]):
                 f"Unexpected return type from transaction {self.tx_hash}: {type(raw_value)}"
             )
 
+    @_fetch_tx_receipt
+    def decode_return(
+        self,
+        target: Union[
+            Type, Tuple[Type, ...], Callable, Dict[str, Any], List[Dict[str, Any]]
+        ],
+    ) -> Any:
+        """
+        Decode the raw return data into a type other than the static return type of the transaction,
+        e.g. when calling a proxy or a fallback function. `target` is one of:
+
+        - a `pytypes` function (e.g. `IERC20.balanceOf`), the data are decoded as its return value,
+        - a JSON ABI of a function (a dictionary with `outputs`) or a list of its outputs,
+        - a type (e.g. `uint256`, `bytes` or a `pytypes` struct) or a tuple of types, the data are decoded using `abi.decode`.
+        """
+        raw_value = self.raw_return_value
+        if not isinstance(raw_value, bytearray):
+            raise ValueError("Contract deployments do not return data")
+        self._check_return_data_truncated()
+        output = bytes(raw_value)
+
+        if isinstance(target, dict) or isinstance(target, list):
+            outputs = target["outputs"] if isinstance(target, dict) else target
+            decoded = Abi.decode(
+                [
+                    eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
+                    for arg in fix_library_abi(outputs)
+                ],
+                output,
+            )
+            if len(decoded) == 1:
+                return decoded[0]
+            return decoded
+        elif isinstance(target, tuple):
+            return abi.decode(output, list(target))
+        elif not isinstance(target, type) and hasattr(target, "selector"):
+            contract = get_class_that_defined_method(target)
+            return_types = get_args(get_type_hints(target)["return"])
+            return_type = next(
+                get_args(t)[0] for t in return_types if get_origin(t) is TransactionAbc
+            )
+            return self._chain._process_return_data(
+                self,
+                output,
+                contract._abi[target.selector],  # pyright: ignore reportGeneralTypeIssues
+                return_type,
+            )
+        else:
+            return abi.decode(output, [target])
+
     @property
     @_fetch_tx_receipt
     def raw_return_value(self) -> Union[Account, bytearray]:
@@ -546,6 +1538,33 @@ class TransactionAbc(ABC, Generic[T]):
         ):
             return Account(self._tx_receipt["contractAddress"], self._chain)
 
+        return bytearray(self._fetch_return_data())
+
+    def _check_return_data_truncated(self) -> None:
+        if self._return_data_truncated:
+            raise DataTruncatedError(
+                f"Return data of transaction {self.tx_hash} were truncated to {len(self._return_data or b'')} bytes "
+                "(general.max_return_data_size) and cannot be decoded, use tx.raw_return_value instead"
+            )
+
+    def _truncate_trace_output(self, output: bytes) -> None:
+        # keep only the truncated data in the cached traces so that the full data can be garbage collected
+        if (
+            self._trace_transaction is not None
+            and self._trace_transaction[0].get("result") is not None
+            and "output" in self._trace_transaction[0]["result"]
+        ):
+            self._trace_transaction[0]["result"]["output"] = "0x" + output.hex()
+        if (
+            self._debug_trace_transaction is not None
+            and "returnValue" in self._debug_trace_transaction
+        ):
+            self._debug_trace_transaction["returnValue"] = output.hex()
+
+    def _fetch_return_data(self) -> bytes:
+        if self._return_data is not None:
+            return self._return_data
+
         chain_interface = self._chain.chain_interface
         if isinstance(chain_interface, AnvilChainInterface):
             self._fetch_trace_transaction()
@@ -592,23 +1611,321 @@ class TransactionAbc(ABC, Generic[T]):
         else:
             raise NotImplementedError
 
-        return bytearray(output)
+        output = bytes(output)
+        max_size = get_config().general.max_return_data_size
+        if max_size is not None and len(output) > max_size:
+            output = output[:max_size]
+            self._return_data_truncated = True
+            self._truncate_trace_output(output)
+
+        self._return_data = output
+        return output
+
+    def gas_forwarding_report(self) -> List[GasForwardingInfo]:
+        """
+        Returns:
+            Gas forwarding information for each external call made during the transaction, in execution order.
+        """
+        self._check_reverted()
+        if self._tx_receipt is None:
+            self.wait()
+        self._fetch_debug_trace_transaction()
+        assert self._debug_trace_transaction is not None
+
+        logs = self._debug_trace_transaction["structLogs"]
+        report: List[GasForwardingInfo] = []
+        addresses: List[Address] = [
+            self.to.address if self.to is not None else Address(0)
+        ]
+        # (depth of the calling frame, index into report or None for contract creations)
+        frames: List[Tuple[int, Optional[int]]] = []
+
+        for i, log in enumerate(logs):
+            depth = log["depth"]
+
+            while len(frames) > 0 and depth <= frames[-1][0]:
+                _, index = frames.pop()
+                addresses.pop()
+                if index is None:
+                    continue
+                status = int(log["stack"][-1], 16) != 0
+                report[index] = dataclasses.replace(
+                    report[index],
+                    status=status,
+                    out_of_gas=not status
+                    and logs[i - 1]["op"] not in {"REVERT", "RETURN", "STOP"},
+                )
+
+            entered = i + 1 < len(logs) and logs[i + 1]["depth"] == depth + 1
+
+            if log["op"] in {"CREATE", "CREATE2"}:
+                if entered:
+                    # created address is not needed for the report
+                    addresses.append(Address(0))
+                    frames.append((depth, None))
+                continue
+            if log["op"] not in {"CALL", "CALLCODE", "DELEGATECALL", "STATICCALL"}:
+                continue
+
+            callee = Address(int(log["stack"][-2], 16))
+            caller = addresses[-1]
+            if entered:
+                gas_forwarded = logs[i + 1]["gas"]
+                if log["op"] in {"CALL", "CALLCODE"} and int(log["stack"][-3], 16) > 0:
+                    gas_forwarded -= 2300
+            else:
+                gas_forwarded = None
+
+            report.append(
+                GasForwardingInfo(
+                    depth,
+                    log["op"],
+                    caller,
+                    callee,
+                    int(log["stack"][-1], 16),
+                    log["gas"],
+                    gas_forwarded,
+                    entered
+                    or (
+                        i + 1 < len(logs) and int(logs[i + 1]["stack"][-1], 16) != 0
+                    ),
+                    False,
+                )
+            )
+            if entered:
+                addresses.append(
+                    caller if log["op"] in {"DELEGATECALL", "CALLCODE"} else callee
+                )
+                frames.append((depth, len(report) - 1))
+
+        return report
+
+    def event_gas_report(self) -> List[EventGasInfo]:
+        """
+        Returns:
+            Gas spent on emitting events grouped by the event type, sorted by the gas in descending order.
+        """
+        from .core import events
+
+        self._check_reverted()
+        if self._tx_receipt is None:
+            self.wait()
+        self._fetch_debug_trace_transaction()
+        assert self._debug_trace_transaction is not None
+
+        # topic => [count, data size, gas]
+        stats: Dict[Optional[bytes], List[int]] = {}
+        for log in self._debug_trace_transaction["structLogs"]:
+            op = log["op"]
+            if not op.startswith("LOG"):
+                continue
+
+            stack = log["stack"]
+            topic = (
+                int(stack[-3], 16).to_bytes(32, "big") if op != "LOG0" else None
+            )
+            entry = stats.setdefault(topic, [0, 0, 0])
+            entry[0] += 1
+            entry[1] += int(stack[-2], 16)
+            entry[2] += log["gasCost"]
+
+        report = []
+        for topic, (count, data_size, gas) in stats.items():
+            name = None
+            if topic is not None and topic in events and len(events[topic]) > 0:
+                name = next(iter(events[topic].values())).__name__
+            report.append(EventGasInfo(topic, name, count, data_size, gas))
+        report.sort(key=lambda info: info.gas, reverse=True)
+        return report
+
+    def token_flows(self, token: Union[Account, Address, str]) -> TokenFlows:
+        """
+        Returns:
+            Net balance changes of the ERC-20 `token` collected from `Transfer` events emitted in the transaction.
+        """
+        if isinstance(token, Account):
+            token = token.address
+        flows = TokenFlows(self._chain, Address(token))
+        if self.status == TransactionStatusEnum.SUCCESS:
+            assert self._tx_receipt is not None
+            _add_transfer_logs(flows, self._tx_receipt["logs"])
+        return flows
+
+    def eth_flows(self) -> TokenFlows:
+        """
+        Net Ether balance changes caused by value transfers in successful calls of the transaction.
+        Transaction fees and `SELFDESTRUCT` transfers are not included.
+
+        Returns:
+            Net Ether balance changes of the accounts involved in the transaction.
+        """
+        flows = TokenFlows(self._chain, None)
+        if self.status != TransactionStatusEnum.SUCCESS:
+            return flows
+
+        # (trace, address of the executing context of the parent frame)
+        stack = [(self.call_trace, self.from_.address)]
+        while len(stack) > 0:
+            trace, caller = stack.pop()
+            if not trace.status:
+                continue
+
+            if trace.kind in {CallTraceKind.DELEGATECALL, CallTraceKind.CALLCODE}:
+                context = caller
+            else:
+                assert trace.address is not None
+                context = trace.address
+                if trace.value > 0:
+                    flows._add_transfer(caller, context, trace.value)
+
+            stack.extend((subtrace, context) for subtrace in trace.subtraces)
+        return flows
+
+    def opcode_histogram(self) -> Dict[str, int]:
+        """
+        Count executed opcodes in the transaction, including all nested calls.
+        The full debug trace is reused if already fetched, otherwise a light trace without stack, memory and storage is requested.
+
+        Returns:
+            Number of executions of each opcode, sorted by the count in descending order.
+        """
+        self._check_reverted()
+        if self._tx_receipt is None:
+            self.wait()
+
+        if self._opcode_histogram is None:
+            if self._debug_trace_transaction is not None:
+                logs = self._debug_trace_transaction["structLogs"]
+            else:
+                logs = self._chain.chain_interface.debug_trace_transaction(
+                    self._tx_hash,
+                    {
+                        "disableStack": True,
+                        "disableMemory": True,
+                        "disableStorage": True,
+                    },
+                )["structLogs"]
+            self._opcode_histogram = dict(
+                Counter(log["op"] for log in logs).most_common()
+            )
+        return dict(self._opcode_histogram)
+
+    def summary(self) -> str:
+        """
+        Deterministic human-readable multi-line summary of the transaction suitable for golden-file testing.
+        Includes the sender, recipient, called function with decoded arguments, value, status, gas used,
+        emitted events and the revert error. Values that change between runs (e.g. the transaction hash,
+        block number or timestamp) are intentionally omitted.
+        """
+        self._check_reverted()
+        if self._tx_receipt is None:
+            self.wait()
+
+        lines = [f"from: {self.from_}"]
+        data = self.data
+        to = self.to
+        if to is None:
+            lines.append("to: <deployment>")
+        else:
+            lines.append(f"to: {to}")
+
+            if len(data) < 4:
+                kind = "<fallback>" if len(data) > 0 else "<receive>"
+                lines.append(f"function: {kind}")
+            elif self._abi is not None and self._abi.get("type") == "function":
+                inputs = fix_library_abi(self._abi["inputs"])
+                types = [
+                    eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
+                    for arg in inputs
+                ]
+                signature = f"{self._abi['name']}({','.join(types)})"
+                lines.append(f"function: {signature} [0x{data[:4].hex()}]")
+                decoded = Abi.decode(types, data[4:])
+                for i, (arg, value) in enumerate(zip(inputs, decoded)):
+                    name = arg.get("name") or f"arg{i}"
+                    value = _format_summary_value(value, self._chain)
+                    lines.append(f"  {name}: {value}")
+            else:
+                lines.append(f"function: <unknown> [0x{data[:4].hex()}]")
+                if len(data) > 4:
+                    lines.append(f"  data: 0x{data[4:].hex()}")
+
+        lines.append(f"value: {int(self.value)}")
+        lines.append(f"status: {self.status.name}")
+        lines.append(f"gas used: {self.gas_used}")
+
+        if self.status == TransactionStatusEnum.SUCCESS:
+            if to is None:
+                lines.append(f"created: {self.raw_return_value}")
+            events = self.events
+            if len(events) > 0:
+                lines.append("events:")
+                lines.extend(
+                    f"  {_format_summary_value(e, self._chain)}" for e in events
+                )
+        else:
+            lines.append(f"error: {_format_summary_value(self.error, self._chain)}")
+
+        return "\n".join(lines)
+
+    @property
+    def call_path_hash(self) -> bytes:
+        """
+        Hash of the call path shape of the transaction, i.e. the sequence of call kinds, called addresses,
+        function selectors and statuses of all calls. Transactions calling the same functions of the same contracts
+        share the same hash regardless of argument values. Computed from a `callTracer` trace without tracing
+        individual instructions, so it is much cheaper than `call_trace`.
+        """
+        self._check_reverted()
+        if self._call_path_hash is not None:
+            return self._call_path_hash
+
+        if self._tx_receipt is None:
+            self.wait()
+        trace = self._chain.chain_interface.debug_trace_transaction(
+            self._tx_hash, {"tracer": "callTracer"}
+        )
+        h = hashlib.blake2b(digest_size=16)
+        stack = [(trace, 0)]
+        while len(stack) > 0:
+            frame, depth = stack.pop()
+            kind = frame["type"]
+            if kind in {"CREATE", "CREATE2"}:
+                # addresses of created contracts differ between transactions
+                target = ""
+            else:
+                target = (
+                    frame.get("to", "").lower() + ":" + frame.get("input", "")[:10]
+                )
+            h.update(f"{depth}:{kind}:{target}:{'error' in frame};".encode())
+            stack.extend(
+                (call, depth + 1) for call in reversed(frame.get("calls", []))
+            )
+        self._call_path_hash = h.digest()
+        return self._call_path_hash
 
     @property
-    @_fetch_tx_data
-    @_fetch_tx_receipt
     def call_trace(self) -> CallTrace:
-        if self._debug_trace_transaction is None:
-            self._fetch_debug_trace_transaction()
+        self._check_reverted()
+        if self._call_trace is not None:
+            return self._call_trace
+
+        if self._tx_data is None:
+            self._tx_data = self._chain.chain_interface.get_transaction(self.tx_hash)
+        if self._tx_receipt is None:
+            self.wait()
+        self._fetch_debug_trace_transaction()
         assert self._debug_trace_transaction is not None
         assert self._tx_data is not None
 
-        return CallTrace.from_debug_trace(
+        self._call_trace = CallTrace.from_debug_trace(
             self,
             self._debug_trace_transaction,  # pyright: ignore reportGeneralTypeIssues
             self._tx_params,
             int(self._tx_data["gas"], 16),
         )
+        self._set_panic_location()
+        return self._call_trace
 
     @property
     @abstractmethod
@@ -717,6 +2034,21 @@ class Eip1559Transaction(TransactionAbc[T]):
         return TransactionTypeEnum.EIP1559
 
 
+class Eip7702Transaction(Eip1559Transaction[T]):
+    @property
+    def authorization_list(self) -> List[Authorization]:
+        assert "authorizationList" in self._tx_params
+        return [
+            Authorization.from_json(entry)
+            for entry in self._tx_params["authorizationList"]
+        ]
+
+    @property
+    def type(self) -> TransactionTypeEnum:
+        assert "type" in self._tx_params and self._tx_params["type"] == 4
+        return TransactionTypeEnum.EIP7702
+
+
 @dataclass
 class TransactionRevertedError(Exception):
     tx: Optional[TransactionAbc] = field(
@@ -729,12 +2061,32 @@ class TransactionRevertedError(Exception):
         )
         return f"{self.__class__.__qualname__}({s})"
 
+    @property
+    def source_location(self) -> Optional[SourceLocation]:
+        """
+        Source file and line of the revert statement (or failed assertion) that raised the error.
+        Available only for errors raised by transactions, `None` otherwise.
+        """
+        if self.tx is None:
+            return None
+        return self.tx.revert_source_location
+
 
 @dataclass
 class UnknownTransactionRevertedError(TransactionRevertedError):
     data: bytes
 
 
+@dataclass
+class ExternalError(TransactionRevertedError):
+    """
+    Error decoded using an ABI registered with `register_error_abi` or `Chain.register_abi`.
+    """
+
+    name: str
+    arguments: Dict[str, Any]
+
+
 @dataclass
 class Error(TransactionRevertedError):
     _abi = {
@@ -768,6 +2120,24 @@ class PanicCodeEnum(IntEnum):
     INVALID_INTERNAL_FUNCTION_CALL = 0x51
     "Called invalid internal function"
 
+    @property
+    def description(self) -> str:
+        return _panic_code_descriptions[self]
+
+
+_panic_code_descriptions = {
+    PanicCodeEnum.GENERIC: "Generic compiler panic",
+    PanicCodeEnum.ASSERT_FAIL: "Assert evaluated to false",
+    PanicCodeEnum.UNDERFLOW_OVERFLOW: "Integer underflow or overflow",
+    PanicCodeEnum.DIVISION_MODULO_BY_ZERO: "Division or modulo by zero",
+    PanicCodeEnum.INVALID_CONVERSION_TO_ENUM: "Too big or negative integer for conversion to enum",
+    PanicCodeEnum.ACCESS_TO_INCORRECTLY_ENCODED_STORAGE_BYTE_ARRAY: "Access to incorrectly encoded storage byte array",
+    PanicCodeEnum.POP_EMPTY_ARRAY: ".pop() on empty array",
+    PanicCodeEnum.INDEX_ACCESS_OUT_OF_BOUNDS: "Out-of-bounds or negative index access to fixed-length array",
+    PanicCodeEnum.TOO_MUCH_MEMORY_ALLOCATED: "Too much memory allocated",
+    PanicCodeEnum.INVALID_INTERNAL_FUNCTION_CALL: "Called invalid internal function",
+}
+
 
 @dataclass
 class Panic(TransactionRevertedError):
@@ -778,6 +2148,144 @@ class Panic(TransactionRevertedError):
     }
     selector = bytes.fromhex("4e487b71")
     code: "PanicCodeEnum"
+    location: Optional[str] = field(init=False, compare=False, default=None)
+    """
+    Contract and function name (`Contract.function`) of the call that panicked, resolved from the trace
+    of the transaction. `None` if the error was not raised by a transaction or the call cannot be determined.
+    """
+
+    def __str__(self):
+        try:
+            code = PanicCodeEnum(self.code)
+            s = f"Panic(code={code.name} ({int(code):#x}): {code.description})"
+        except ValueError:
+            s = f"Panic(code={int(self.code):#x})"
+
+        if self.location is not None:
+            s += f" in {self.location}"
+        return s
+
+
+class HaltReasonEnum(StrEnum):
+    OUT_OF_GAS = "OUT_OF_GAS"
+    "Execution ran out of gas"
+    INVALID_OPCODE = "INVALID_OPCODE"
+    "Invalid or not yet activated opcode (including the designated INVALID opcode)"
+    INVALID_JUMP = "INVALID_JUMP"
+    "Jump to an invalid destination"
+    STACK_UNDERFLOW = "STACK_UNDERFLOW"
+    "Stack underflow"
+    STACK_OVERFLOW = "STACK_OVERFLOW"
+    "Stack overflow"
+    STATE_CHANGE_DURING_STATIC_CALL = "STATE_CHANGE_DURING_STATIC_CALL"
+    "State modification inside a static call"
+    INVALID_RETURN_DATA_ACCESS = "INVALID_RETURN_DATA_ACCESS"
+    "Return data accessed out of bounds"
+    CALL_TOO_DEEP = "CALL_TOO_DEEP"
+    "Call depth limit exceeded"
+    OUT_OF_FUNDS = "OUT_OF_FUNDS"
+    "Insufficient balance for value transfer"
+    CREATE_COLLISION = "CREATE_COLLISION"
+    "Contract already deployed at the created address"
+    CREATE_CONTRACT_SIZE_LIMIT = "CREATE_CONTRACT_SIZE_LIMIT"
+    "Created contract code exceeds the size limit"
+    CREATE_INITCODE_SIZE_LIMIT = "CREATE_INITCODE_SIZE_LIMIT"
+    "Init code exceeds the size limit"
+    CREATE_CONTRACT_STARTING_WITH_EF = "CREATE_CONTRACT_STARTING_WITH_EF"
+    "Created contract code starts with the 0xEF byte"
+    NONCE_OVERFLOW = "NONCE_OVERFLOW"
+    "Nonce of the creating account overflowed"
+    UNKNOWN = "UNKNOWN"
+    "Halt reason not recognized"
+
+
+# error identifiers (lowercase alphanumeric) reported by revm (Anvil), Geth and parity-style traces
+_halt_reasons: Dict[str, Optional[HaltReasonEnum]] = {
+    "revert": None,
+    "reverted": None,
+    "executionreverted": None,
+    "outofgas": HaltReasonEnum.OUT_OF_GAS,
+    "memoryoog": HaltReasonEnum.OUT_OF_GAS,
+    "memorylimitoog": HaltReasonEnum.OUT_OF_GAS,
+    "precompileoog": HaltReasonEnum.OUT_OF_GAS,
+    "invalidoperandoog": HaltReasonEnum.OUT_OF_GAS,
+    "gasuint64overflow": HaltReasonEnum.OUT_OF_GAS,
+    "opcodenotfound": HaltReasonEnum.INVALID_OPCODE,
+    "invalidfeopcode": HaltReasonEnum.INVALID_OPCODE,
+    "notactivated": HaltReasonEnum.INVALID_OPCODE,
+    "invalidopcode": HaltReasonEnum.INVALID_OPCODE,
+    "badinstruction": HaltReasonEnum.INVALID_OPCODE,
+    "invalidjump": HaltReasonEnum.INVALID_JUMP,
+    "invalidjumpdestination": HaltReasonEnum.INVALID_JUMP,
+    "badjumpdestination": HaltReasonEnum.INVALID_JUMP,
+    "stackunderflow": HaltReasonEnum.STACK_UNDERFLOW,
+    "stackoverflow": HaltReasonEnum.STACK_OVERFLOW,
+    "stacklimitreached": HaltReasonEnum.STACK_OVERFLOW,
+    "outofstack": HaltReasonEnum.STACK_OVERFLOW,
+    "statechangeduringstaticcall": HaltReasonEnum.STATE_CHANGE_DURING_STATIC_CALL,
+    "callnotallowedinsidestatic": HaltReasonEnum.STATE_CHANGE_DURING_STATIC_CALL,
+    "writeprotection": HaltReasonEnum.STATE_CHANGE_DURING_STATIC_CALL,
+    "mutablecallinstaticcontext": HaltReasonEnum.STATE_CHANGE_DURING_STATIC_CALL,
+    "outofoffset": HaltReasonEnum.INVALID_RETURN_DATA_ACCESS,
+    "returndataoutofbounds": HaltReasonEnum.INVALID_RETURN_DATA_ACCESS,
+    "calltoodeep": HaltReasonEnum.CALL_TOO_DEEP,
+    "maxcalldepthexceeded": HaltReasonEnum.CALL_TOO_DEEP,
+    "outoffunds": HaltReasonEnum.OUT_OF_FUNDS,
+    "insufficientbalancefortransfer": HaltReasonEnum.OUT_OF_FUNDS,
+    "createcollision": HaltReasonEnum.CREATE_COLLISION,
+    "contractaddresscollision": HaltReasonEnum.CREATE_COLLISION,
+    "createinitcodesizelimit": HaltReasonEnum.CREATE_INITCODE_SIZE_LIMIT,
+    "maxinitcodesizeexceeded": HaltReasonEnum.CREATE_INITCODE_SIZE_LIMIT,
+    "createcontractsizelimit": HaltReasonEnum.CREATE_CONTRACT_SIZE_LIMIT,
+    "maxcodesizeexceeded": HaltReasonEnum.CREATE_CONTRACT_SIZE_LIMIT,
+    "createcontractstartingwithef": HaltReasonEnum.CREATE_CONTRACT_STARTING_WITH_EF,
+    "invalidcode": HaltReasonEnum.CREATE_CONTRACT_STARTING_WITH_EF,
+    "nonceoverflow": HaltReasonEnum.NONCE_OVERFLOW,
+    "nonceuint64overflow": HaltReasonEnum.NONCE_OVERFLOW,
+}
+
+_HALT_MESSAGE_PREFIX_RE = re.compile(
+    r"^\s*(?:evm\s*error|execution\s+halted)\s*:?\s*", re.IGNORECASE
+)
+
+
+def parse_halt_reason(message: str) -> Optional[HaltReasonEnum]:
+    """
+    Map an error message reported by the client for a failed execution to `HaltReasonEnum`.
+    Only the error identifier is matched, e.g. `OutOfGas` in `EvmError: OutOfGas(Basic)` or `stack underflow` in `stack underflow (0 <=> 2)`.
+    Returns `None` if the execution reverted (`REVERT` opcode) instead of halting.
+    """
+    match = _HALT_MESSAGE_PREFIX_RE.match(message)
+    if match is not None:
+        message = message[match.end() :]
+    # strip details following the identifier, e.g. "(Basic)", ": opcode 0xfe not defined" or " 1024 (1023)"
+    identifier = re.split(r"[(:{]", message, maxsplit=1)[0]
+    identifier = re.sub(r"[\d\s]+$", "", identifier)
+    identifier = "".join(c for c in identifier.lower() if c.isalnum())
+    return _halt_reasons.get(identifier, HaltReasonEnum.UNKNOWN)
+
+
+@dataclass
+class Halt(UnknownTransactionRevertedError):
+    """
+    Execution halted without reaching a `REVERT` opcode (e.g. ran out of gas or executed an invalid opcode).
+    `data` is always empty, `message` holds the error message reported by the client, e.g. `EvmError: OutOfGas`.
+    """
+
+    data: bytes = b""
+    reason: HaltReasonEnum = HaltReasonEnum.UNKNOWN
+    message: str = field(default="", compare=False)
+
+    def __eq__(self, other):
+        # halts were raised as UnknownTransactionRevertedError(b"") before their reasons were decoded
+        if type(other) is UnknownTransactionRevertedError:
+            return self.data == other.data
+        if isinstance(other, Halt):
+            return self.data == other.data and self.reason == other.reason
+        return NotImplemented
+
+    def __str__(self):
+        return f"Halt(reason={self.reason.name})"
 
 
 class ExceptionWrapper:
@@ -794,7 +2302,9 @@ def must_revert(
         Tuple[Union[str, int, Exception, Type[Exception]], ...],
     ] = TransactionRevertedError,
 ) -> Iterator[ExceptionWrapper]:
-    if isinstance(exceptions, str):
+    if isinstance(exceptions, HaltReasonEnum):
+        exceptions = Halt(reason=exceptions)
+    elif isinstance(exceptions, str):
         exceptions = Error(exceptions)
     elif isinstance(exceptions, int):
         exceptions = Panic(PanicCodeEnum(exceptions))
@@ -802,7 +2312,9 @@ def must_revert(
     if isinstance(exceptions, (tuple, list)):
         tmp: List[Union[str, int, Exception, Type[Exception]]] = []
         for ex in exceptions:
-            if isinstance(ex, str):
+            if isinstance(ex, HaltReasonEnum):
+                tmp.append(Halt(reason=ex))
+            elif isinstance(ex, str):
                 tmp.append(Error(ex))
             elif isinstance(ex, int):
                 tmp.append(Panic(PanicCodeEnum(ex)))
@@ -844,7 +2356,9 @@ def may_revert(
         Tuple[Union[str, int, Exception, Type[Exception]], ...],
     ] = TransactionRevertedError,
 ) -> Iterator[ExceptionWrapper]:
-    if isinstance(exceptions, str):
+    if isinstance(exceptions, HaltReasonEnum):
+        exceptions = Halt(reason=exceptions)
+    elif isinstance(exceptions, str):
         exceptions = Error(exceptions)
     elif isinstance(exceptions, int):
         exceptions = Panic(PanicCodeEnum(exceptions))
@@ -852,7 +2366,9 @@ def may_revert(
     if isinstance(exceptions, (tuple, list)):
         tmp: List[Union[str, int, Exception, Type[Exception]]] = []
         for ex in exceptions:
-            if isinstance(ex, str):
+            if isinstance(ex, HaltReasonEnum):
+                tmp.append(Halt(reason=ex))
+            elif isinstance(ex, str):
                 tmp.append(Error(ex))
             elif isinstance(ex, int):
                 tmp.append(Panic(PanicCodeEnum(ex)))
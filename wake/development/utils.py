@@ -1,22 +1,29 @@
 from __future__ import annotations
 
 import asyncio
+import dataclasses
 import functools
 import importlib
 import inspect
 import json
+import keyword
 import math
+import threading
+import time
 import warnings
 from dataclasses import dataclass
+from enum import IntEnum
 from functools import lru_cache
 from json import JSONDecodeError
 from pathlib import PurePosixPath
 from typing import (
+    TYPE_CHECKING,
     Any,
     Callable,
     Dict,
     Iterable,
     List,
+    Literal,
     Optional,
     Sequence,
     Tuple,
@@ -31,6 +38,7 @@ from urllib.request import Request, urlopen
 from Crypto.Hash import keccak
 from eth_utils.abi import function_abi_to_4byte_selector
 from pydantic import TypeAdapter, ValidationError
+from typing_extensions import get_args, get_origin
 
 from ..compiler import SolcOutputSelectionEnum, SolidityCompiler
 from ..compiler.solc_frontend import (
@@ -48,14 +56,33 @@ from .core import (
     Abi,
     Account,
     Address,
+    Chain,
     Contract,
+    Eip712Domain,
+    _as_buffer,
+    _get_type_hints,
+    _prepare_eip712_dict,
     abi,
+    detect_default_chain,
     get_contracts_by_fqn,
     get_fqn_from_address,
     get_user_defined_value_types_index,
 )
 from .globals import get_config
-from .primitive_types import FixedSizeList, bytes32, fixed_list_map, uint256
+from .primitive_types import (
+    FixedSizeBytes,
+    FixedSizeList,
+    Integer,
+    bytes32,
+    fixed_bytes_map,
+    fixed_list_map,
+    int_map,
+    uint256,
+    uint_map,
+)
+
+if TYPE_CHECKING:
+    from .transactions import TransactionAbc
 
 # pyright: reportGeneralTypeIssues=false, reportOptionalIterable=false, reportOptionalSubscript=false, reportOptionalMemberAccess=false
 
@@ -143,23 +170,31 @@ chain_explorer_urls: Dict[int, ChainExplorer] = {
 }
 
 
-@lru_cache(maxsize=1024)
-def get_contract_info_from_explorer(
-    addr: Address, chain_id: int
-) -> Optional[Tuple[str, Dict]]:
-    if chain_id not in chain_explorer_urls:
-        return None
+_explorer_lock = threading.Lock()
+_explorer_last_request: float = 0.0
+
+
+def _explorer_get_source_code(addr: str, chain_id: int, api_key: str) -> Dict:
+    """
+    Fetch the `getsourcecode` explorer API response for the given address.
+    Requests are rate limited and successful responses are cached on disk.
+    """
+    global _explorer_last_request
 
     config = get_config()
-    api_key = config.api_keys.get(chain_explorer_urls[chain_id].config_key, None)
-    if api_key is None:
-        return None
+    cache_path = (
+        config.global_cache_path / "explorer" / str(chain_id) / f"{addr.lower()}.json"
+    )
+    if config.general.explorer_cache and cache_path.is_file():
+        try:
+            return json.loads(cache_path.read_text())
+        except JSONDecodeError:
+            pass
 
     url = (
         chain_explorer_urls[chain_id].api_url
         + f"?module=contract&action=getsourcecode&address={addr}&apikey={api_key}"
     )
-
     req = Request(
         url,
         headers={
@@ -168,9 +203,39 @@ def get_contract_info_from_explorer(
         },
     )
 
+    with _explorer_lock:
+        rate = config.general.explorer_requests_per_second
+        if rate > 0:
+            delay = _explorer_last_request + 1 / rate - time.monotonic()
+            if delay > 0:
+                time.sleep(delay)
+        try:
+            with urlopen(req) as response:
+                ret = json.loads(response.read().decode("utf-8"))
+        finally:
+            _explorer_last_request = time.monotonic()
+
+    if config.general.explorer_cache and ret["status"] == "1":
+        cache_path.parent.mkdir(parents=True, exist_ok=True)
+        cache_path.write_text(json.dumps(ret))
+
+    return ret
+
+
+@lru_cache(maxsize=1024)
+def get_contract_info_from_explorer(
+    addr: Address, chain_id: int
+) -> Optional[Tuple[str, Dict]]:
+    if chain_id not in chain_explorer_urls:
+        return None
+
+    config = get_config()
+    api_key = config.api_keys.get(chain_explorer_urls[chain_id].config_key, None)
+    if api_key is None:
+        return None
+
     try:
-        with urlopen(req) as response:
-            ret = json.loads(response.read().decode("utf-8"))
+        ret = _explorer_get_source_code(str(addr), chain_id, api_key)
     except URLError as e:
         return None
 
@@ -228,8 +293,8 @@ def partition(
     return p, not_p
 
 
-def keccak256(b: bytes) -> bytes32:
-    h = keccak.new(data=b, digest_bits=256)
+def keccak256(b: Union[bytes, bytearray, memoryview]) -> bytes32:
+    h = keccak.new(data=_as_buffer(b), digest_bits=256)
     return bytes32(h.digest())
 
 
@@ -294,7 +359,218 @@ def get_create2_address_from_code(
     return get_create2_address_from_hash(deployer, salt, keccak256(creation_code))
 
 
+# EIP-170 code size limit minus the leading STOP opcode
+MAX_BLOB_CHUNK_SIZE = 0x6000 - 1
+
+
+def _blob_creation_code(data: bytes) -> bytes:
+    runtime = b"\x00" + data
+    # PUSH2 size, DUP1, PUSH1 0x0a, RETURNDATASIZE, CODECOPY, RETURNDATASIZE, RETURN
+    return (
+        b"\x61"
+        + len(runtime).to_bytes(2, "big")
+        + bytes.fromhex("80600a3d393df3")
+        + runtime
+    )
+
+
+def write_blob(
+    data: Union[bytes, bytearray, memoryview],
+    *,
+    from_: Optional[Union[Account, Address, str]] = None,
+    chain: Optional[Chain] = None,
+) -> Account:
+    """
+    Store `data` on chain using the SSTORE2 pattern, i.e. as the code of a newly deployed data contract
+    prefixed with the STOP opcode. Returns the data contract that can be passed to `read_blob`.
+    """
+    data = bytes(_as_buffer(data))
+    if len(data) > MAX_BLOB_CHUNK_SIZE:
+        raise ValueError(
+            f"Blob of {len(data)} bytes exceeds the maximum size of {MAX_BLOB_CHUNK_SIZE} bytes, use write_blob_chunks"
+        )
+    if chain is None:
+        chain = detect_default_chain()
+
+    contract = chain.deploy(_blob_creation_code(data), from_=from_)
+    return Account(contract.address, chain)
+
+
+def write_blob_chunks(
+    data: Union[bytes, bytearray, memoryview],
+    *,
+    chunk_size: int = MAX_BLOB_CHUNK_SIZE,
+    from_: Optional[Union[Account, Address, str]] = None,
+    chain: Optional[Chain] = None,
+) -> List[Account]:
+    """
+    Store `data` of arbitrary size on chain as multiple SSTORE2 data contracts of at most `chunk_size` bytes each.
+    The returned list of data contracts can be passed to `read_blob` to read the whole blob back.
+    """
+    if not 0 < chunk_size <= MAX_BLOB_CHUNK_SIZE:
+        raise ValueError(f"chunk_size must be between 1 and {MAX_BLOB_CHUNK_SIZE}")
+
+    data = bytes(_as_buffer(data))
+    return [
+        write_blob(data[i : i + chunk_size], from_=from_, chain=chain)
+        for i in range(0, max(len(data), 1), chunk_size)
+    ]
+
+
+def read_blob(pointer: Union[Account, Sequence[Account]]) -> bytes:
+    """
+    Read data stored by `write_blob` or `write_blob_chunks` from one or more SSTORE2 data contracts.
+    """
+    if isinstance(pointer, Account):
+        pointer = [pointer]
+
+    ret = bytearray()
+    for account in pointer:
+        code = account.code
+        if len(code) == 0 or code[0] != 0:
+            raise ValueError(f"{account} is not an SSTORE2 data contract")
+        ret += code[1:]
+    return bytes(ret)
+
+
+def eip712_to_json(message: Any, domain: Eip712Domain) -> str:
+    """
+    Convert an EIP-712 message (any ABI-compatible dataclass) and its domain into the typed data JSON structure
+    accepted by `eth_signTypedData_v4`.
+    """
+    return json.dumps(_prepare_eip712_dict(message, domain, True))
+
+
+def _eip712_type_from_str(
+    type_str: str, struct_types: Dict[str, Type], types: Dict[str, Any]
+) -> Type:
+    if type_str.endswith("]"):
+        base = type_str[: type_str.rfind("[")]
+        length = type_str[type_str.rfind("[") + 1 : -1]
+        inner = _eip712_type_from_str(base, struct_types, types)
+        if len(length) > 0 and int(length) in fixed_list_map:
+            return fixed_list_map[int(length)][inner]
+        return List[inner]
+    elif type_str in struct_types:
+        return struct_types[type_str]
+    elif type_str in types:
+        return _eip712_dataclass(type_str, struct_types, types)
+    elif type_str.startswith("uint"):
+        return uint_map[int(type_str[4:] or 256)]
+    elif type_str.startswith("int"):
+        return int_map[int(type_str[3:] or 256)]
+    elif type_str.startswith("bytes") and len(type_str) > 5:
+        return fixed_bytes_map[int(type_str[5:])]
+    elif type_str == "bytes":
+        return bytes
+    elif type_str == "string":
+        return str
+    elif type_str == "bool":
+        return bool
+    elif type_str == "address":
+        return Address
+    else:
+        raise ValueError(f"Unknown EIP-712 type {type_str}")
+
+
+def _eip712_dataclass(
+    name: str, struct_types: Dict[str, Type], types: Dict[str, Any]
+) -> Type:
+    if name in struct_types:
+        return struct_types[name]
+
+    fields = []
+    for member in types[name]:
+        field_name = member["name"]
+        if not field_name.isidentifier() or keyword.iskeyword(field_name):
+            field_name += "_"
+        fields.append(
+            (
+                field_name,
+                _eip712_type_from_str(member["type"], struct_types, types),
+                dataclasses.field(metadata={"original_name": member["name"]}),
+            )
+        )
+
+    struct_types[name] = dataclasses.make_dataclass(name, fields)
+    return struct_types[name]
+
+
+def _eip712_value(t: Type, value: Any) -> Any:
+    origin = get_origin(t)
+
+    if isinstance(origin, type) and issubclass(origin, list):
+        return origin(_eip712_value(get_args(t)[0], v) for v in value)
+    elif dataclasses.is_dataclass(t):
+        hints = _get_type_hints(t, include_extras=True)
+        return t(
+            **{
+                f.name: _eip712_value(
+                    hints[f.name], value[f.metadata.get("original_name", f.name)]
+                )
+                for f in dataclasses.fields(t)
+            }
+        )
+    elif isinstance(t, type) and issubclass(t, (Account, Address)):
+        return t(value) if t is Address else Account(value)
+    elif isinstance(t, type) and issubclass(t, (bytes, bytearray)):
+        if isinstance(value, str):
+            value = bytes.fromhex(value[2:] if value.startswith("0x") else value)
+        return t(value)
+    elif isinstance(t, type) and issubclass(t, bool):
+        return bool(value)
+    elif isinstance(t, type) and issubclass(t, int):
+        if isinstance(value, str):
+            value = int(value, 0)
+        return t(value)
+    else:
+        return value
+
+
+def eip712_from_json(
+    typed_data: Union[str, Dict[str, Any]], message_type: Optional[Type] = None
+) -> Tuple[Any, Eip712Domain]:
+    """
+    Convert the typed data JSON structure accepted by `eth_signTypedData_v4` into a message dataclass and its domain.
+
+    Args:
+        typed_data: JSON string or already parsed dictionary.
+        message_type: Dataclass of the primary type (e.g. from `pytypes`); dataclasses are generated from the `types` section if not given.
+
+    Returns:
+        Tuple of the message and the domain.
+    """
+    if isinstance(typed_data, str):
+        typed_data = json.loads(typed_data)
+    assert isinstance(typed_data, dict)
+
+    types = {k: v for k, v in typed_data["types"].items() if k != "EIP712Domain"}
+    if message_type is None:
+        message_type = _eip712_dataclass(typed_data["primaryType"], {}, types)
+    message = _eip712_value(message_type, typed_data["message"])
+
+    domain_data = typed_data.get("domain", {})
+    domain: Eip712Domain = {}
+    if "name" in domain_data:
+        domain["name"] = domain_data["name"]
+    if "version" in domain_data:
+        domain["version"] = domain_data["version"]
+    if "chainId" in domain_data:
+        domain["chainId"] = _eip712_value(int, domain_data["chainId"])
+    if "verifyingContract" in domain_data:
+        domain["verifyingContract"] = Address(domain_data["verifyingContract"])
+    if "salt" in domain_data:
+        domain["salt"] = _eip712_value(bytes32, domain_data["salt"])
+
+    return message, domain
+
+
 def get_logic_contract(contract: Account) -> Account:
+    impl = get_proxy_implementation(contract)
+    return impl if impl is not None else contract
+
+
+def get_proxy_implementation(contract: Account) -> Optional[Account]:
     # keccak256("eip1967.proxy.implementation") - 1
     impl_addr = Abi.decode(
         ["address"],
@@ -332,7 +608,7 @@ def get_logic_contract(contract: Account) -> Account:
         )
         return Account(Abi.decode(["address"], impl_addr_raw)[0], chain=contract.chain)
 
-    return contract
+    return None
 
 
 def read_storage_variable(
@@ -803,6 +1079,170 @@ def write_storage_variable(
     )
 
 
+@dataclass(frozen=True)
+class StorageField:
+    """
+    Location of a value in storage computed by `compute_storage_layout`.
+    """
+
+    name: str
+    type: Type
+    slot: int
+    offset: int
+    """Offset in bytes from the least significant (rightmost) byte of the slot."""
+    size: int
+    """Size in bytes; a multiple of 32 for structs, static arrays and dynamic types."""
+
+
+def _storage_type_size(t: Type) -> Tuple[int, bool]:
+    # returns (size in bytes, whether the type is packed with neighbouring values)
+    origin = get_origin(t)
+    if origin is not None:
+        if isinstance(origin, type) and issubclass(origin, FixedSizeList):
+            item_size, packed = _storage_type_size(get_args(t)[0])
+            if packed:
+                items_per_slot = 32 // item_size
+                slots = math.ceil(origin.length / items_per_slot)
+            else:
+                slots = origin.length * (item_size // 32)
+            return slots * 32, False
+        # dynamic arrays and mappings
+        return 32, False
+    elif dataclasses.is_dataclass(t):
+        fields = compute_storage_layout(t)
+        if len(fields) == 0:
+            return 32, False
+        last = fields[-1]
+        return (last.slot + math.ceil((last.offset + last.size) / 32)) * 32, False
+    elif issubclass(t, bool):
+        return 1, True
+    elif issubclass(t, (Account, Address)):
+        return 20, True
+    elif issubclass(t, IntEnum):
+        return 1, True
+    elif issubclass(t, Integer):
+        bits = t.__name__[4:] if t.__name__.startswith("u") else t.__name__[3:]
+        return int(bits) // 8, True
+    elif issubclass(t, FixedSizeBytes):
+        return t.length, True
+    elif issubclass(t, int):
+        return 32, True
+    elif issubclass(t, (bytes, bytearray, str, list, dict)):
+        return 32, False
+    raise TypeError(f"Cannot compute storage size of {t}")
+
+
+def compute_storage_layout(
+    types: Union[Type, Sequence[Type]], *, slot: int = 0
+) -> List[StorageField]:
+    """
+    Compute the storage layout of a struct (a dataclass, e.g. from pytypes) or a list of types following the Solidity packing rules.
+    Value types are packed into a slot while they fit, structs and static arrays always start a new slot and are followed by a new slot.
+
+    Args:
+        types: Dataclass or a sequence of types laid out in order; fields of a sequence are named by their index.
+        slot: Slot the layout starts at, e.g. the slot of a struct variable or a mapping entry.
+
+    Returns:
+        Storage fields in declaration order.
+    """
+    if isinstance(types, type) and dataclasses.is_dataclass(types):
+        hints = _get_type_hints(types)
+        members = [(f.name, hints[f.name]) for f in dataclasses.fields(types)]
+    elif isinstance(types, Sequence):
+        members = [(str(i), t) for i, t in enumerate(types)]
+    else:
+        raise TypeError(f"Expected a dataclass or a sequence of types, got {types}")
+
+    ret = []
+    offset = 0
+    for name, t in members:
+        size, packed = _storage_type_size(t)
+        if not packed or offset + size > 32:
+            if offset > 0:
+                slot += 1
+            offset = 0
+
+        ret.append(StorageField(name, t, slot, offset, size))
+
+        if packed:
+            offset += size
+        else:
+            slot += size // 32
+            offset = 0
+    return ret
+
+
+def read_storage_field(account: Account, field: StorageField) -> Any:
+    """
+    Read a value type (integer, `bool`, `Address`, enum, or fixed-size bytes) stored at a location computed by `compute_storage_layout`.
+    """
+    if not _storage_type_size(field.type)[1]:
+        raise ValueError(
+            f"Field {field.name} of type {field.type} is not a value type, use read_storage_variable instead"
+        )
+
+    slot_data = account.chain.chain_interface.get_storage_at(
+        str(account.address), field.slot
+    )
+    data = slot_data[
+        -field.offset - field.size : (-field.offset if field.offset != 0 else None)
+    ]
+    t = field.type
+
+    if issubclass(t, bool):
+        return data[-1] != 0
+    elif issubclass(t, Account):
+        return t(Address("0x" + data.hex()), chain=account.chain)
+    elif issubclass(t, Address):
+        return Address("0x" + data.hex())
+    elif issubclass(t, FixedSizeBytes):
+        return t(data)
+    elif issubclass(t, Integer) and t.__name__.startswith("int"):
+        return t(int.from_bytes(data, "big", signed=True))
+    return t(int.from_bytes(data, "big"))
+
+
+def write_storage_field(account: Account, field: StorageField, value: Any) -> None:
+    """
+    Write a value type (integer, `bool`, `Address`, enum, or fixed-size bytes) to a location computed by `compute_storage_layout`,
+    keeping other values packed in the same slot.
+    """
+    if not _storage_type_size(field.type)[1]:
+        raise ValueError(
+            f"Field {field.name} of type {field.type} is not a value type, use write_storage_variable instead"
+        )
+
+    t = field.type
+    if issubclass(t, bool):
+        encoded = bytes([1 if value else 0])
+    elif issubclass(t, (Account, Address)):
+        if isinstance(value, Account):
+            value = value.address
+        encoded = bytes.fromhex(str(Address(value))[2:])
+    elif issubclass(t, FixedSizeBytes):
+        encoded = bytes(value).ljust(field.size, b"\x00")
+    else:
+        encoded = int(value).to_bytes(
+            field.size,
+            "big",
+            signed=issubclass(t, Integer) and t.__name__.startswith("int"),
+        )
+
+    if len(encoded) != field.size:
+        raise ValueError(f"Value {value} does not fit into field {field.name}")
+
+    original_data = bytearray(
+        account.chain.chain_interface.get_storage_at(str(account.address), field.slot)
+    )
+    original_data[
+        -field.offset - field.size : (-field.offset if field.offset != 0 else None)
+    ] = encoded
+    account.chain.chain_interface.set_storage_at(
+        str(account.address), field.slot, original_data
+    )
+
+
 def mint_erc20(
     contract: Account,
     to: Union[Account, Address],
@@ -1118,6 +1558,656 @@ def _update_erc20_balance(
         warnings.warn(f"Could not update total supply of {contract.address}")
 
 
+@dataclass(frozen=True)
+class ConformanceFinding:
+    standard: str
+    check: str
+    message: str
+    severity: Literal["error", "warning"] = "error"
+
+    def __str__(self) -> str:
+        return f"[{self.standard}] {self.check} ({self.severity}): {self.message}"
+
+
+def _conformance_call(contract: Account, signature: str, *args, types: List[Type]):
+    call_acc = contract.chain.default_call_account
+    if call_acc is None and len(contract.chain.accounts) > 0:
+        call_acc = contract.chain.accounts[0]
+
+    return abi.decode(
+        contract.call(
+            data=abi.encode_with_signature(signature, *args),
+            from_=call_acc,
+        ),
+        types,
+    )
+
+
+def _conformance_transact(
+    contract: Account, signature: str, *args, from_: Account
+) -> Tuple[Optional[TransactionAbc[bytearray]], Optional[bool]]:
+    """
+    Returns the transaction (or `None` if it reverted) and the decoded `bool`
+    return value (or `None` if the function does not return any data).
+    """
+    if from_.balance == 0:
+        from_.balance = 10**18
+
+    try:
+        tx = contract.transact(
+            data=abi.encode_with_signature(signature, *args), from_=from_
+        )
+        if tx.error is not None:
+            return None, None
+        ret = tx.return_value
+    except Exception:
+        return None, None
+
+    if len(ret) == 0:
+        return tx, None
+    try:
+        return tx, abi.decode(ret, [bool])
+    except Exception:
+        return tx, False
+
+
+def _has_event(
+    tx: TransactionAbc, topic: bytes, *indexed: Union[Account, Address, int]
+) -> bool:
+    expected = []
+    for i in indexed:
+        if isinstance(i, Account):
+            i = i.address
+        if isinstance(i, Address):
+            expected.append(bytes(12) + bytes(i))
+        else:
+            expected.append(i.to_bytes(32, "big"))
+    return any(
+        len(e.topics) == len(expected) + 1
+        and e.topics[0] == topic
+        and list(e.topics[1:]) == expected
+        for e in tx.raw_events
+    )
+
+
+def _check_supports_interface(
+    contract: Account, interface_id: bytes
+) -> Optional[bool]:
+    try:
+        return _conformance_call(
+            contract, "supportsInterface(bytes4)", interface_id, types=[bool]
+        )
+    except Exception:
+        return None
+
+
+def check_erc165(contract: Account) -> List[ConformanceFinding]:
+    """
+    Check that `contract` implements [ERC-165](https://eips.ethereum.org/EIPS/eip-165), i.e. `supportsInterface`
+    returns `True` for the ERC-165 interface ID `0x01ffc9a7` and `False` for the invalid interface ID `0xffffffff`.
+
+    Args:
+        contract: Deployed contract to check.
+
+    Returns:
+        Deviations from the standard, empty if none were found.
+    """
+    findings: List[ConformanceFinding] = []
+
+    supported = _check_supports_interface(contract, bytes.fromhex("01ffc9a7"))
+    if supported is None:
+        findings.append(
+            ConformanceFinding(
+                "ERC-165",
+                "supportsInterface",
+                "supportsInterface(0x01ffc9a7) reverted or returned malformed data",
+            )
+        )
+        return findings
+    if not supported:
+        findings.append(
+            ConformanceFinding(
+                "ERC-165",
+                "supportsInterface",
+                "supportsInterface(0x01ffc9a7) must return true",
+            )
+        )
+
+    supported = _check_supports_interface(contract, bytes.fromhex("ffffffff"))
+    if supported is None:
+        findings.append(
+            ConformanceFinding(
+                "ERC-165",
+                "supportsInterface",
+                "supportsInterface(0xffffffff) reverted or returned malformed data",
+            )
+        )
+    elif supported:
+        findings.append(
+            ConformanceFinding(
+                "ERC-165",
+                "supportsInterface",
+                "supportsInterface(0xffffffff) must return false",
+            )
+        )
+
+    return findings
+
+
+def _check_erc20_semantics(
+    contract: Account,
+    holder: Account,
+    amount: Optional[int],
+    balance: int,
+    total_supply: int,
+    finding: Callable[..., None],
+) -> None:
+    from .transactions import _ERC20_APPROVAL_TOPIC, _ERC20_TRANSFER_TOPIC
+
+    recipient = Account.new(contract.chain)
+    spender = Account.new(contract.chain)
+
+    if balance == 0:
+        try:
+            mint_erc20(contract, holder, amount if amount is not None else 1000)
+            balance = _conformance_call(
+                contract, "balanceOf(address)", holder, types=[uint256]
+            )
+            total_supply = _conformance_call(
+                contract, "totalSupply()", types=[uint256]
+            )
+        except Exception:
+            pass
+    if balance == 0:
+        finding(
+            "transfer",
+            f"{holder} holds no tokens, transfer semantics not checked",
+            "warning",
+        )
+        return
+
+    if amount is None or amount > balance:
+        amount = max(balance // 2, 1)
+
+    tx, ret = _conformance_transact(
+        contract, "transfer(address,uint256)", recipient, amount, from_=holder
+    )
+    if tx is None or ret is False:
+        finding("transfer", f"transfer of {amount} tokens by {holder} failed")
+        return
+    if ret is None:
+        finding("transfer", "transfer does not return bool", "warning")
+    if not _has_event(tx, _ERC20_TRANSFER_TOPIC, holder, recipient):
+        finding("transfer", "transfer did not emit Transfer event")
+
+    if (
+        _conformance_call(contract, "balanceOf(address)", holder, types=[uint256])
+        != balance - amount
+        or _conformance_call(
+            contract, "balanceOf(address)", recipient, types=[uint256]
+        )
+        != amount
+    ):
+        finding("transfer", "transfer did not move the expected amount")
+    if _conformance_call(contract, "totalSupply()", types=[uint256]) != total_supply:
+        finding("transfer", "transfer changed totalSupply")
+
+    balance -= amount
+    tx, ret = _conformance_transact(
+        contract, "transfer(address,uint256)", recipient, balance + 1, from_=holder
+    )
+    if tx is not None and ret is not False:
+        finding("transfer", "transfer of more than the sender balance succeeded")
+
+    tx, ret = _conformance_transact(
+        contract, "transfer(address,uint256)", recipient, 0, from_=holder
+    )
+    if tx is None or ret is False:
+        finding("transfer", "transfer of zero tokens failed", "warning")
+
+    tx, ret = _conformance_transact(
+        contract, "approve(address,uint256)", spender, balance, from_=holder
+    )
+    if tx is None or ret is False:
+        finding("approve", "approve failed")
+        return
+    if ret is None:
+        finding("approve", "approve does not return bool", "warning")
+    if not _has_event(tx, _ERC20_APPROVAL_TOPIC, holder, spender):
+        finding("approve", "approve did not emit Approval event")
+    try:
+        allowance = _conformance_call(
+            contract, "allowance(address,address)", holder, spender, types=[uint256]
+        )
+    except Exception:
+        finding("allowance", "allowance(address,address) reverted")
+        return
+    if allowance != balance:
+        finding("approve", "allowance does not match the approved amount")
+
+    if balance == 0:
+        return
+
+    tx, ret = _conformance_transact(
+        contract,
+        "transferFrom(address,address,uint256)",
+        holder,
+        recipient,
+        balance + 1,
+        from_=spender,
+    )
+    if tx is not None and ret is not False:
+        finding("transferFrom", "transferFrom of more than the allowance succeeded")
+
+    tx, ret = _conformance_transact(
+        contract,
+        "transferFrom(address,address,uint256)",
+        holder,
+        recipient,
+        balance,
+        from_=spender,
+    )
+    if tx is None or ret is False:
+        finding("transferFrom", "transferFrom within the allowance failed")
+        return
+    if ret is None:
+        finding("transferFrom", "transferFrom does not return bool", "warning")
+    if not _has_event(tx, _ERC20_TRANSFER_TOPIC, holder, recipient):
+        finding("transferFrom", "transferFrom did not emit Transfer event")
+    if (
+        _conformance_call(
+            contract, "allowance(address,address)", holder, spender, types=[uint256]
+        )
+        != 0
+    ):
+        finding("transferFrom", "transferFrom did not decrease the allowance")
+
+
+def check_erc20(
+    contract: Account,
+    *,
+    holder: Optional[Union[Account, Address]] = None,
+    amount: Optional[int] = None,
+) -> List[ConformanceFinding]:
+    """
+    Check that `contract` implements [ERC-20](https://eips.ethereum.org/EIPS/eip-20). Metadata and balance getters are called first,
+    then `transfer`, `approve` and `transferFrom` are executed and their effects on balances and allowances,
+    emitted events and reverts on insufficient balance or allowance are verified. State changes are reverted afterwards.
+
+    Args:
+        contract: Deployed contract to check.
+        holder: Account transferring tokens in the checks, `chain.accounts[0]` by default; tokens are minted with `mint_erc20` if it has none.
+        amount: Amount of tokens transferred in the checks, derived from the balance of `holder` by default.
+
+    Returns:
+        Deviations from the standard, empty if none were found.
+    """
+    chain = contract.chain
+    findings: List[ConformanceFinding] = []
+
+    def finding(
+        check: str, message: str, severity: Literal["error", "warning"] = "error"
+    ):
+        findings.append(ConformanceFinding("ERC-20", check, message, severity))
+
+    for check in ("name", "symbol"):
+        try:
+            _conformance_call(contract, f"{check}()", types=[str])
+        except Exception:
+            finding(
+                check, f"{check}() reverted or returned malformed data", "warning"
+            )
+
+    try:
+        decimals = _conformance_call(contract, "decimals()", types=[uint256])
+        if decimals > 255:
+            finding("decimals", f"decimals() returned {decimals} which exceeds uint8")
+    except Exception:
+        finding("decimals", "decimals() reverted or returned malformed data", "warning")
+
+    try:
+        total_supply = _conformance_call(contract, "totalSupply()", types=[uint256])
+    except Exception:
+        finding("totalSupply", "totalSupply() reverted or returned malformed data")
+        return findings
+
+    if holder is None:
+        if len(chain.accounts) == 0:
+            finding("transfer", "No account available to test transfers", "warning")
+            return findings
+        holder = chain.accounts[0]
+    elif isinstance(holder, Address):
+        holder = Account(holder, chain=chain)
+
+    try:
+        balance = _conformance_call(
+            contract, "balanceOf(address)", holder, types=[uint256]
+        )
+    except Exception:
+        finding("balanceOf", "balanceOf(address) reverted or returned malformed data")
+        return findings
+
+    with chain.snapshot_and_revert():
+        try:
+            _check_erc20_semantics(
+                contract, holder, amount, balance, total_supply, finding
+            )
+        except Exception as e:
+            finding("transfer", f"Unexpected failure: {e!r}")
+
+    return findings
+
+
+def _check_erc721_semantics(
+    contract: Account,
+    owner: Account,
+    token_id: int,
+    finding: Callable[..., None],
+) -> None:
+    # ERC-721 events share the signatures of ERC-20 events, only the token ID is indexed
+    from .transactions import _ERC20_APPROVAL_TOPIC, _ERC20_TRANSFER_TOPIC
+
+    recipient = Account.new(contract.chain)
+    operator = Account.new(contract.chain)
+
+    owner_balance = _conformance_call(
+        contract, "balanceOf(address)", owner, types=[uint256]
+    )
+
+    tx, _ = _conformance_transact(
+        contract,
+        "transferFrom(address,address,uint256)",
+        owner,
+        recipient,
+        token_id,
+        from_=operator,
+    )
+    if tx is not None:
+        finding("transferFrom", "transferFrom by an unauthorized account succeeded")
+        return
+
+    tx, _ = _conformance_transact(
+        contract, "approve(address,uint256)", operator, token_id, from_=owner
+    )
+    if tx is None:
+        finding("approve", f"approve of token {token_id} by its owner failed")
+        return
+    if not _has_event(tx, _ERC20_APPROVAL_TOPIC, owner, operator, token_id):
+        finding("approve", "approve did not emit Approval event")
+    if (
+        _conformance_call(contract, "getApproved(uint256)", token_id, types=[Address])
+        != operator.address
+    ):
+        finding("getApproved", "getApproved does not return the approved account")
+
+    tx, _ = _conformance_transact(
+        contract,
+        "transferFrom(address,address,uint256)",
+        owner,
+        recipient,
+        token_id,
+        from_=operator,
+    )
+    if tx is None:
+        finding("transferFrom", "transferFrom by the approved account failed")
+        return
+    if not _has_event(tx, _ERC20_TRANSFER_TOPIC, owner, recipient, token_id):
+        finding("transferFrom", "transferFrom did not emit Transfer event")
+    if (
+        _conformance_call(contract, "ownerOf(uint256)", token_id, types=[Address])
+        != recipient.address
+    ):
+        finding("transferFrom", "transferFrom did not change the owner")
+    if (
+        _conformance_call(contract, "balanceOf(address)", owner, types=[uint256])
+        != owner_balance - 1
+        or _conformance_call(
+            contract, "balanceOf(address)", recipient, types=[uint256]
+        )
+        != 1
+    ):
+        finding("transferFrom", "transferFrom did not update balances")
+    if (
+        _conformance_call(contract, "getApproved(uint256)", token_id, types=[Address])
+        != Address(0)
+    ):
+        finding("transferFrom", "transferFrom did not clear the approval")
+
+
+def check_erc721(
+    contract: Account, *, token_id: Optional[int] = None
+) -> List[ConformanceFinding]:
+    """
+    Check that `contract` implements [ERC-721](https://eips.ethereum.org/EIPS/eip-721), including the ERC-165 checks of `check_erc165`,
+    the ERC-721 interface ID, metadata getters (if the metadata extension is supported) and a reverting `balanceOf(address(0))`.
+    State changes are reverted afterwards.
+
+    Args:
+        contract: Deployed contract to check.
+        token_id: Existing token used to check `approve`, `getApproved` and `transferFrom` semantics; skipped if not given.
+
+    Returns:
+        Deviations from the standard, empty if none were found.
+    """
+    chain = contract.chain
+    findings: List[ConformanceFinding] = check_erc165(contract)
+
+    def finding(
+        check: str, message: str, severity: Literal["error", "warning"] = "error"
+    ):
+        findings.append(ConformanceFinding("ERC-721", check, message, severity))
+
+    if not _check_supports_interface(contract, bytes.fromhex("80ac58cd")):
+        finding("supportsInterface", "supportsInterface(0x80ac58cd) must return true")
+
+    if _check_supports_interface(contract, bytes.fromhex("5b5e139f")):
+        for check in ("name", "symbol"):
+            try:
+                _conformance_call(contract, f"{check}()", types=[str])
+            except Exception:
+                finding(check, f"{check}() reverted or returned malformed data")
+
+    try:
+        _conformance_call(contract, "balanceOf(address)", Address(0), types=[uint256])
+        finding("balanceOf", "balanceOf(address(0)) must revert")
+    except Exception:
+        pass
+
+    if token_id is None:
+        return findings
+
+    try:
+        owner = Account(
+            _conformance_call(contract, "ownerOf(uint256)", token_id, types=[Address]),
+            chain=chain,
+        )
+    except Exception:
+        finding("ownerOf", f"ownerOf({token_id}) reverted or returned malformed data")
+        return findings
+    if owner.address == Address(0):
+        finding("ownerOf", f"ownerOf({token_id}) returned the zero address")
+        return findings
+
+    with chain.snapshot_and_revert():
+        try:
+            _check_erc721_semantics(contract, owner, token_id, finding)
+        except Exception as e:
+            finding("transferFrom", f"Unexpected failure: {e!r}")
+
+    return findings
+
+
+@dataclass(frozen=True)
+class StorageLayoutChange:
+    label: str
+    kind: Literal["removed", "moved", "retyped", "renamed"]
+    message: str
+
+    def __str__(self) -> str:
+        return f"{self.label} ({self.kind}): {self.message}"
+
+
+class StorageLayoutIncompatible(AssertionError):
+    changes: List[StorageLayoutChange]
+
+    def __init__(self, changes: List[StorageLayoutChange]):
+        self.changes = changes
+        super().__init__(
+            "Storage layouts are not compatible:\n"
+            + "\n".join(f"  {change}" for change in changes)
+        )
+
+
+def _storage_types_compatible(
+    old_id: str,
+    old_types: Dict[str, SolcOutputStorageLayoutType],
+    new_id: str,
+    new_types: Dict[str, SolcOutputStorageLayoutType],
+    inline: bool,
+) -> bool:
+    # type identifiers contain AST IDs (e.g. t_struct(S)12_storage), compare the type structure instead
+    # `inline` is false for mapping values, which may grow as nothing is laid out after them
+    old = old_types[old_id]
+    new = new_types[new_id]
+    if old.encoding != new.encoding:
+        return False
+    if inline and old.number_of_bytes != new.number_of_bytes:
+        return False
+
+    if old.members is not None or new.members is not None:
+        if old.members is None or new.members is None:
+            return False
+        # members may only be appended, existing members must keep their position and type
+        if len(new.members) < len(old.members):
+            return False
+        return all(
+            (old_member.slot, old_member.offset) == (new_member.slot, new_member.offset)
+            and _storage_types_compatible(
+                old_member.type, old_types, new_member.type, new_types, True
+            )
+            for old_member, new_member in zip(old.members, new.members)
+        )
+
+    for old_nested, new_nested, nested_inline in (
+        (old.base, new.base, True),
+        (old.key, new.key, True),
+        (old.value, new.value, False),
+    ):
+        if (old_nested is None) != (new_nested is None):
+            return False
+        if (
+            old_nested is not None
+            and new_nested is not None
+            and not _storage_types_compatible(
+                old_nested, old_types, new_nested, new_types, nested_inline
+            )
+        ):
+            return False
+
+    if old.base is None and old.key is None and old.value is None:
+        return old.label == new.label
+    return True
+
+
+def check_storage_compatibility(
+    old: Union[Account, Type[Contract]], new: Union[Account, Type[Contract]]
+) -> List[StorageLayoutChange]:
+    """
+    Compare storage layouts of two contract versions and return the changes making the new version
+    unsafe to upgrade to, i.e. removed, moved and retyped state variables, and renamed variables.
+    Variables are matched by slot, offset and type first, so a variable renamed in place is reported as `renamed`,
+    which is not considered incompatible. Members may be appended to structs stored only as mapping values.
+    Variables named `__gap` may shrink as long as the gap ends at the same slot.
+    """
+    old_layout = _get_storage_layout(old)
+    new_layout = _get_storage_layout(new)
+    old_types = old_layout.types or {}
+    new_types = new_layout.types or {}
+    new_by_label = {s.label: s for s in new_layout.storage}
+    new_by_position = {(s.slot, s.offset): s for s in new_layout.storage}
+    changes: List[StorageLayoutChange] = []
+
+    for old_var in old_layout.storage:
+        old_type = old_types[old_var.type]
+
+        new_var = new_by_position.get((old_var.slot, old_var.offset))
+        if new_var is not None and _storage_types_compatible(
+            old_var.type, old_types, new_var.type, new_types, True
+        ):
+            if new_var.label != old_var.label:
+                changes.append(
+                    StorageLayoutChange(
+                        old_var.label,
+                        "renamed",
+                        f"renamed to {new_var.label} in slot {old_var.slot} offset {old_var.offset}",
+                    )
+                )
+            continue
+
+        new_var = new_by_label.get(old_var.label)
+        if new_var is None:
+            changes.append(
+                StorageLayoutChange(
+                    old_var.label,
+                    "removed",
+                    f"removed from slot {old_var.slot} offset {old_var.offset}",
+                )
+            )
+            continue
+
+        new_type = new_types[new_var.type]
+
+        if old_var.label.startswith("__gap") and (
+            old_type.base is not None
+            and new_type.base is not None
+            and _storage_types_compatible(
+                old_type.base, old_types, new_type.base, new_types, True
+            )
+            and old_var.slot * 32 + old_type.number_of_bytes
+            == new_var.slot * 32 + new_type.number_of_bytes
+        ):
+            continue
+
+        if (old_var.slot, old_var.offset) != (new_var.slot, new_var.offset):
+            changes.append(
+                StorageLayoutChange(
+                    old_var.label,
+                    "moved",
+                    f"moved from slot {old_var.slot} offset {old_var.offset} "
+                    f"to slot {new_var.slot} offset {new_var.offset}",
+                )
+            )
+        if not _storage_types_compatible(
+            old_var.type, old_types, new_var.type, new_types, True
+        ):
+            changes.append(
+                StorageLayoutChange(
+                    old_var.label,
+                    "retyped",
+                    f"type changed from {old_type.label} to {new_type.label}",
+                )
+            )
+
+    return changes
+
+
+def assert_storage_compatible(
+    old: Union[Account, Type[Contract]], new: Union[Account, Type[Contract]]
+) -> None:
+    """
+    Raise `StorageLayoutIncompatible` if `new` is not storage compatible with `old`, see `check_storage_compatibility`.
+    Renamed variables only issue a `UserWarning`.
+    """
+    changes = check_storage_compatibility(old, new)
+    for change in changes:
+        if change.kind == "renamed":
+            warnings.warn(f"Storage variable {change}", UserWarning, stacklevel=2)
+
+    incompatible = [change for change in changes if change.kind != "renamed"]
+    if len(incompatible) > 0:
+        raise StorageLayoutIncompatible(incompatible)
+
+
 def _get_storage_layout(
     contract: Union[Account, Type[Contract]]
 ) -> SolcOutputStorageLayout:
@@ -1131,6 +2221,10 @@ def _get_storage_layout(
     if fqn is None:
         if contract.chain._forked_chain_id is None:
             raise ValueError("Contract not found")
+        if contract.chain.offline:
+            raise ValueError(
+                "Contract not found and chain explorer lookups are disabled in offline mode"
+            )
 
         if contract.chain._forked_chain_id not in chain_explorer_urls:
             raise ValueError(
@@ -1168,20 +2262,7 @@ def _get_storage_layout_from_explorer(
     if api_key is None:
         raise ValueError(f"Contract not found and API key for {u.netloc} not provided")
 
-    url = (
-        chain_explorer_urls[chain_id].api_url
-        + f"?module=contract&action=getsourcecode&address={addr}&apikey={api_key}"
-    )
-    req = Request(
-        url,
-        headers={
-            "Content-Type": "application/json",
-            "User-Agent": f"wake/{get_package_version('eth-wake')}",
-        },
-    )
-
-    with urlopen(req) as response:
-        parsed = json.loads(response.read().decode("utf-8"))
+    parsed = _explorer_get_source_code(addr, chain_id, api_key)
 
     if parsed["status"] != "1":
         raise ValueError(f"Request to {u.netloc} failed: {parsed['result']}")
@@ -1,28 +1,52 @@
 from __future__ import annotations
 
-from contextlib import contextmanager
-from typing import Any, Dict, Iterable, List, Optional, Tuple, Union, cast
+import functools
+import json
+import weakref
+from collections import defaultdict
+from contextlib import ExitStack, contextmanager
+from pathlib import Path
+from random import Random
+from typing import (
+    Any,
+    Callable,
+    Dict,
+    Iterable,
+    Iterator,
+    List,
+    Optional,
+    Tuple,
+    Union,
+    cast,
+)
 
-import eth_utils
 from Crypto.Hash import keccak
+from typing_extensions import Literal
 
 import wake.development.core
 from wake.development.chain_interfaces import TxParams
 from wake.development.core import (
+    _BEACON_ROOTS_CODE,
+    _BEACON_ROOTS_HISTORY_LENGTH,
+    BEACON_ROOTS_ADDRESS,
     Abi,
     Account,
     Address,
+    BlockGasLimitExceeded,
+    NotConnectedError,
     RequestType,
     RevertToSnapshotFailedError,
+    Snapshot,
     Wei,
+    Withdrawal,
     check_connected,
-    fix_library_abi,
 )
-from wake.development.globals import chain_interfaces_manager, random
+from wake.development.globals import chain_interfaces_manager, get_config, random
 from wake.development.json_rpc import JsonRpcError
 
-from ..development.chain_interfaces import AnvilChainInterface
+from ..development.chain_interfaces import AnvilChainInterface, HardhatChainInterface
 from ..development.transactions import TransactionAbc, TransactionStatusEnum
+from .gas_snapshot import gas_snapshots
 
 
 class Chain(wake.development.core.Chain):
@@ -30,6 +54,9 @@ class Chain(wake.development.core.Chain):
     _gas_price: Wei
     _max_priority_fee_per_gas: Wei
     _initial_base_fee_per_gas: Wei
+    _block_gas_limit_policy: Literal["split", "reject", "mine"]
+    _random: Optional[Random] = None
+    _genesis: Optional[Union[str, Path, Dict[str, Any]]] = None
 
     @contextmanager
     def connect(
@@ -42,7 +69,11 @@ class Chain(wake.development.core.Chain):
         hardfork: Optional[str] = None,
         min_gas_price: Optional[Union[int, str]] = 0,
         block_base_fee_per_gas: Optional[Union[int, str]] = 0,
+        offline: bool = False,
+        genesis: Optional[Union[str, Path, Dict[str, Any]]] = None,
+        genesis_timestamp: Optional[int] = None,
     ):
+        self._genesis = genesis
         yield from self._connect(
             uri,
             accounts=accounts,
@@ -51,6 +82,8 @@ class Chain(wake.development.core.Chain):
             hardfork=hardfork,
             min_gas_price=min_gas_price,
             block_base_fee_per_gas=block_base_fee_per_gas,
+            offline=offline,
+            genesis_timestamp=genesis_timestamp,
         )
 
     def _connect_setup(
@@ -59,6 +92,8 @@ class Chain(wake.development.core.Chain):
         connected_chains.append(self)
 
         self._require_signed_txs = False
+        self._block_gas_limit_policy = "split"
+        self._tx_ordering = None
         self._gas_price = Wei(0)
         self._max_priority_fee_per_gas = Wei(0)
         block_info = self._chain_interface.get_block("pending")
@@ -79,46 +114,249 @@ class Chain(wake.development.core.Chain):
         else:
             self.gas_price = self._chain_interface.get_gas_price()
 
+        if (
+            isinstance(self._chain_interface, AnvilChainInterface)
+            and self._chain_interface._process is not None
+            and get_config().testing.deterministic_timestamps
+        ):
+            # block hashes depend on timestamps, keep them independent of the wall clock
+            self._chain_interface.set_block_timestamp_interval(1)
+
+        if self._genesis is not None:
+            self.apply_genesis(self._genesis)
+
     def _connect_finalize(self) -> None:
         connected_chains.remove(self)
         chain_interfaces_manager.free(self._chain_interface)
 
+    @check_connected
+    def apply_genesis(self, genesis: Union[str, Path, Dict[str, Any]]) -> None:
+        """
+        Set balances, code, nonces and storage of accounts (e.g. predeployed contracts) from a genesis allocation.
+        Accepts a path to a geth-style `genesis.json` file, a loaded genesis dictionary or an `alloc` dictionary alone.
+        """
+        if isinstance(genesis, (str, Path)):
+            genesis = json.loads(Path(genesis).read_text())
+        assert isinstance(genesis, dict)
+        alloc: Dict[str, Any] = genesis.get("alloc", genesis)
+
+        for address, account_alloc in alloc.items():
+            if not address.startswith(("0x", "0X")):
+                address = "0x" + address
+            account = Account(address, self)
+
+            if "balance" in account_alloc:
+                account.balance = _parse_genesis_int(account_alloc["balance"])
+            if "nonce" in account_alloc:
+                account.nonce = _parse_genesis_int(account_alloc["nonce"])
+            if "code" in account_alloc:
+                account.code = _parse_genesis_bytes(account_alloc["code"])
+            for slot, value in account_alloc.get("storage", {}).items():
+                self._chain_interface.set_storage_at(
+                    str(account.address),
+                    _parse_genesis_int(slot),
+                    _parse_genesis_bytes(value).rjust(32, b"\x00"),
+                )
+
+    @property
+    def random(self) -> Random:
+        """
+        Random number generator of the chain. Seeded deterministically per chain when connected using `connect_many`,
+        otherwise the global Wake random number generator.
+        """
+        return self._random if self._random is not None else random
+
     def _new_private_key(self, extra_entropy: bytes = b"") -> bytes:
-        data = random.getrandbits(256).to_bytes(32, "little") + extra_entropy
+        data = self.random.getrandbits(256).to_bytes(32, "little") + extra_entropy
         return keccak.new(data=data, digest_bits=256).digest()
 
     @check_connected
-    def snapshot(self) -> str:
-        snapshot_id = self._chain_interface.snapshot()
-
-        self._snapshots[snapshot_id] = {
-            "nonces": self._nonces.copy(),
-            "accounts": self._accounts.copy(),
+    def snapshot(self) -> Snapshot:
+        raw_snapshot_id = self._chain_interface.snapshot()
+        checkpoint = self._journal.checkpoint()
+        snapshot_id = self._new_snapshot(raw_snapshot_id)
+
+        # accounts lists are replaced, never modified in place, and cached transactions and blocks are truncated
+        # on revert, so nothing is copied here
+        self._snapshots[snapshot_id.id] = {
+            # the `Snapshot` object is not kept alive, its block is only resolved while it is referenced
+            "object": weakref.ref(snapshot_id),
+            "journal": checkpoint,
+            "accounts": self._accounts,
+            "accounts_set": self._accounts_set,
             "default_call_account": self._default_call_account,
             "default_tx_account": self._default_tx_account,
             "block_gas_limit": self._block_gas_limit,
-            "txs": dict(self._txs._transactions),
-            "tx_hashes": list(self._txs._tx_hashes),
-            "blocks": dict(self._blocks._blocks),
+            "tx_count": len(self._txs),
+            "chain_id": self._chain_id,
         }
+        self._run_snapshot_hooks(snapshot_id)
         return snapshot_id
 
     @check_connected
     def revert(self, snapshot_id: str) -> None:
-        reverted = self._chain_interface.revert(snapshot_id)
+        self._check_snapshot(snapshot_id)
+        # transactions collected for the batched callback are still readable before the revert
+        self.flush_tx_callback()
+        reverted = self._chain_interface.revert(str.__str__(snapshot_id))
         if not reverted:
             raise RevertToSnapshotFailedError()
 
+        self._revert_count += 1
+        self._drop_snapshots_after(snapshot_id)
         snapshot = self._snapshots[snapshot_id]
-        self._nonces = snapshot["nonces"]
+        self._journal.rollback(snapshot["journal"])
         self._accounts = snapshot["accounts"]
+        self._accounts_set = snapshot["accounts_set"]
         self._default_call_account = snapshot["default_call_account"]
         self._default_tx_account = snapshot["default_tx_account"]
         self._block_gas_limit = snapshot["block_gas_limit"]
-        self._txs._transactions = snapshot["txs"]
-        self._txs._tx_hashes = snapshot["tx_hashes"]
-        self._blocks._blocks = snapshot["blocks"]
+        self._txs._truncate(snapshot["tx_count"])
+        self._restore_chain_id(snapshot["chain_id"])
+        self._blocks._invalidate_head()
+        self._resolve_snapshot_block(snapshot)
+        # ENS records may have changed after the snapshot was taken
+        self._ens_cache.clear()
         del self._snapshots[snapshot_id]
+        self._run_revert_hooks(snapshot_id)
+
+    @contextmanager
+    def with_rollback(self):
+        """
+        Context manager rolling back all state changes made inside it, including transactions sent.
+        Intended for invariants probing the system with state-changing transactions without perturbing the test sequence.
+        The chain is always rolled back, even if the body raises. If the journal checkpoint of the context was evicted
+        because of `journal_limit`, `RevertToSnapshotFailedError` is raised after rolling back the chain
+        (unless the body raised), as locally tracked nonces and written storage slots could not be restored.
+        """
+        if not self._connected:
+            raise NotConnectedError("Not connected to a chain")
+
+        raw_snapshot_id = self._chain_interface.snapshot()
+        tx_count = len(self._txs)
+        snapshot_ids = set(self._snapshots.keys())
+        checkpoint = self._journal.checkpoint()
+        accounts = self._accounts
+        accounts_set = self._accounts_set
+        default_call_account = self._default_call_account
+        default_tx_account = self._default_tx_account
+        block_gas_limit = self._block_gas_limit
+        chain_id = self._chain_id
+
+        failed = False
+        try:
+            yield
+        except BaseException:
+            failed = True
+            raise
+        finally:
+            self.flush_tx_callback()
+            if not self._chain_interface.revert(raw_snapshot_id):
+                raise RevertToSnapshotFailedError()
+
+            self._revert_count += 1
+            # snapshots taken inside the context were invalidated by the revert
+            for snapshot_id in list(self._snapshots.keys()):
+                if snapshot_id not in snapshot_ids:
+                    del self._snapshots[snapshot_id]
+
+            evicted = not self._journal.has_checkpoint(checkpoint)
+            if evicted:
+                # older checkpoints were evicted as well, cached nonces are fetched again
+                self._journal.clear()
+                self._nonces.clear()
+                self._verified_libraries.clear()
+            else:
+                self._journal.rollback(checkpoint)
+            self._accounts = accounts
+            self._accounts_set = accounts_set
+            self._default_call_account = default_call_account
+            self._default_tx_account = default_tx_account
+            self._block_gas_limit = block_gas_limit
+            self._txs._truncate(tx_count)
+            self._restore_chain_id(chain_id)
+            self._blocks._invalidate_head()
+            self._ens_cache.clear()
+            self._run_revert_hooks(raw_snapshot_id)
+
+            if evicted and not failed:
+                raise RevertToSnapshotFailedError(
+                    "The chain was rolled back, but the journal exceeded chain.journal_limit, "
+                    "so written storage slots tracked inside the context could not be rolled back"
+                )
+
+    @check_connected
+    def set_beacon_root(
+        self,
+        root: Optional[bytes] = None,
+        *,
+        block: Union[int, Literal["latest"]] = "latest",
+    ) -> bytes:
+        """
+        Store the parent beacon block root of `block` in the [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) beacon roots contract,
+        so that contracts can read it by the block timestamp. The contract is deployed if missing.
+        Development chains do not follow a beacon chain, so the root defaults to the keccak256 hash of the block hash.
+
+        Returns:
+            Stored beacon root.
+        """
+        block_info = self._chain_interface.get_block(block)
+        timestamp = int(block_info["timestamp"], 16)
+        if root is None:
+            root = keccak.new(
+                data=bytes.fromhex(block_info["hash"][2:]), digest_bits=256
+            ).digest()
+        elif len(root) != 32:
+            raise ValueError("Beacon root must be 32 bytes long")
+
+        if len(self._chain_interface.get_code(BEACON_ROOTS_ADDRESS)) == 0:
+            self._chain_interface.set_code(BEACON_ROOTS_ADDRESS, _BEACON_ROOTS_CODE)
+
+        index = timestamp % _BEACON_ROOTS_HISTORY_LENGTH
+        self._chain_interface.set_storage_at(
+            BEACON_ROOTS_ADDRESS, index, timestamp.to_bytes(32, "big")
+        )
+        self._chain_interface.set_storage_at(
+            BEACON_ROOTS_ADDRESS, index + _BEACON_ROOTS_HISTORY_LENGTH, root
+        )
+        return root
+
+    @check_connected
+    def apply_withdrawals(self, withdrawals: Iterable[Withdrawal]) -> None:
+        """
+        Simulate [EIP-4895](https://eips.ethereum.org/EIPS/eip-4895) validator withdrawals by crediting the withdrawn
+        amounts to the recipients. As with real withdrawals, no code is executed and no gas is spent.
+        Withdrawals are not recorded in the block withdrawals list, which cannot be modified through JSON-RPC.
+        """
+        credits: Dict[Address, int] = defaultdict(int)
+        for withdrawal in withdrawals:
+            credits[Address(withdrawal.address)] += withdrawal.amount * 10**9
+
+        for address, amount in credits.items():
+            balance = self._chain_interface.get_balance(str(address))
+            self._chain_interface.set_balance(str(address), balance + amount)
+
+    @contextmanager
+    def gas_snapshot(self, name: str):
+        """
+        Sum gas used by all transactions sent from this chain inside the context manager and compare it
+        with the value recorded under `name` in the gas snapshot file (see `testing.gas_snapshot` config namespace).
+        """
+        if not self._connected:
+            raise NotConnectedError("Not connected to a chain")
+
+        tx_count = len(self._txs)
+        yield
+
+        if tx_count < self._txs._tx_hashes_offset:
+            raise RuntimeError(
+                f"Cannot compute gas snapshot '{name}', {self._txs._tx_hashes_offset - tx_count} of the transactions "
+                "were pruned from history. Increase chain.txs.history_limit."
+            )
+        gas_used = sum(
+            self._txs[i].adjusted_gas_used for i in range(tx_count, len(self._txs))
+        )
+        gas_snapshots.check(name, gas_used)
 
     @property
     @check_connected
@@ -131,6 +369,129 @@ class Chain(wake.development.core.Chain):
         self._chain_interface.set_block_gas_limit(value)
         self._block_gas_limit = value
 
+    @property
+    @check_connected
+    def block_gas_limit_policy(self) -> Literal["split", "reject", "mine"]:
+        """
+        What happens when automine is disabled and a transaction does not fit into the pending block:
+
+        - `split` - the transaction is left to the client to be included in one of the next blocks,
+        - `reject` - `BlockGasLimitExceeded` is raised and the transaction is not sent,
+        - `mine` - the pending block is mined before the transaction is sent.
+        """
+        return self._block_gas_limit_policy
+
+    @block_gas_limit_policy.setter
+    @check_connected
+    def block_gas_limit_policy(
+        self, value: Literal["split", "reject", "mine"]
+    ) -> None:
+        if value not in {"split", "reject", "mine"}:
+            raise ValueError("Invalid block gas limit policy")
+        self._block_gas_limit_policy = value
+
+    @property
+    @check_connected
+    def tx_ordering(
+        self,
+    ) -> Optional[
+        Union[
+            Literal["fees", "fifo"], Callable[[TransactionAbc, TransactionAbc], int]
+        ]
+    ]:
+        """
+        Order in which pending transactions are included into a block when it is mined with automine disabled:
+
+        - `None` - the order is left to the client,
+        - `fees` - transactions paying a higher priority fee are included first,
+        - `fifo` - transactions are included in the order they were sent,
+        - a comparator returning a negative number if the first transaction should be included before the second one.
+
+        Transactions from the same sender are always included in nonce order. Only transactions sent from this `Chain` instance are reordered.
+        """
+        return self._tx_ordering
+
+    @tx_ordering.setter
+    @check_connected
+    def tx_ordering(
+        self,
+        value: Optional[
+            Union[
+                Literal["fees", "fifo"],
+                Callable[[TransactionAbc, TransactionAbc], int],
+            ]
+        ],
+    ) -> None:
+        if value is not None and value not in {"fees", "fifo"} and not callable(value):
+            raise ValueError("Invalid transaction ordering")
+        if value is not None and not isinstance(
+            self._chain_interface, (AnvilChainInterface, HardhatChainInterface)
+        ):
+            raise NotImplementedError(
+                "Transaction ordering is only supported with Anvil and Hardhat"
+            )
+        self._tx_ordering = value
+
+    @check_connected
+    def mine(self, timestamp_change: Optional[Callable[[int], int]] = None) -> None:
+        if self._tx_ordering is not None:
+            self._reorder_pending_txs()
+        super().mine(timestamp_change)
+
+    @check_connected
+    def mine_many(
+        self, num_blocks: int, timestamp_change: Optional[int] = None
+    ) -> None:
+        if self._tx_ordering is not None:
+            self._reorder_pending_txs()
+        super().mine_many(num_blocks, timestamp_change)
+
+    def _reorder_pending_txs(self) -> None:
+        assert isinstance(
+            self._chain_interface, (AnvilChainInterface, HardhatChainInterface)
+        )
+        assert self._tx_ordering is not None
+
+        block_info = self._chain_interface.get_block("pending")
+        pending = [tx_hash.lower() for tx_hash in block_info["transactions"]]
+        known = self._txs._transactions
+        # transactions in the order they were sent
+        txs = [
+            known[tx_hash.lower()]
+            for tx_hash in self._txs._tx_hashes
+            if tx_hash.lower() in known and tx_hash.lower() in pending
+        ]
+        if len(txs) < 2:
+            return
+
+        ordered = _order_transactions(
+            txs, self._tx_ordering, int(block_info.get("baseFeePerGas", "0x0"), 16)
+        )
+        ordered_hashes = [tx.tx_hash.lower() for tx in ordered]
+        if ordered_hashes == [tx_hash for tx_hash in pending if tx_hash in known]:
+            return
+
+        for tx in txs:
+            self._chain_interface.drop_transaction(tx.tx_hash)
+
+        # the client is expected to keep the submission order of re-sent transactions
+        for tx in ordered:
+            tx_params = cast(TxParams, dict(tx._tx_params))
+            tx_hash = self._submit_transaction(tx_params, Address(tx_params["from"]))
+            if tx_hash.lower() != tx.tx_hash.lower():
+                raise RuntimeError(
+                    f"Re-sent transaction {tx.tx_hash} got a different hash {tx_hash}"
+                )
+
+        # Anvil orders transactions by priority fees by default, only keeping the submission order of equal fees
+        block_info = self._chain_interface.get_block("pending")
+        pending = [tx_hash.lower() for tx_hash in block_info["transactions"]]
+        if [tx_hash for tx_hash in pending if tx_hash in known] != ordered_hashes:
+            raise RuntimeError(
+                "Client did not keep the submission order of pending transactions, "
+                "add `--order fifo` to `testing.anvil.cmd_args` or send all transactions with the same priority fee"
+            )
+
     @property
     @check_connected
     def gas_price(self) -> Wei:
@@ -138,7 +499,7 @@ class Chain(wake.development.core.Chain):
 
     @gas_price.setter
     @check_connected
-    def gas_price(self, value: int) -> None:
+    def gas_price(self, value: Union[int, str]) -> None:
         self._gas_price = Wei(value)
 
     @property
@@ -148,7 +509,7 @@ class Chain(wake.development.core.Chain):
 
     @max_priority_fee_per_gas.setter
     @check_connected
-    def max_priority_fee_per_gas(self, value: int) -> None:
+    def max_priority_fee_per_gas(self, value: Union[int, str]) -> None:
         self._max_priority_fee_per_gas = Wei(value)
 
     def _build_transaction(
@@ -158,8 +519,12 @@ class Chain(wake.development.core.Chain):
         arguments: Iterable,
         abi: Optional[Dict],
     ) -> TxParams:
+        view = self._in_view_request()
+        if not view:
+            self._last_gas_estimate = None
+        gas_estimate: Optional[int] = None
         tx_type = params.get("type", self._default_tx_type)
-        if tx_type not in {0, 1, 2}:
+        if tx_type not in {0, 1, 2, 4}:
             raise ValueError("Invalid transaction type")
 
         if tx_type == 0 and (
@@ -176,8 +541,23 @@ class Chain(wake.development.core.Chain):
             raise ValueError(
                 "Cannot specify maxFeePerGas or maxPriorityFeePerGas for type 1 transaction"
             )
-        elif tx_type == 2 and "gasPrice" in params:
-            raise ValueError("Cannot specify gasPrice for type 2 transaction")
+        elif tx_type in {2, 4} and "gasPrice" in params:
+            raise ValueError(f"Cannot specify gasPrice for type {tx_type} transaction")
+
+        if tx_type == 4:
+            if not params.get("authorizationList"):
+                raise ValueError(
+                    "authorizationList must be specified and non-empty for type 4 transaction"
+                )
+            if "to" not in params:
+                raise ValueError("Type 4 transaction cannot create a contract")
+        elif "authorizationList" in params:
+            raise ValueError(
+                f"Cannot specify authorizationList for type {tx_type} transaction"
+            )
+
+        if request_type == "tx":
+            self._check_fee_caps(params)
 
         if "from" in params:
             sender = params["from"]
@@ -206,19 +586,16 @@ class Chain(wake.development.core.Chain):
         if abi is None:
             params["data"] += Abi.encode([], [])
         else:
-            arguments = [self._convert_to_web3_type(arg) for arg in arguments]
-            types = [
-                eth_utils.abi.collapse_if_tuple(cast(Dict[str, Any], arg))
-                for arg in fix_library_abi(abi["inputs"])
-            ]
-            params["data"] += Abi.encode(types, arguments)
+            params["data"] += self._encode_arguments(abi, arguments)
 
         tx: TxParams = {
-            "nonce": self._nonces[Address(sender)],
             "from": sender,
             "value": params["value"] if "value" in params else 0,
             "data": params["data"],
         }
+        if not view:
+            # calls do not need a nonce, views must not insert it into the journaled nonces
+            tx["nonce"] = self._nonces[Address(sender)]
         if tx_type != 0:
             tx["type"] = tx_type
 
@@ -238,12 +615,14 @@ class Chain(wake.development.core.Chain):
             tx["gasPrice"] = (
                 params["gasPrice"] if "gasPrice" in params else self._gas_price
             )
-        elif tx_type == 2:
+        elif tx_type in {2, 4}:
             if "accessList" not in params:
                 tx["accessList"] = []
             elif params["accessList"] != "auto":
                 tx["accessList"] = params["accessList"]
             tx["chainId"] = self._chain_id
+            if tx_type == 4:
+                tx["authorizationList"] = params["authorizationList"]
             tx["maxPriorityFeePerGas"] = (
                 params["maxPriorityFeePerGas"]
                 if "maxPriorityFeePerGas" in params
@@ -270,14 +649,17 @@ class Chain(wake.development.core.Chain):
         elif params["gas"] == "auto":
             # auto
             try:
-                tx["gas"] = int(self._chain_interface.estimate_gas(tx) * 1.1)
+                gas_estimate = self._chain_interface.estimate_gas(tx)
+                tx["gas"] = int(
+                    gas_estimate * get_config().general.gas_limit_multiplier
+                )
             except JsonRpcError as e:
                 raise self._process_call_revert(e) from None
         else:
             raise ValueError(f"Invalid gas value: {params['gas']}")
 
         if (
-            tx_type in {1, 2}
+            tx_type in {1, 2, 4}
             and "accessList" in params
             and params["accessList"] == "auto"
         ):
@@ -286,10 +668,14 @@ class Chain(wake.development.core.Chain):
                 tx["accessList"] = response["accessList"]
 
                 if "gas" in params and params["gas"] == "auto":
-                    tx["gas"] = int(response["gasUsed"], 16)
+                    # gas used with the access list applied, no multiplier
+                    gas_estimate = int(response["gasUsed"], 16)
+                    tx["gas"] = gas_estimate
             except JsonRpcError as e:
                 raise self._process_call_revert(e) from None
 
+        if not view:
+            self._last_gas_estimate = gas_estimate
         return tx
 
     def _wait_for_transaction(
@@ -310,7 +696,23 @@ class Chain(wake.development.core.Chain):
             pass
 
     def _confirm_transaction(self, tx: TxParams) -> None:
-        pass
+        if self._block_gas_limit_policy == "split" or self.automine:
+            return
+
+        assert "gas" in tx and isinstance(tx["gas"], int)
+        if tx["gas"] > self._block_gas_limit:
+            raise BlockGasLimitExceeded(tx["gas"], 0, self._block_gas_limit)
+
+        block_info = self._chain_interface.get_block("pending")
+        assert "gasUsed" in block_info
+        pending_gas_used = int(block_info["gasUsed"], 16)
+
+        if pending_gas_used + tx["gas"] > self._block_gas_limit:
+            if self._block_gas_limit_policy == "reject":
+                raise BlockGasLimitExceeded(
+                    tx["gas"], pending_gas_used, self._block_gas_limit
+                )
+            self.mine()
 
 
 default_chain = Chain()
@@ -319,3 +721,94 @@ connected_chains: List[Chain] = []
 
 def get_connected_chains() -> Tuple[Chain, ...]:
     return tuple(connected_chains)
+
+
+def _parse_genesis_int(value: Union[int, str]) -> int:
+    if isinstance(value, int):
+        return value
+    return int(value, 16) if value.startswith(("0x", "0X")) else int(value)
+
+
+def _parse_genesis_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, bytes):
+        return value
+    if value.startswith(("0x", "0X")):
+        value = value[2:]
+    return bytes.fromhex(value.zfill(len(value) + len(value) % 2))
+
+
+def _order_transactions(
+    txs: List[TransactionAbc],
+    ordering: Union[
+        Literal["fees", "fifo"], Callable[[TransactionAbc, TransactionAbc], int]
+    ],
+    base_fee: int,
+) -> List[TransactionAbc]:
+    index = {tx.tx_hash: i for i, tx in enumerate(txs)}
+
+    def priority_fee(tx: TransactionAbc) -> int:
+        params = tx._tx_params
+        if "maxPriorityFeePerGas" in params:
+            return min(
+                params["maxPriorityFeePerGas"], params["maxFeePerGas"] - base_fee
+            )
+        return params.get("gasPrice", 0) - base_fee
+
+    if ordering == "fifo":
+        key = lambda tx: index[tx.tx_hash]
+    elif ordering == "fees":
+        key = lambda tx: (-priority_fee(tx), index[tx.tx_hash])
+    else:
+        key = functools.cmp_to_key(ordering)
+
+    queues: Dict[Address, List[TransactionAbc]] = {}
+    for tx in txs:
+        queues.setdefault(Address(tx._tx_params["from"]), []).append(tx)
+    for queue in queues.values():
+        queue.sort(key=lambda tx: tx._tx_params["nonce"])
+
+    # pick the best transaction among the lowest-nonce transactions of all senders
+    ordered = []
+    while len(queues) > 0:
+        sender = min(queues, key=lambda s: key(queues[s][0]))
+        ordered.append(queues[sender].pop(0))
+        if len(queues[sender]) == 0:
+            del queues[sender]
+    return ordered
+
+
+@contextmanager
+def connect_many(
+    **chains: Union[Chain, Tuple[Chain, Dict[str, Any]]]
+) -> Iterator[Tuple[Chain, ...]]:
+    """
+    Connect multiple chains and disconnect all of them on exit, even if an exception is raised.
+    Each keyword argument is either a `Chain` instance or a tuple of a `Chain` instance and keyword arguments
+    passed to `Chain.connect`. The connected chains are yielded in the order of the keyword arguments.
+
+    Every chain is assigned its own random number generator (see `Chain.random`) seeded from the global
+    random number generator and the keyword argument name.
+    """
+    seed = random.getrandbits(256).to_bytes(32, "little")
+
+    with ExitStack() as stack:
+        connected: List[Chain] = []
+        for name, value in chains.items():
+            if isinstance(value, tuple):
+                c, kwargs = value
+            else:
+                c, kwargs = value, {}
+
+            if any(c is other for other in connected):
+                raise ValueError(
+                    f"Chain passed to connect_many multiple times ({name})"
+                )
+
+            c._random = Random(
+                keccak.new(data=seed + name.encode("utf-8"), digest_bits=256).digest()
+            )
+            stack.callback(setattr, c, "_random", None)
+            stack.enter_context(c.connect(**kwargs))
+            connected.append(c)
+
+        yield tuple(connected)
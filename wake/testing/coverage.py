@@ -5,6 +5,7 @@ import json
 import logging
 import pathlib
 import re
+import threading
 import time
 from collections import ChainMap, defaultdict
 from dataclasses import asdict, dataclass, field
@@ -334,6 +335,17 @@ def returning_none():
     return None
 
 
+@dataclass(frozen=True)
+class CoverageDelta:
+    """
+    Number of statement, function and modifier hits recorded since the previous coverage sync.
+    """
+
+    statement_hits: int
+    function_hits: int
+    modifier_hits: int
+
+
 class CoverageHandler:
     _pc_maps: Dict[str, Dict[int, SourceMapPcRecord]]
     _pc_maps_undeployed: Dict[str, Dict[int, SourceMapPcRecord]]
@@ -349,6 +361,13 @@ class CoverageHandler:
         Optional[Tuple[Union[StatementAbc, YulStatementAbc], int]],
     ]
     _callback: Optional[Callable]
+    _lock: threading.RLock
+    # serializes invocations of the callback
+    _sync_lock: threading.Lock
+    _sync_timeout: float
+    _last_sync: float
+    # total statement, function and modifier hits at the last sync
+    _synced_hits: Tuple[int, int, int]
 
     def __init__(self, config: WakeConfig):
         compiler = SolidityCompiler(config)
@@ -368,6 +387,11 @@ class CoverageHandler:
         self._visited_modifiers = set()
         self._last_statements = defaultdict(returning_none)
         self._callback = None
+        self._lock = threading.RLock()
+        self._sync_lock = threading.Lock()
+        self._sync_timeout = 5.0
+        self._last_sync = time.perf_counter()
+        self._synced_hits = (0, 0, 0)
 
         errored = False
         for cu in compiler.latest_build_info.compilation_units.values():
@@ -401,8 +425,65 @@ class CoverageHandler:
     def set_callback(self, callback: Callable) -> None:
         self._callback = callback
 
+    @property
+    def sync_timeout(self) -> float:
+        """
+        Minimum time in seconds between two automatic invocations of the coverage callback.
+        """
+        return self._sync_timeout
+
+    @sync_timeout.setter
+    def sync_timeout(self, value: float) -> None:
+        if value < 0:
+            raise ValueError("sync_timeout must be non-negative")
+        self._sync_timeout = value
+
+    def sync(self) -> CoverageDelta:
+        """
+        Invoke the coverage callback on the calling thread regardless of `sync_timeout`.
+
+        Returns:
+            Hits recorded since the previous sync.
+        """
+        delta = self._sync(force=True)
+        assert delta is not None
+        return delta
+
+    def _sync(self, force: bool) -> Optional[CoverageDelta]:
+        with self._sync_lock:
+            if not force and time.perf_counter() - self._last_sync < self._sync_timeout:
+                # already synced by another thread
+                return None
+
+            with self._lock:
+                hits = (
+                    sum(self._statement_coverage.values()),
+                    sum(self._function_coverage.values()),
+                    sum(self._modifier_coverage.values()),
+                )
+                delta = CoverageDelta(*(h - s for h, s in zip(hits, self._synced_hits)))
+                self._synced_hits = hits
+                self._last_sync = time.perf_counter()
+
+            # the callback may block (e.g. sending to another process), recording coverage only waits for `_lock`
+            if self._callback is not None:
+                self._callback()
+            return delta
+
     def add_coverage(
         self, params: TxParams, chain: Chain, debug_trace: Dict[str, Any]
+    ) -> None:
+        with self._lock:
+            self._add_coverage(params, chain, debug_trace)
+
+        if (
+            self._callback is not None
+            and time.perf_counter() - self._last_sync >= self._sync_timeout
+        ):
+            self._sync(force=False)
+
+    def _add_coverage(
+        self, params: TxParams, chain: Chain, debug_trace: Dict[str, Any]
     ) -> None:
         fqn_overrides: ChainMap[Address, Optional[str]] = ChainMap()
         # TODO process fqn overrides for tx: process txs in the same block before the given tx
@@ -440,15 +521,18 @@ class CoverageHandler:
                 is_from_deployment=False,
             )
 
-        if self._callback is not None:
-            self._callback()
-
     def get_contract_ide_coverage(
         self,
     ) -> Dict[pathlib.Path, Dict[IdePosition, IdeFunctionCoverageRecord]]:
         """
         Returns coverage data for IDE usage
         """
+        with self._lock:
+            return self._get_contract_ide_coverage()
+
+    def _get_contract_ide_coverage(
+        self,
+    ) -> Dict[pathlib.Path, Dict[IdePosition, IdeFunctionCoverageRecord]]:
         cov_data = {}
         for func, func_count in chain(
             self._function_coverage.items(), self._modifier_coverage.items()
@@ -1,13 +1,14 @@
 from __future__ import annotations
 
+import hashlib
 from collections import defaultdict
-from typing import Callable, DefaultDict, List, Optional
+from typing import Callable, DefaultDict, Dict, List, Optional, Tuple
 
 from typing_extensions import get_type_hints
 
 from wake.development.globals import random
 
-from ..core import get_connected_chains
+from ..core import Chain, get_connected_chains
 from .generators import generate
 
 
@@ -41,6 +42,9 @@ def invariant(*, period: int = 1):
 class FuzzTest:
     _sequence_num: int
     _flow_num: int
+    _path_buckets: DefaultDict[str, DefaultDict[bytes, int]]
+    # bucket => number of executions of all flows reaching the bucket
+    _path_bucket_counts: DefaultDict[bytes, int]
 
     @property
     def sequence_num(self):
@@ -50,6 +54,38 @@ class FuzzTest:
     def flow_num(self):
         return self._flow_num
 
+    @property
+    def path_buckets(self) -> Dict[str, Dict[bytes, int]]:
+        """
+        Number of executions of each flow (by name) per call path bucket, collected when `bias_rare_paths` is enabled.
+        """
+        return {k: dict(v) for k, v in self._path_buckets.items()}
+
+    def __flow_path_bucket(
+        self, chains: Tuple[Chain, ...], tx_counts: List[int]
+    ) -> bytes:
+        h = hashlib.blake2b(digest_size=16)
+        for chain, tx_count in zip(chains, tx_counts):
+            for i in range(tx_count, len(chain.txs)):
+                try:
+                    h.update(chain.txs[i].call_path_hash)
+                except IndexError:
+                    # pruned from history
+                    pass
+        return h.digest()
+
+    def __flow_weight(self, flow: Callable) -> float:
+        buckets = self._path_buckets[flow.__name__]
+        executions = sum(buckets.values())
+        if executions == 0:
+            return getattr(flow, "weight")
+        # average rarity of the buckets reached by the flow, buckets reached often (by any flow) are worth less
+        rarity = (
+            sum(n / self._path_bucket_counts[b] for b, n in buckets.items())
+            / executions
+        )
+        return getattr(flow, "weight") * rarity
+
     def __get_methods(self, attr: str) -> List[Callable]:
         ret = []
         for x in dir(self):
@@ -65,8 +101,11 @@ class FuzzTest:
         flows_count: int,
         *,
         dry_run: bool = False,
+        bias_rare_paths: bool = False,
     ):
         chains = get_connected_chains()
+        self._path_buckets = defaultdict(lambda: defaultdict(int))
+        self._path_bucket_counts = defaultdict(int)
 
         flows: List[Callable] = self.__get_methods("flow")
         invariants: List[Callable] = self.__get_methods("invariant")
@@ -95,7 +134,10 @@ class FuzzTest:
                         or getattr(f, "precondition")(self)
                     )
                 ]
-                weights = [getattr(f, "weight") for f in valid_flows]
+                if bias_rare_paths:
+                    weights = [self.__flow_weight(f) for f in valid_flows]
+                else:
+                    weights = [getattr(f, "weight") for f in valid_flows]
                 if len(valid_flows) == 0:
                     max_times_flows = [
                         f
@@ -121,8 +163,13 @@ class FuzzTest:
 
                 self._flow_num = j
                 self.pre_flow(flow)
+                tx_counts = [len(chain.txs) for chain in chains]
                 flow(self, *flow_params)
                 flows_counter[flow] += 1
+                if bias_rare_paths:
+                    bucket = self.__flow_path_bucket(chains, tx_counts)
+                    self._path_buckets[flow.__name__][bucket] += 1
+                    self._path_bucket_counts[bucket] += 1
                 self.post_flow(flow)
 
                 if not dry_run:
@@ -5,7 +5,6 @@ import multiprocessing.synchronize
 import os
 import pickle
 import sys
-import time
 import types
 from contextlib import redirect_stderr, redirect_stdout
 from pathlib import Path
@@ -24,6 +23,7 @@ from wake.development.globals import (
     random,
     set_coverage_handler,
     set_exception_handler,
+    set_random_seed,
 )
 from wake.testing.coverage import (
     CoverageHandler,
@@ -78,19 +78,14 @@ def _run(
             finished_event.set()
 
     exception_handled = False
-    last_coverage_sync = time.perf_counter()
-
     def coverage_callback() -> None:
-        nonlocal last_coverage_sync
-        t = time.perf_counter()
-        if coverage is not None and t - last_coverage_sync > 5:
+        if coverage is not None:
             cov_child_conn.send(coverage.get_contract_ide_coverage())
-            last_coverage_sync = t
 
     ctx_managers = []
 
     pickling_support.install()
-    random.seed(random_seed)
+    set_random_seed(random_seed)
 
     set_exception_handler(exception_handler)
     if coverage is not None:
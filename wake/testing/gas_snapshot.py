@@ -0,0 +1,81 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import Dict, Optional
+
+from wake.development.globals import get_config
+
+
+class GasRegressionError(AssertionError):
+    name: str
+    recorded: int
+    actual: int
+    tolerance: float
+
+    def __init__(self, name: str, recorded: int, actual: int, tolerance: float):
+        self.name = name
+        self.recorded = recorded
+        self.actual = actual
+        self.tolerance = tolerance
+        super().__init__(
+            f"Gas snapshot '{name}' regressed from {recorded} to {actual} "
+            f"(+{actual - recorded}, tolerance {tolerance:.2%})"
+        )
+
+
+class GasSnapshots:
+    _path: Optional[Path]
+    _recorded: Optional[Dict[str, int]]
+
+    def __init__(self):
+        self._path = None
+        self._recorded = None
+
+    def _load(self) -> Dict[str, int]:
+        path = get_config().testing.gas_snapshot.path
+        if self._recorded is None or self._path != path:
+            self._path = path
+            if path.is_file():
+                self._recorded = {
+                    k: int(v) for k, v in json.loads(path.read_text()).items()
+                }
+            else:
+                self._recorded = {}
+        return self._recorded
+
+    def _save(self) -> None:
+        assert self._path is not None and self._recorded is not None
+
+        # merge with values written in the meantime (e.g. by other test processes)
+        if self._path.is_file():
+            current = json.loads(self._path.read_text())
+        else:
+            current = {}
+        current.update(self._recorded)
+        self._path.write_text(
+            json.dumps(dict(sorted(current.items())), indent=4) + "\n"
+        )
+
+    def get(self, name: str) -> Optional[int]:
+        return self._load().get(name, None)
+
+    def check(self, name: str, gas_used: int) -> None:
+        """
+        Compare `gas_used` with the recorded value for `name`. Record the value if not recorded yet or if
+        updating snapshots is enabled in the config. Raise `GasRegressionError` on regression beyond the configured tolerance.
+        """
+        config = get_config().testing.gas_snapshot
+        recorded = self._load()
+
+        if name not in recorded or config.update:
+            if recorded.get(name, None) != gas_used:
+                recorded[name] = gas_used
+                self._save()
+            return
+
+        if gas_used > recorded[name] * (1 + config.tolerance):
+            raise GasRegressionError(name, recorded[name], gas_used, config.tolerance)
+
+
+gas_snapshots = GasSnapshots()
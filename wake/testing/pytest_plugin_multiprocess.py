@@ -5,7 +5,6 @@ import pickle
 import queue
 import signal
 import sys
-import time
 from contextlib import redirect_stderr, redirect_stdout
 from pathlib import Path
 from types import TracebackType
@@ -20,10 +19,10 @@ from wake.cli.console import console
 from wake.development.globals import (
     attach_debugger,
     chain_interfaces_manager,
-    random,
     reset_exception_handled,
     set_coverage_handler,
     set_exception_handler,
+    set_random_seed,
 )
 from wake.testing.coverage import CoverageHandler
 from wake.utils.tee import StderrTee, StdoutTee
@@ -158,12 +157,8 @@ class PytestWakePluginMultiprocess:
         if session.config.option.collectonly:
             return True
 
-        last_coverage_sync = time.perf_counter()
-
         def coverage_callback() -> None:
-            nonlocal last_coverage_sync
-            t = time.perf_counter()
-            if self._coverage is not None and t - last_coverage_sync > 5:
+            if self._coverage is not None:
                 try:
                     self._queue.put(
                         (
@@ -173,7 +168,6 @@ class PytestWakePluginMultiprocess:
                         ),
                         timeout=0.125,
                     )
-                    last_coverage_sync = t
                 except queue.Full:
                     pass
 
@@ -193,7 +187,7 @@ class PytestWakePluginMultiprocess:
             indexes = self._conn.recv()
             for i in range(len(indexes)):
                 # set random seed before each test item
-                random.seed(self._random_seed)
+                set_random_seed(self._random_seed)
                 console.print(f"Setting random seed '{self._random_seed.hex()}'")
 
                 item = session.items[indexes[i]]
@@ -8,10 +8,10 @@ from wake.development.globals import (
     attach_debugger,
     chain_interfaces_manager,
     get_coverage_handler,
-    random,
     reset_exception_handled,
     set_coverage_handler,
     set_exception_handler,
+    set_random_seed,
 )
 from wake.testing.coverage import (
     CoverageHandler,
@@ -60,7 +60,7 @@ class PytestWakePluginSingle:
 
         coverage = self._cov_proc_count == 1 or self._cov_proc_count == -1
 
-        random.seed(self._random_seeds[0])
+        set_random_seed(self._random_seeds[0])
         console.print(f"Using random seed '{self._random_seeds[0].hex()}'")
 
         if self._debug:
@@ -0,0 +1,268 @@
+from typing import Any, Callable, Dict, List, NamedTuple, Optional, Set, Tuple
+
+from .keyed_default_dict import KeyedDefaultDict
+
+_MISSING = object()
+
+
+class JournalStats(NamedTuple):
+    entries: int
+    """Number of undo entries currently held by the journal."""
+    checkpoints: int
+    """Number of active checkpoints."""
+    recorded: int
+    """Total number of undo entries recorded."""
+    rolled_back: int
+    """Total number of undo entries applied by rollbacks."""
+    evicted: int
+    """Total number of checkpoints evicted because the journal exceeded its limit."""
+
+
+class Journal:
+    """
+    Undo log of changes made to journaled collections. Changes are only recorded while at least one checkpoint exists
+    and only the first change of an entry between two checkpoints is recorded, so that taking a checkpoint does not copy
+    the collections and rolling back only undoes the changed entries.
+
+    If `limit` is set, the oldest checkpoints are evicted (and cannot be rolled back anymore) whenever the journal would
+    hold more than `limit` entries. `on_evict` is called with the evicted checkpoints.
+    """
+
+    _limit: Optional[int]
+    _on_evict: Optional[Callable[[List[int]], None]]
+
+    _entries: List[Tuple[Any, Any, Any]]
+    # (collection ID, key) pairs recorded since the latest checkpoint
+    _recorded_keys: Set[Tuple[int, Any]]
+    _checkpoints: Dict[int, int]
+    _next_checkpoint: int
+    _recorded: int
+    _rolled_back: int
+    _evicted: int
+
+    def __init__(self, on_evict: Optional[Callable[[List[int]], None]] = None):
+        self._limit = None
+        self._on_evict = on_evict
+        self._entries = []
+        self._recorded_keys = set()
+        self._checkpoints = {}
+        self._next_checkpoint = 0
+        self._recorded = 0
+        self._rolled_back = 0
+        self._evicted = 0
+
+    @property
+    def stats(self) -> JournalStats:
+        return JournalStats(
+            len(self._entries),
+            len(self._checkpoints),
+            self._recorded,
+            self._rolled_back,
+            self._evicted,
+        )
+
+    @property
+    def limit(self) -> Optional[int]:
+        return self._limit
+
+    @limit.setter
+    def limit(self, value: Optional[int]) -> None:
+        self._limit = value
+        if value is not None and len(self._entries) > value:
+            self._evict()
+
+    def has_checkpoint(self, checkpoint: int) -> bool:
+        return checkpoint in self._checkpoints
+
+    def checkpoint(self) -> int:
+        checkpoint = self._next_checkpoint
+        self._next_checkpoint += 1
+        self._checkpoints[checkpoint] = len(self._entries)
+        self._recorded_keys = set()
+        return checkpoint
+
+    def record(self, collection: Any, key: Any, old: Any) -> None:
+        if len(self._checkpoints) == 0:
+            return
+        # only the first change of an entry since the latest checkpoint is needed to roll back
+        recorded_key = (id(collection), key)
+        if recorded_key in self._recorded_keys:
+            return
+        self._recorded_keys.add(recorded_key)
+        self._entries.append((collection, key, old))
+        self._recorded += 1
+
+        if self._limit is not None and len(self._entries) > self._limit:
+            self._evict()
+
+    def release(self, checkpoint: int) -> None:
+        """
+        Discard `checkpoint` without undoing any changes. Entries only needed by released checkpoints are dropped
+        by `compact`, which is run automatically once they make up at least half of the journal.
+        """
+        del self._checkpoints[checkpoint]
+        if 2 * self._compactable() >= len(self._entries):
+            self.compact()
+
+    def compact(self) -> None:
+        """
+        Drop entries recorded before the oldest checkpoint, as no checkpoint can roll them back anymore.
+        """
+        position = self._compactable()
+        if position == 0:
+            return
+        del self._entries[:position]
+        for c in self._checkpoints:
+            self._checkpoints[c] -= position
+        if len(self._checkpoints) == 0:
+            self._recorded_keys = set()
+
+    def clear(self) -> None:
+        """
+        Discard all checkpoints and entries without undoing any changes.
+        """
+        self._entries = []
+        self._checkpoints = {}
+        self._recorded_keys = set()
+
+    def _evict(self) -> None:
+        assert self._limit is not None
+        evicted = []
+        # checkpoints are numbered in the order they were taken
+        while (
+            len(self._checkpoints) > 0
+            and len(self._entries) - self._compactable() > self._limit
+        ):
+            checkpoint = min(self._checkpoints)
+            del self._checkpoints[checkpoint]
+            evicted.append(checkpoint)
+            self._evicted += 1
+        self.compact()
+
+        if len(evicted) > 0 and self._on_evict is not None:
+            self._on_evict(evicted)
+
+    def _compactable(self) -> int:
+        if len(self._checkpoints) == 0:
+            return len(self._entries)
+        return min(self._checkpoints.values())
+
+    def rollback(self, checkpoint: int) -> None:
+        """
+        Undo all changes recorded since `checkpoint`. The checkpoint and all checkpoints taken after it are discarded.
+        """
+        position = self._checkpoints[checkpoint]
+        for collection, key, old in reversed(self._entries[position:]):
+            collection._undo(key, old)
+        self._rolled_back += len(self._entries) - position
+        del self._entries[position:]
+
+        self._recorded_keys = set()
+
+        for c in [c for c in self._checkpoints if c >= checkpoint]:
+            del self._checkpoints[c]
+
+
+class JournaledKeyedDefaultDict(KeyedDefaultDict):
+    """
+    `KeyedDefaultDict` recording item assignments (including values created by the default factory)
+    and deletions into a `Journal`. Other mutating methods are not journaled.
+    """
+
+    _journal: Journal
+
+    def __init__(self, journal: Journal, default_factory):
+        super().__init__(default_factory)
+        self._journal = journal
+
+    def __setitem__(self, key, value) -> None:
+        self._journal.record(self, key, dict.get(self, key, _MISSING))
+        super().__setitem__(key, value)
+
+    def __delitem__(self, key) -> None:
+        self._journal.record(self, key, dict.__getitem__(self, key))
+        super().__delitem__(key)
+
+    def _undo(self, key, old) -> None:
+        if old is _MISSING:
+            dict.pop(self, key, None)
+        else:
+            dict.__setitem__(self, key, old)
+
+
+class JournaledDict(dict):
+    """
+    Dict recording item assignments and deletions into a `Journal`. Other mutating methods are not journaled.
+    """
+
+    _journal: Journal
+
+    def __init__(self, journal: Journal):
+        super().__init__()
+        self._journal = journal
+
+    def __setitem__(self, key, value) -> None:
+        self._journal.record(self, key, dict.get(self, key, _MISSING))
+        super().__setitem__(key, value)
+
+    def __delitem__(self, key) -> None:
+        self._journal.record(self, key, dict.__getitem__(self, key))
+        super().__delitem__(key)
+
+    def _undo(self, key, old) -> None:
+        if old is _MISSING:
+            dict.pop(self, key, None)
+        else:
+            dict.__setitem__(self, key, old)
+
+
+class JournaledSet(set):
+    """
+    Set recording `add`, `discard` and `remove` calls into a `Journal`. Other mutating methods are not journaled.
+    """
+
+    _journal: Journal
+
+    def __init__(self, journal: Journal):
+        super().__init__()
+        self._journal = journal
+
+    def add(self, item) -> None:
+        if item not in self:
+            self._journal.record(self, item, False)
+            super().add(item)
+
+    def discard(self, item) -> None:
+        if item in self:
+            self._journal.record(self, item, True)
+            super().discard(item)
+
+    def remove(self, item) -> None:
+        if item not in self:
+            raise KeyError(item)
+        self.discard(item)
+
+    def _undo(self, item, present: bool) -> None:
+        if present:
+            set.add(self, item)
+        else:
+            set.discard(self, item)
+
+
+class JournaledList(list):
+    """
+    List recording `append` calls into a `Journal`. Other mutating methods are not journaled.
+    """
+
+    _journal: Journal
+
+    def __init__(self, journal: Journal):
+        super().__init__()
+        self._journal = journal
+
+    def append(self, item) -> None:
+        self._journal.record(self, len(self), None)
+        super().append(item)
+
+    def _undo(self, length: int, _) -> None:
+        del self[length:]